@@ -0,0 +1,186 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+// An atomically reference counted pointer without support for weak
+// references, used for tree nodes when the `light-arc` feature is on.
+//
+// Leaving out the weak count saves a word per allocation, and an atomic
+// operation every time `make_mut` checks for uniqueness.
+
+#![allow(unsafe_code)]
+
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::ops::Deref;
+use std::process::abort;
+use std::ptr::NonNull;
+use std::sync::atomic::{fence, AtomicUsize, Ordering};
+
+// The same limit `std::sync::Arc` uses: if the count gets this high,
+// something is leaking clones and we're at risk of overflowing.
+const MAX_REFCOUNT: usize = isize::MAX as usize;
+
+struct ArcInner<A> {
+    count: AtomicUsize,
+    data: A,
+}
+
+pub(crate) struct Arc<A> {
+    ptr: NonNull<ArcInner<A>>,
+    phantom: PhantomData<ArcInner<A>>,
+}
+
+unsafe impl<A: Send + Sync> Send for Arc<A> {}
+unsafe impl<A: Send + Sync> Sync for Arc<A> {}
+
+impl<A> Arc<A> {
+    #[inline]
+    pub(crate) fn new(data: A) -> Self {
+        let inner = Box::new(ArcInner {
+            count: AtomicUsize::new(1),
+            data,
+        });
+        Arc {
+            ptr: NonNull::from(Box::leak(inner)),
+            phantom: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn inner(&self) -> &ArcInner<A> {
+        unsafe { self.ptr.as_ref() }
+    }
+
+    #[inline]
+    fn is_unique(&self) -> bool {
+        // With no weak references around, a count of one means nobody
+        // else can be holding on to the data, and nobody can start to.
+        Arc::strong_count(self) == 1
+    }
+
+    #[inline]
+    pub(crate) fn strong_count(this: &Self) -> usize {
+        this.inner().count.load(Ordering::Acquire)
+    }
+
+    #[inline]
+    pub(crate) fn ptr_eq(left: &Self, right: &Self) -> bool {
+        left.ptr == right.ptr
+    }
+
+    pub(crate) fn make_mut(this: &mut Self) -> &mut A
+    where
+        A: Clone,
+    {
+        if !this.is_unique() {
+            *this = Arc::new((**this).clone());
+        }
+        unsafe { &mut this.ptr.as_mut().data }
+    }
+
+    pub(crate) fn try_unwrap(this: Self) -> Result<A, Self> {
+        if this
+            .inner()
+            .count
+            .compare_exchange(1, 0, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            return Err(this);
+        }
+        let this = ManuallyDrop::new(this);
+        let inner = unsafe { Box::from_raw(this.ptr.as_ptr()) };
+        Ok(inner.data)
+    }
+}
+
+impl<A> Clone for Arc<A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        let old_count = self.inner().count.fetch_add(1, Ordering::Relaxed);
+        if old_count > MAX_REFCOUNT {
+            abort();
+        }
+        Arc {
+            ptr: self.ptr,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<A> Drop for Arc<A> {
+    #[inline]
+    fn drop(&mut self) {
+        if self.inner().count.fetch_sub(1, Ordering::Release) != 1 {
+            return;
+        }
+        fence(Ordering::Acquire);
+        unsafe { drop(Box::from_raw(self.ptr.as_ptr())) }
+    }
+}
+
+impl<A> Deref for Arc<A> {
+    type Target = A;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.inner().data
+    }
+}
+
+impl<A: Default> Default for Arc<A> {
+    fn default() -> Self {
+        Arc::new(A::default())
+    }
+}
+
+impl<A: std::fmt::Debug> std::fmt::Debug for Arc<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        (**self).fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    struct DropCounter<'a>(&'a AtomicUsize);
+
+    impl<'a> Clone for DropCounter<'a> {
+        fn clone(&self) -> Self {
+            DropCounter(self.0)
+        }
+    }
+
+    impl<'a> Drop for DropCounter<'a> {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn make_mut_and_unwrap() {
+        let mut left = Arc::new(1);
+        let right = left.clone();
+        assert!(Arc::ptr_eq(&left, &right));
+        *Arc::make_mut(&mut left) += 1;
+        assert!(!Arc::ptr_eq(&left, &right));
+        assert_eq!((2, 1), (*left, *right));
+        let alias = left.clone();
+        let left = Arc::try_unwrap(left).unwrap_err();
+        drop(alias);
+        assert_eq!(Some(2), Arc::try_unwrap(left).ok());
+    }
+
+    #[test]
+    fn drops_data_once() {
+        let drops = AtomicUsize::new(0);
+        let first = Arc::new(DropCounter(&drops));
+        let second = first.clone();
+        drop(first);
+        assert_eq!(0, drops.load(Ordering::SeqCst));
+        drop(second);
+        assert_eq!(1, drops.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,471 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! A persistent bidirectional map.
+//!
+//! A [`BiMap`][BiMap] is a one to one mapping between left values and
+//! right values, which can be looked up from either side. Every left
+//! value maps to exactly one right value and vice versa, and inserting
+//! a pair which conflicts with existing pairs on either side either
+//! replaces them or fails, depending on which method you use.
+//!
+//! It's implemented as a pair of [`OrdMap`][OrdMap]s which are always
+//! updated together, so cloning a bidirectional map is O(1) and
+//! lookups and updates are O(log n).
+//!
+//! # Examples
+//!
+//! ```
+//! # #[macro_use] extern crate im;
+//! # use im::bimap::BiMap;
+//! let mut names = BiMap::new();
+//! names.insert(1, "alice");
+//! names.insert(2, "bob");
+//! assert_eq!(Some(&"bob"), names.get_by_left(&2));
+//! assert_eq!(Some(&1), names.get_by_right(&"alice"));
+//! ```
+//!
+//! [BiMap]: struct.BiMap.html
+//! [OrdMap]: ../ordmap/struct.OrdMap.html
+
+use std::borrow::Borrow;
+use std::fmt::{Debug, Error, Formatter};
+use std::iter::FromIterator;
+
+use crate::ordmap::{Iter, OrdMap};
+
+/// The pairs removed from a bidirectional map by
+/// [`BiMap::insert()`][insert] to make room for a new pair.
+///
+/// [insert]: struct.BiMap.html#method.insert
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Overwritten<A, B> {
+    /// Neither value was in the map, so nothing was removed.
+    Neither,
+    /// The exact same pair was already in the map.
+    Pair(A, B),
+    /// The left value was already in the map, paired with a different
+    /// right value, and that pair was removed.
+    Left(A, B),
+    /// The right value was already in the map, paired with a different
+    /// left value, and that pair was removed.
+    Right(A, B),
+    /// Both values were already in the map, as parts of two different
+    /// pairs, which were both removed. The pair containing the left
+    /// value comes first.
+    Both((A, B), (A, B)),
+}
+
+/// A persistent bidirectional map.
+///
+/// Each left value of type `A` is paired with exactly one right value
+/// of type `B`, and each right value with exactly one left value.
+///
+/// Please see the [module documentation][module] for more details.
+///
+/// [module]: index.html
+pub struct BiMap<A, B> {
+    left: OrdMap<A, B>,
+    right: OrdMap<B, A>,
+}
+
+impl<A, B> BiMap<A, B>
+where
+    A: Ord + Clone,
+    B: Ord + Clone,
+{
+    /// Construct an empty bidirectional map.
+    #[must_use]
+    pub fn new() -> Self {
+        BiMap {
+            left: OrdMap::new(),
+            right: OrdMap::new(),
+        }
+    }
+
+    /// Construct a bidirectional map with a single pair.
+    #[must_use]
+    pub fn unit(left: A, right: B) -> Self {
+        BiMap {
+            left: OrdMap::unit(left.clone(), right.clone()),
+            right: OrdMap::unit(right, left),
+        }
+    }
+
+    /// Test whether a bidirectional map is empty.
+    ///
+    /// Time: O(1)
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.left.is_empty()
+    }
+
+    /// Get the number of pairs in a bidirectional map.
+    ///
+    /// Time: O(1)
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.left.len()
+    }
+
+    /// Get the right value paired with a left value.
+    ///
+    /// Time: O(log n)
+    #[must_use]
+    pub fn get_by_left<BA>(&self, left: &BA) -> Option<&B>
+    where
+        BA: Ord + ?Sized,
+        A: Borrow<BA>,
+    {
+        self.left.get(left)
+    }
+
+    /// Get the left value paired with a right value.
+    ///
+    /// Time: O(log n)
+    #[must_use]
+    pub fn get_by_right<BB>(&self, right: &BB) -> Option<&A>
+    where
+        BB: Ord + ?Sized,
+        B: Borrow<BB>,
+    {
+        self.right.get(right)
+    }
+
+    /// Test whether a bidirectional map contains a left value.
+    ///
+    /// Time: O(log n)
+    #[must_use]
+    pub fn contains_left<BA>(&self, left: &BA) -> bool
+    where
+        BA: Ord + ?Sized,
+        A: Borrow<BA>,
+    {
+        self.left.contains_key(left)
+    }
+
+    /// Test whether a bidirectional map contains a right value.
+    ///
+    /// Time: O(log n)
+    #[must_use]
+    pub fn contains_right<BB>(&self, right: &BB) -> bool
+    where
+        BB: Ord + ?Sized,
+        B: Borrow<BB>,
+    {
+        self.right.contains_key(right)
+    }
+
+    /// Get an iterator over the pairs in a bidirectional map, in order
+    /// of their left values.
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_, A, B> {
+        self.left.iter()
+    }
+
+    /// Get the mapping from left values to right values as an
+    /// [`OrdMap`][OrdMap].
+    ///
+    /// Time: O(1)
+    ///
+    /// [OrdMap]: ../ordmap/struct.OrdMap.html
+    #[must_use]
+    pub fn left_map(&self) -> &OrdMap<A, B> {
+        &self.left
+    }
+
+    /// Get the mapping from right values to left values as an
+    /// [`OrdMap`][OrdMap].
+    ///
+    /// Time: O(1)
+    ///
+    /// [OrdMap]: ../ordmap/struct.OrdMap.html
+    #[must_use]
+    pub fn right_map(&self) -> &OrdMap<B, A> {
+        &self.right
+    }
+
+    /// Insert a pair into a bidirectional map, removing any existing
+    /// pairs containing either of its values.
+    ///
+    /// Returns the pairs which were removed.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::bimap::{BiMap, Overwritten};
+    /// let mut map = BiMap::new();
+    /// assert_eq!(Overwritten::Neither, map.insert(1, "a"));
+    /// assert_eq!(Overwritten::Neither, map.insert(2, "b"));
+    /// assert_eq!(Overwritten::Both((1, "a"), (2, "b")), map.insert(1, "b"));
+    /// assert_eq!(1, map.len());
+    /// assert_eq!(Some(&1), map.get_by_right(&"b"));
+    /// ```
+    pub fn insert(&mut self, left: A, right: B) -> Overwritten<A, B> {
+        let overwritten = match (self.remove_by_left(&left), self.remove_by_right(&right)) {
+            (None, None) => Overwritten::Neither,
+            (Some((old_left, old_right)), None) if old_right == right => {
+                Overwritten::Pair(old_left, old_right)
+            }
+            (Some((old_left, old_right)), None) => Overwritten::Left(old_left, old_right),
+            (None, Some((old_left, old_right))) => Overwritten::Right(old_left, old_right),
+            (Some(left_pair), Some(right_pair)) => Overwritten::Both(left_pair, right_pair),
+        };
+        self.left.insert(left.clone(), right.clone());
+        self.right.insert(right, left);
+        overwritten
+    }
+
+    /// Insert a pair into a bidirectional map, unless either of its
+    /// values is already in the map, in which case the map is left
+    /// unchanged and the pair is handed back as an error.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::bimap::BiMap;
+    /// let mut map = BiMap::new();
+    /// assert_eq!(Ok(()), map.insert_no_overwrite(1, "a"));
+    /// assert_eq!(Err((2, "a")), map.insert_no_overwrite(2, "a"));
+    /// assert_eq!(Some(&1), map.get_by_right(&"a"));
+    /// ```
+    pub fn insert_no_overwrite(&mut self, left: A, right: B) -> Result<(), (A, B)> {
+        if self.left.contains_key(&left) || self.right.contains_key(&right) {
+            return Err((left, right));
+        }
+        self.left.insert(left.clone(), right.clone());
+        self.right.insert(right, left);
+        Ok(())
+    }
+
+    /// Construct a new bidirectional map by inserting a pair into a
+    /// map, removing any existing pairs containing either of its
+    /// values.
+    ///
+    /// Time: O(log n)
+    #[must_use]
+    pub fn update(&self, left: A, right: B) -> Self {
+        let mut out = self.clone();
+        out.insert(left, right);
+        out
+    }
+
+    /// Remove the pair containing a left value from a bidirectional
+    /// map, and return it.
+    ///
+    /// Time: O(log n)
+    pub fn remove_by_left<BA>(&mut self, left: &BA) -> Option<(A, B)>
+    where
+        BA: Ord + ?Sized,
+        A: Borrow<BA>,
+    {
+        let (left, right) = self.left.remove_with_key(left)?;
+        self.right.remove(&right);
+        Some((left, right))
+    }
+
+    /// Remove the pair containing a right value from a bidirectional
+    /// map, and return it.
+    ///
+    /// Time: O(log n)
+    pub fn remove_by_right<BB>(&mut self, right: &BB) -> Option<(A, B)>
+    where
+        BB: Ord + ?Sized,
+        B: Borrow<BB>,
+    {
+        let (right, left) = self.right.remove_with_key(right)?;
+        self.left.remove(&left);
+        Some((left, right))
+    }
+
+    /// Construct a new bidirectional map without the pair containing
+    /// a left value.
+    ///
+    /// Time: O(log n)
+    #[must_use]
+    pub fn without_left<BA>(&self, left: &BA) -> Self
+    where
+        BA: Ord + ?Sized,
+        A: Borrow<BA>,
+    {
+        let mut out = self.clone();
+        out.remove_by_left(left);
+        out
+    }
+
+    /// Construct a new bidirectional map without the pair containing
+    /// a right value.
+    ///
+    /// Time: O(log n)
+    #[must_use]
+    pub fn without_right<BB>(&self, right: &BB) -> Self
+    where
+        BB: Ord + ?Sized,
+        B: Borrow<BB>,
+    {
+        let mut out = self.clone();
+        out.remove_by_right(right);
+        out
+    }
+
+    /// Construct the inverse of a bidirectional map, with the left and
+    /// right values swapped.
+    ///
+    /// Time: O(1)
+    #[must_use]
+    pub fn invert(&self) -> BiMap<B, A> {
+        BiMap {
+            left: self.right.clone(),
+            right: self.left.clone(),
+        }
+    }
+}
+
+// Core traits
+
+impl<A, B> Clone for BiMap<A, B> {
+    /// Clone a bidirectional map.
+    ///
+    /// Time: O(1)
+    #[inline]
+    fn clone(&self) -> Self {
+        BiMap {
+            left: self.left.clone(),
+            right: self.right.clone(),
+        }
+    }
+}
+
+impl<A, B> Default for BiMap<A, B>
+where
+    A: Ord + Clone,
+    B: Ord + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A, B> PartialEq for BiMap<A, B>
+where
+    A: Ord + Clone,
+    B: Ord + Clone,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.left == other.left
+    }
+}
+
+impl<A, B> Eq for BiMap<A, B>
+where
+    A: Ord + Clone,
+    B: Ord + Clone,
+{
+}
+
+impl<A, B> Debug for BiMap<A, B>
+where
+    A: Ord + Clone + Debug,
+    B: Ord + Clone + Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<A, B> Extend<(A, B)> for BiMap<A, B>
+where
+    A: Ord + Clone,
+    B: Ord + Clone,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (A, B)>,
+    {
+        for (left, right) in iter {
+            self.insert(left, right);
+        }
+    }
+}
+
+impl<A, B> FromIterator<(A, B)> for BiMap<A, B>
+where
+    A: Ord + Clone,
+    B: Ord + Clone,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (A, B)>,
+    {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<'a, A, B> IntoIterator for &'a BiMap<A, B>
+where
+    A: Ord + Clone,
+    B: Ord + Clone,
+{
+    type Item = (&'a A, &'a B);
+    type IntoIter = Iter<'a, A, B>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::proptest::collection;
+    use ::proptest::num::u8;
+    use ::proptest::proptest;
+
+    fn assert_coherent(map: &BiMap<u8, u8>) {
+        assert_eq!(map.left.len(), map.right.len());
+        for (left, right) in map.iter() {
+            assert_eq!(Some(left), map.get_by_right(right));
+        }
+    }
+
+    #[test]
+    fn insert_reports_overwritten_pairs() {
+        let mut map = BiMap::new();
+        assert_eq!(Overwritten::Neither, map.insert(1, 10));
+        assert_eq!(Overwritten::Pair(1, 10), map.insert(1, 10));
+        assert_eq!(Overwritten::Left(1, 10), map.insert(1, 11));
+        assert_eq!(Overwritten::Right(1, 11), map.insert(2, 11));
+        map.insert(3, 13);
+        assert_eq!(Overwritten::Both((2, 11), (3, 13)), map.insert(2, 13));
+        assert_eq!(BiMap::unit(2, 13), map);
+        assert_coherent(&map);
+    }
+
+    proptest! {
+        #[test]
+        fn stays_coherent(ref pairs in collection::vec((u8::ANY, u8::ANY), 0..500)) {
+            let mut map = BiMap::new();
+            for (index, (left, right)) in pairs.iter().enumerate() {
+                if index % 3 == 2 {
+                    map.remove_by_right(right);
+                } else {
+                    map.insert(*left, *right);
+                    assert_eq!(Some(right), map.get_by_left(left));
+                }
+                assert_coherent(&map);
+            }
+            assert_eq!(map, map.invert().invert());
+        }
+    }
+}
@@ -0,0 +1,45 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! A fixed capacity array with inline storage.
+//!
+//! [`Chunk`][Chunk] is the array type the nodes of `im`'s data
+//! structures are built from. It's a plain, mutable, non-persistent
+//! buffer which keeps its elements inline rather than on the heap, with
+//! a capacity decided at compile time by a [`typenum`][typenum] type
+//! level number, defaulting to 64. Pushing and popping are O(1) at
+//! either end, and inserting and removing in the middle are O(n) in the
+//! number of elements moved.
+//!
+//! It's a good fit for small buffers you fill up before handing them
+//! over to one of the persistent data structures, without having to pull
+//! in another small vector crate.
+//!
+//! Operations which would take the chunk past its capacity, or pop a
+//! value off an empty chunk, panic, so check [`is_full()`][is_full] or
+//! `is_empty()` first if you're not sure.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[macro_use] extern crate im;
+//! # use im::chunk::Chunk;
+//! # use im::vector::Vector;
+//! let mut chunk: Chunk<i32> = Chunk::new();
+//! chunk.push_back(2);
+//! chunk.push_back(3);
+//! chunk.push_front(1);
+//! chunk.insert(3, 4);
+//! assert_eq!(1, chunk.pop_front());
+//! assert_eq!(&[2, 3, 4][..], chunk.as_slice());
+//! let vec: Vector<i32> = chunk.drain().collect();
+//! assert!(chunk.is_empty());
+//! assert_eq!(3, vec.len());
+//! ```
+//!
+//! [Chunk]: struct.Chunk.html
+//! [is_full]: struct.Chunk.html#method.is_full
+//! [typenum]: https://docs.rs/typenum/
+
+pub use sized_chunks::sized_chunk::{Chunk, Drain, Iter};
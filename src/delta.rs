@@ -0,0 +1,170 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! A compact binary encoding for sets of integers.
+//!
+//! With the `delta` feature flag enabled, [`encode()`][encode] writes an
+//! [`OrdSet<u64>`][OrdSet] as the number of values followed by the
+//! smallest value and the difference between each value and the one
+//! before it, in ascending order, each as an unsigned [LEB128][leb128]
+//! varint. Sets of ids which are close together, which is the common
+//! case, take up a byte or two per value instead of the eight bytes a
+//! full width encoding needs.
+//!
+//! [`decode()`][decode] reads an encoded set back, validating it as it
+//! goes, and builds the set from its values in order.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[macro_use] extern crate im;
+//! # use im::delta;
+//! # use im::ordset::OrdSet;
+//! let ids: OrdSet<u64> = (1_000_000..1_001_000u64).collect();
+//! let bytes = delta::encode(&ids);
+//! assert!(bytes.len() < 1010);
+//! assert_eq!(Ok(ids), delta::decode(&bytes));
+//! ```
+//!
+//! [encode]: fn.encode.html
+//! [decode]: fn.decode.html
+//! [OrdSet]: ../ordset/struct.OrdSet.html
+//! [leb128]: https://en.wikipedia.org/wiki/LEB128
+
+use std::fmt::{Display, Error, Formatter};
+
+use crate::ordset::OrdSet;
+
+/// The error returned by [`decode()`][decode] when its input isn't a
+/// valid encoded set.
+///
+/// [decode]: fn.decode.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DecodeError {
+    /// The input ended in the middle of the set.
+    Truncated,
+    /// A varint, or the sum of the differences, doesn't fit in a `u64`.
+    Overflow,
+    /// A difference of zero between two values, which would make them
+    /// the same value.
+    Duplicate,
+    /// The input has bytes left over after the end of the set.
+    TrailingBytes,
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        f.write_str(match self {
+            DecodeError::Truncated => "encoded set is truncated",
+            DecodeError::Overflow => "encoded set has a value which overflows a u64",
+            DecodeError::Duplicate => "encoded set has a duplicate value",
+            DecodeError::TrailingBytes => "encoded set is followed by trailing bytes",
+        })
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Encode a set of integers as a sequence of varint deltas.
+///
+/// Time: O(n)
+#[must_use]
+pub fn encode(set: &OrdSet<u64>) -> Vec<u8> {
+    let mut out = Vec::with_capacity(set.len() + 1);
+    write_varint(&mut out, set.len() as u64);
+    let mut previous = 0;
+    for value in set {
+        write_varint(&mut out, value - previous);
+        previous = *value;
+    }
+    out
+}
+
+/// Decode a set of integers encoded by [`encode()`][encode].
+///
+/// The values come out in ascending order, so the set is built directly
+/// from them rather than by inserting them one at a time.
+///
+/// Time: O(n)
+///
+/// [encode]: fn.encode.html
+pub fn decode(mut bytes: &[u8]) -> Result<OrdSet<u64>, DecodeError> {
+    let len = read_varint(&mut bytes)?;
+    // Every value takes at least a byte, so don't trust a length the
+    // input can't possibly hold.
+    let mut values = Vec::with_capacity((len as usize).min(bytes.len()));
+    let mut previous = 0u64;
+    for index in 0..len {
+        let delta = read_varint(&mut bytes)?;
+        if index > 0 && delta == 0 {
+            return Err(DecodeError::Duplicate);
+        }
+        previous = previous.checked_add(delta).ok_or(DecodeError::Overflow)?;
+        values.push(previous);
+    }
+    if !bytes.is_empty() {
+        return Err(DecodeError::TrailingBytes);
+    }
+    Ok(OrdSet::from_ordered(values))
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn read_varint(bytes: &mut &[u8]) -> Result<u64, DecodeError> {
+    let mut value = 0u64;
+    let mut shift = 0;
+    loop {
+        let (byte, rest) = bytes.split_first().ok_or(DecodeError::Truncated)?;
+        *bytes = rest;
+        let bits = u64::from(byte & 0x7f);
+        if shift == 63 && bits > 1 || shift > 63 {
+            return Err(DecodeError::Overflow);
+        }
+        value |= bits << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+        shift += 7;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::proptest::collection;
+    use ::proptest::num::u64;
+    use ::proptest::proptest;
+
+    #[test]
+    fn rejects_invalid_input() {
+        assert_eq!(Ok(OrdSet::new()), decode(&[0]));
+        assert_eq!(Err(DecodeError::Truncated), decode(&[]));
+        assert_eq!(Err(DecodeError::Truncated), decode(&[2, 1]));
+        assert_eq!(Err(DecodeError::Truncated), decode(&[1, 0x80]));
+        assert_eq!(Err(DecodeError::Duplicate), decode(&[2, 1, 0]));
+        assert_eq!(Err(DecodeError::TrailingBytes), decode(&[1, 1, 1]));
+        let mut too_big = vec![1];
+        too_big.extend(&[0xff; 9]);
+        too_big.push(0x02);
+        assert_eq!(Err(DecodeError::Overflow), decode(&too_big));
+        let mut wraps = vec![2];
+        write_varint(&mut wraps, <u64>::MAX);
+        write_varint(&mut wraps, 1);
+        assert_eq!(Err(DecodeError::Overflow), decode(&wraps));
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(ref values in collection::vec(u64::ANY, 0..1000)) {
+            let set: OrdSet<u64> = values.iter().cloned().collect();
+            assert_eq!(Ok(set.clone()), decode(&encode(&set)));
+        }
+    }
+}
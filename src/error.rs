@@ -0,0 +1,78 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+// Error types shared between the ordered and the hashed collections, which
+// each of them re-export.
+
+pub(crate) mod map {
+    use std::fmt::{Debug, Display, Error, Formatter};
+
+    /// The error returned by [`OrdMap::try_insert()`][ordmap] and
+    /// [`HashMap::try_insert()`][hashmap] when the key is already in the
+    /// map.
+    ///
+    /// [ordmap]: ../ordmap/struct.OrdMap.html#method.try_insert
+    /// [hashmap]: ../hashmap/struct.HashMap.html#method.try_insert
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct OccupiedError<K, V> {
+        /// The key which was already in the map.
+        pub key: K,
+        /// The value which wasn't inserted.
+        pub value: V,
+        /// The value the map already has for the key.
+        pub existing: V,
+    }
+
+    impl<K, V> Display for OccupiedError<K, V>
+    where
+        K: Debug,
+        V: Debug,
+    {
+        fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+            write!(
+                f,
+                "failed to insert {:?}, key {:?} already exists with value {:?}",
+                self.value, self.key, self.existing
+            )
+        }
+    }
+
+    impl<K, V> std::error::Error for OccupiedError<K, V>
+    where
+        K: Debug,
+        V: Debug,
+    {
+    }
+}
+
+pub(crate) mod set {
+    use std::fmt::{Debug, Display, Error, Formatter};
+
+    /// The error returned by [`OrdSet::try_insert()`][ordset] and
+    /// [`HashSet::try_insert()`][hashset] when the value is already in
+    /// the set.
+    ///
+    /// [ordset]: ../ordset/struct.OrdSet.html#method.try_insert
+    /// [hashset]: ../hashset/struct.HashSet.html#method.try_insert
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct OccupiedError<A> {
+        /// The value which wasn't inserted.
+        pub value: A,
+    }
+
+    impl<A> Display for OccupiedError<A>
+    where
+        A: Debug,
+    {
+        fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+            write!(
+                f,
+                "failed to insert {:?}, it's already in the set",
+                self.value
+            )
+        }
+    }
+
+    impl<A> std::error::Error for OccupiedError<A> where A: Debug {}
+}
@@ -7,8 +7,12 @@
 use std::marker::PhantomData;
 use std::ops::Deref;
 use std::rc::Rc as RRc;
+#[cfg(not(feature = "light-arc"))]
 use std::sync::Arc as RArc;
 
+#[cfg(feature = "light-arc")]
+use crate::arc::Arc as RArc;
+
 use crate::nodes::chunk::Chunk;
 
 pub(crate) trait PoolDefault: Default {}
@@ -48,11 +52,15 @@ impl<A> Rc<A> {
     where
         A: PoolDefault,
     {
+        #[cfg(feature = "instrument")]
+        crate::instrument::record_allocation();
         Self(Default::default())
     }
 
     #[inline(always)]
     pub(crate) fn new(_pool: &Pool<A>, value: A) -> Self {
+        #[cfg(feature = "instrument")]
+        crate::instrument::record_allocation();
         Rc(RRc::new(value))
     }
 
@@ -61,6 +69,8 @@ impl<A> Rc<A> {
     where
         A: PoolClone,
     {
+        #[cfg(feature = "instrument")]
+        crate::instrument::record_allocation();
         Rc(RRc::new(value.clone()))
     }
 
@@ -69,6 +79,12 @@ impl<A> Rc<A> {
     where
         A: PoolClone,
     {
+        #[cfg(feature = "instrument")]
+        {
+            if RRc::strong_count(&this.0) > 1 {
+                crate::instrument::record_path_copy();
+            }
+        }
         RRc::make_mut(&mut this.0)
     }
 
@@ -88,6 +104,8 @@ impl<A> Rc<A> {
 impl<A> Clone for Rc<A> {
     #[inline(always)]
     fn clone(&self) -> Self {
+        #[cfg(feature = "instrument")]
+        crate::instrument::record_ref_clone();
         Rc(self.0.clone())
     }
 }
@@ -133,11 +151,15 @@ impl<A> Arc<A> {
     where
         A: PoolDefault,
     {
+        #[cfg(feature = "instrument")]
+        crate::instrument::record_allocation();
         Self(Default::default())
     }
 
     #[inline(always)]
     pub(crate) fn new(_pool: &Pool<A>, value: A) -> Self {
+        #[cfg(feature = "instrument")]
+        crate::instrument::record_allocation();
         Self(RArc::new(value))
     }
 
@@ -146,6 +168,8 @@ impl<A> Arc<A> {
     where
         A: PoolClone,
     {
+        #[cfg(feature = "instrument")]
+        crate::instrument::record_allocation();
         Self(RArc::new(value.clone()))
     }
 
@@ -154,6 +178,12 @@ impl<A> Arc<A> {
     where
         A: PoolClone,
     {
+        #[cfg(feature = "instrument")]
+        {
+            if RArc::strong_count(&this.0) > 1 {
+                crate::instrument::record_path_copy();
+            }
+        }
         RArc::make_mut(&mut this.0)
     }
 
@@ -173,6 +203,8 @@ impl<A> Arc<A> {
 impl<A> Clone for Arc<A> {
     #[inline(always)]
     fn clone(&self) -> Self {
+        #[cfg(feature = "instrument")]
+        crate::instrument::record_ref_clone();
         Self(self.0.clone())
     }
 }
@@ -0,0 +1,347 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! A persistent map counting occurrences of values.
+//!
+//! A [`FrequencyMap`][FrequencyMap] counts how many times each value has
+//! been added to it, and keeps a secondary index of values ordered by
+//! their counts, so that it can answer questions like "what are the
+//! most frequent values?" without scanning every value.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[macro_use] extern crate im;
+//! # use im::frequencymap::FrequencyMap;
+//! let words: FrequencyMap<&str> = "the cat and the dog and the bird"
+//!     .split(' ')
+//!     .collect();
+//! assert_eq!(3, words.count("the"));
+//! assert_eq!(vec![(&"the", 3), (&"and", 2)], words.top_k(2));
+//! assert_eq!(2, words.count_at_least(2));
+//! ```
+//!
+//! [FrequencyMap]: struct.FrequencyMap.html
+
+use std::borrow::Borrow;
+use std::fmt::{Debug, Error, Formatter};
+use std::iter::FromIterator;
+
+use crate::ordmap::{Iter, OrdMap};
+use crate::ordset::OrdSet;
+
+/// A persistent map counting occurrences of values.
+///
+/// Values with a count of zero aren't stored, so every value in the map
+/// has a count of at least one.
+///
+/// Please see the [module documentation][module] for more details.
+///
+/// [module]: index.html
+pub struct FrequencyMap<A> {
+    counts: OrdMap<A, usize>,
+    by_count: OrdMap<usize, OrdSet<A>>,
+    total: usize,
+}
+
+impl<A> FrequencyMap<A>
+where
+    A: Ord + Clone,
+{
+    /// Construct an empty frequency map.
+    #[must_use]
+    pub fn new() -> Self {
+        FrequencyMap {
+            counts: OrdMap::new(),
+            by_count: OrdMap::new(),
+            total: 0,
+        }
+    }
+
+    /// Test whether a frequency map is empty.
+    ///
+    /// Time: O(1)
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Get the number of distinct values in a frequency map.
+    ///
+    /// Time: O(1)
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Get the sum of the counts of every value in a frequency map.
+    ///
+    /// Time: O(1)
+    #[inline]
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Get the number of times a value occurs in a frequency map.
+    ///
+    /// Time: O(log n)
+    #[must_use]
+    pub fn count<BA>(&self, value: &BA) -> usize
+    where
+        BA: Ord + ?Sized,
+        A: Borrow<BA>,
+    {
+        self.counts.get(value).cloned().unwrap_or(0)
+    }
+
+    /// Get an iterator over the values in a frequency map and their
+    /// counts, in order of the values.
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_, A, usize> {
+        self.counts.iter()
+    }
+
+    /// Add one occurrence of a value to a frequency map, and return
+    /// its new count.
+    ///
+    /// Time: O(log n)
+    pub fn increment(&mut self, value: A) -> usize {
+        self.increment_by(value, 1)
+    }
+
+    /// Add `count` occurrences of a value to a frequency map, and
+    /// return its new count.
+    ///
+    /// Time: O(log n)
+    pub fn increment_by(&mut self, value: A, count: usize) -> usize {
+        let old_count = self.count(&value);
+        let new_count = old_count + count;
+        self.set_count(value, old_count, new_count);
+        new_count
+    }
+
+    /// Remove one occurrence of a value from a frequency map, and
+    /// return its new count.
+    ///
+    /// The value is removed from the map when its count reaches zero.
+    /// Decrementing a value which isn't in the map does nothing.
+    ///
+    /// Time: O(log n)
+    pub fn decrement<BA>(&mut self, value: &BA) -> usize
+    where
+        BA: Ord + ?Sized,
+        A: Borrow<BA>,
+    {
+        match self.counts.get_key_value(value) {
+            None => 0,
+            Some((value, count)) => {
+                let (value, old_count) = (value.clone(), *count);
+                self.set_count(value, old_count, old_count - 1);
+                old_count - 1
+            }
+        }
+    }
+
+    /// Remove every occurrence of a value from a frequency map, and
+    /// return the count it had.
+    ///
+    /// Time: O(log n)
+    pub fn remove<BA>(&mut self, value: &BA) -> usize
+    where
+        BA: Ord + ?Sized,
+        A: Borrow<BA>,
+    {
+        match self.counts.get_key_value(value) {
+            None => 0,
+            Some((value, count)) => {
+                let (value, old_count) = (value.clone(), *count);
+                self.set_count(value, old_count, 0);
+                old_count
+            }
+        }
+    }
+
+    /// Get the `k` most frequent values in a frequency map, along
+    /// with their counts.
+    ///
+    /// Values are returned in descending order of their counts, and
+    /// values with the same count in ascending order.
+    ///
+    /// Time: O(k + log n)
+    #[must_use]
+    pub fn top_k(&self, k: usize) -> Vec<(&A, usize)> {
+        self.by_count
+            .iter()
+            .rev()
+            .flat_map(|(count, values)| values.iter().map(move |value| (value, *count)))
+            .take(k)
+            .collect()
+    }
+
+    /// Get the number of distinct values which occur at least `count`
+    /// times in a frequency map.
+    ///
+    /// Time: O(d) where d is the number of distinct counts
+    #[must_use]
+    pub fn count_at_least(&self, count: usize) -> usize {
+        self.by_count
+            .range(count..)
+            .map(|(_, values)| values.len())
+            .sum()
+    }
+
+    fn set_count(&mut self, value: A, old_count: usize, new_count: usize) {
+        if old_count > 0 {
+            let values = self.by_count.get_mut(&old_count).unwrap();
+            values.remove(&value);
+            if values.is_empty() {
+                self.by_count.remove(&old_count);
+            }
+        }
+        self.total = self.total - old_count + new_count;
+        if new_count > 0 {
+            self.by_count
+                .entry(new_count)
+                .or_default()
+                .insert(value.clone());
+            self.counts.insert(value, new_count);
+        } else {
+            self.counts.remove(&value);
+        }
+    }
+}
+
+// Core traits
+
+impl<A> Clone for FrequencyMap<A> {
+    /// Clone a frequency map.
+    ///
+    /// Time: O(1)
+    #[inline]
+    fn clone(&self) -> Self {
+        FrequencyMap {
+            counts: self.counts.clone(),
+            by_count: self.by_count.clone(),
+            total: self.total,
+        }
+    }
+}
+
+impl<A> Default for FrequencyMap<A>
+where
+    A: Ord + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A> PartialEq for FrequencyMap<A>
+where
+    A: Ord + Clone,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.counts == other.counts
+    }
+}
+
+impl<A> Eq for FrequencyMap<A> where A: Ord + Clone {}
+
+impl<A> Debug for FrequencyMap<A>
+where
+    A: Ord + Clone + Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<A> Extend<A> for FrequencyMap<A>
+where
+    A: Ord + Clone,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = A>,
+    {
+        for value in iter {
+            self.increment(value);
+        }
+    }
+}
+
+impl<A> FromIterator<A> for FrequencyMap<A>
+where
+    A: Ord + Clone,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = A>,
+    {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<'a, A> IntoIterator for &'a FrequencyMap<A>
+where
+    A: Ord + Clone,
+{
+    type Item = (&'a A, &'a usize);
+    type IntoIter = Iter<'a, A, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::proptest::collection;
+    use ::proptest::num::u8;
+    use ::proptest::proptest;
+    use std::collections::BTreeMap;
+
+    proptest! {
+        #[test]
+        fn matches_counting(ref actions in collection::vec((u8::ANY, 0..10u8), 0..500)) {
+            let mut map = FrequencyMap::new();
+            let mut expected: BTreeMap<u8, usize> = BTreeMap::new();
+            for (action, value) in actions {
+                match action % 3 {
+                    0 | 1 => {
+                        *expected.entry(*value).or_insert(0) += 1;
+                        assert_eq!(expected[value], map.increment(*value));
+                    }
+                    _ => {
+                        let remaining = match expected.get(value) {
+                            None => 0,
+                            Some(count) => count - 1,
+                        };
+                        if remaining == 0 {
+                            expected.remove(value);
+                        } else {
+                            expected.insert(*value, remaining);
+                        }
+                        assert_eq!(remaining, map.decrement(value));
+                    }
+                }
+                assert_eq!(expected.len(), map.len());
+                assert_eq!(expected.values().sum::<usize>(), map.total());
+                for n in 0..5 {
+                    assert_eq!(expected.values().filter(|count| **count >= n).count(), map.count_at_least(n));
+                }
+                let mut ranked: Vec<(&u8, usize)> = expected.iter().map(|(value, count)| (value, *count)).collect();
+                ranked.sort_by(|left, right| right.1.cmp(&left.1).then(left.0.cmp(right.0)));
+                ranked.truncate(3);
+                assert_eq!(ranked, map.top_k(3));
+            }
+        }
+    }
+}
@@ -31,11 +31,16 @@ use std::iter::{FromIterator, FusedIterator, Sum};
 use std::mem;
 use std::ops::{Add, Index, IndexMut};
 
+use crate::hashset::HashSet;
+use crate::merge::{Merge, MergeStrategy};
 use crate::nodes::hamt::{
-    hash_key, Drain as NodeDrain, HashBits, HashValue, Iter as NodeIter, IterMut as NodeIterMut,
-    Node,
+    hash_key, DiffItem as NodeDiffItem, DiffIter as NodeDiffIter, Drain as NodeDrain, HashBits,
+    HashValue, Identical, Iter as NodeIter, IterMut as NodeIterMut, Node,
 };
-use crate::util::{Pool, PoolRef, Ref};
+use crate::ordset::OrdSet;
+use crate::util::{Merged, Pool, PoolRef, Ref};
+
+pub use crate::error::map::OccupiedError;
 
 /// Construct a hash map from a sequence of key/value pairs.
 ///
@@ -307,6 +312,32 @@ impl<K, V, S> HashMap<K, V, S> {
         }
     }
 
+    /// Get an iterator over clones of the key/value pairs of a hash
+    /// map.
+    ///
+    /// The pairs come out in the same order as they do from
+    /// [`iter()`][iter].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::HashMap;
+    /// let map = hashmap!{1 => "one"};
+    /// let pairs: Vec<(i32, &str)> = map.cloned_iter().collect();
+    /// assert_eq!(vec![(1, "one")], pairs);
+    /// ```
+    ///
+    /// [iter]: #method.iter
+    #[must_use]
+    pub fn cloned_iter(&self) -> ClonedIter<'_, K, V>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        ClonedIter { it: self.iter() }
+    }
+
     /// Get an iterator over a hash map's keys.
     ///
     /// Please note that the order is consistent between maps using
@@ -337,6 +368,237 @@ impl<K, V, S> HashMap<K, V, S> {
         }
     }
 
+    /// Fold the key/value pairs of a map into an accumulator.
+    ///
+    /// This walks the tree directly rather than going through an
+    /// iterator, which makes it faster than `iter().fold()`.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::HashMap;
+    /// let map = hashmap!{1 => 11, 2 => 22, 3 => 33};
+    /// assert_eq!(66, map.fold(0, |sum, (_, value)| sum + value));
+    /// ```
+    pub fn fold<'a, B, F>(&'a self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, (&'a K, &'a V)) -> B,
+    {
+        match self
+            .root
+            .fold_while(init, &mut |acc, (key, value)| Ok(f(acc, (key, value))))
+        {
+            Ok(acc) | Err(acc) => acc,
+        }
+    }
+
+    /// Fold the key/value pairs of a map into an accumulator, passing
+    /// the key and the value to the function as separate arguments.
+    ///
+    /// Like [`fold`][fold], this walks the tree directly.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::HashMap;
+    /// let map = hashmap!{1 => 11, 2 => 22, 3 => 33};
+    /// assert_eq!(72, map.fold_with_key(0, |sum, key, value| sum + key + value));
+    /// ```
+    ///
+    /// [fold]: #method.fold
+    pub fn fold_with_key<'a, B, F>(&'a self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, &'a K, &'a V) -> B,
+    {
+        self.fold(init, |acc, (key, value)| f(acc, key, value))
+    }
+
+    /// Call a function on every key/value pair in a map.
+    ///
+    /// Like [`fold`][fold], this walks the tree directly rather than
+    /// going through an iterator.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::HashMap;
+    /// let map = hashmap!{1 => "a", 2 => "b", 3 => "c"};
+    /// let mut keys = Vec::new();
+    /// map.for_each(|key, _| keys.push(*key));
+    /// keys.sort();
+    /// assert_eq!(vec![1, 2, 3], keys);
+    /// ```
+    ///
+    /// [fold]: #method.fold
+    pub fn for_each<'a, F>(&'a self, mut f: F)
+    where
+        F: FnMut(&'a K, &'a V),
+    {
+        self.fold((), |(), (key, value)| f(key, value))
+    }
+
+    /// Test whether any key/value pair in a map satisfies a predicate.
+    ///
+    /// Time: O(n)
+    pub fn any<F>(&self, mut f: F) -> bool
+    where
+        F: FnMut((&K, &V)) -> bool,
+    {
+        self.find(|(key, value)| f((key, value))).is_some()
+    }
+
+    /// Test whether every key/value pair in a map satisfies a predicate.
+    ///
+    /// Time: O(n)
+    pub fn all<F>(&self, mut f: F) -> bool
+    where
+        F: FnMut((&K, &V)) -> bool,
+    {
+        !self.any(|(key, value)| !f((key, value)))
+    }
+
+    /// Find a key/value pair in a map which satisfies a predicate.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::HashMap;
+    /// let map = hashmap!{1 => 11, 2 => 22, 3 => 33};
+    /// assert_eq!(Some((&2, &22)), map.find(|(_, value)| *value == 22));
+    /// assert_eq!(None, map.find(|(_, value)| *value > 33));
+    /// ```
+    #[must_use]
+    pub fn find<F>(&self, mut f: F) -> Option<(&K, &V)>
+    where
+        F: FnMut((&K, &V)) -> bool,
+    {
+        match self.root.fold_while(None, &mut |_, (key, value)| {
+            if f((key, value)) {
+                Err(Some((key, value)))
+            } else {
+                Ok(None)
+            }
+        }) {
+            Ok(_) => None,
+            Err(found) => found,
+        }
+    }
+
+    /// Collect the keys of a map into a hash set using the same hasher
+    /// as the map.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::HashMap;
+    /// # use im::hashset::HashSet;
+    /// let map = hashmap!{1 => "up", 2 => "down", 3 => "up"};
+    /// assert_eq!(hashset![1, 2, 3], map.keys_set());
+    /// ```
+    #[must_use]
+    pub fn keys_set(&self) -> HashSet<K, S>
+    where
+        K: Hash + Eq + Clone,
+        S: BuildHasher,
+    {
+        let mut out = HashSet::with_hasher(self.hasher.clone());
+        for key in self.keys() {
+            out.insert(key.clone());
+        }
+        out
+    }
+
+    /// Collect the distinct values of a map into an ordered set.
+    ///
+    /// Time: O(n log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::HashMap;
+    /// # use im::ordset::OrdSet;
+    /// let map = hashmap!{1 => "up", 2 => "down", 3 => "up"};
+    /// assert_eq!(ordset!["down", "up"], map.values_set());
+    /// ```
+    #[must_use]
+    pub fn values_set(&self) -> OrdSet<V>
+    where
+        V: Ord + Clone,
+    {
+        self.values().cloned().collect()
+    }
+
+    /// Collect the distinct values of a map into a hash set.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::HashMap;
+    /// # use im::hashset::HashSet;
+    /// let map = hashmap!{1 => "up", 2 => "down", 3 => "up"};
+    /// assert_eq!(hashset!["down", "up"], map.values_hashset());
+    /// ```
+    #[must_use]
+    pub fn values_hashset(&self) -> HashSet<V>
+    where
+        V: Hash + Eq + Clone,
+    {
+        self.values().cloned().collect()
+    }
+
+    /// Construct a reverse index of a map, mapping each distinct value
+    /// to the set of keys which map to it.
+    ///
+    /// The new map and its sets use the same hasher as the current map.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::HashMap;
+    /// let map = hashmap!{1 => "up", 2 => "down", 3 => "up"};
+    /// assert_eq!(
+    ///     hashmap!{"down" => hashset![2], "up" => hashset![1, 3]},
+    ///     map.invert()
+    /// );
+    /// ```
+    #[must_use]
+    pub fn invert(&self) -> HashMap<V, HashSet<K, S>, S>
+    where
+        K: Hash + Eq + Clone,
+        V: Hash + Eq + Clone,
+        S: BuildHasher,
+    {
+        let mut out = HashMap::with_hasher(self.hasher.clone());
+        for (key, value) in self.iter() {
+            out.entry(value.clone())
+                .or_insert_with(|| HashSet::with_hasher(self.hasher.clone()))
+                .insert(key.clone());
+        }
+        out
+    }
+
     /// Discard all elements from the map.
     ///
     /// This leaves you with an empty map, and all elements that
@@ -389,6 +651,80 @@ where
         true
     }
 
+    /// Get an iterator over the differences between this map and
+    /// another, i.e. the set of entries to add, update, or remove to
+    /// this map in order to make it equal to the other map.
+    ///
+    /// If the two maps use the same hasher (ie. one was derived from
+    /// the other, or they were created using [`new_from`][new_from]),
+    /// this function will avoid visiting trie nodes which are shared
+    /// between the two maps, meaning that even very large maps can be
+    /// compared quickly if most of their structure is shared.
+    /// Otherwise, it falls back to looking up the keys of each map in
+    /// the other.
+    ///
+    /// The differences are not produced in any particular order.
+    ///
+    /// Time: O(n) (where n is the number of unique elements across
+    /// the two maps, minus the number of elements belonging to nodes
+    /// shared between them)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::{DiffItem, HashMap};
+    /// let map1: HashMap<i32, i32> = (0..1000).map(|i| (i, i)).collect();
+    /// let map2 = map1.update(5, 6).without(&7);
+    /// let mut diff: Vec<_> = map1.diff(&map2).collect();
+    /// diff.sort_by_key(|item| match item {
+    ///     DiffItem::Add(k, _) | DiffItem::Remove(k, _) => **k,
+    ///     DiffItem::Update { old: (k, _), .. } => **k,
+    /// });
+    /// assert_eq!(
+    ///     vec![
+    ///         DiffItem::Update { old: (&5, &5), new: (&5, &6) },
+    ///         DiffItem::Remove(&7, &7),
+    ///     ],
+    ///     diff
+    /// );
+    /// ```
+    ///
+    /// [new_from]: #method.new_from
+    #[must_use]
+    pub fn diff<'a>(&'a self, other: &'a Self) -> DiffIter<'a, K, V>
+    where
+        V: PartialEq,
+    {
+        if Ref::ptr_eq(&self.hasher, &other.hasher) {
+            return DiffIter {
+                it: DiffIterInner::Trie(NodeDiffIter::new(&self.root, &other.root)),
+            };
+        }
+        let mut items = Vec::new();
+        for (key, value) in self {
+            match other.get_key_value(key) {
+                None => items.push(DiffItem::Remove(key, value)),
+                Some((new_key, new_value)) => {
+                    if value != new_value {
+                        items.push(DiffItem::Update {
+                            old: (key, value),
+                            new: (new_key, new_value),
+                        });
+                    }
+                }
+            }
+        }
+        for (key, value) in other {
+            if !self.contains_key(key) {
+                items.push(DiffItem::Add(key, value));
+            }
+        }
+        DiffIter {
+            it: DiffIterInner::Lookup(items.into_iter()),
+        }
+    }
+
     /// Get the value for a key from a hash map.
     ///
     /// Time: O(log n)
@@ -441,6 +777,54 @@ where
             .map(|&(ref k, ref v)| (k, v))
     }
 
+    /// Get a copy of the value for a key, or a default value if the key
+    /// isn't in the map.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::HashMap;
+    /// let map = hashmap!{"port" => 8080};
+    /// assert_eq!(8080, map.get_or("port", 80));
+    /// assert_eq!(30, map.get_or("timeout", 30));
+    /// ```
+    #[must_use]
+    pub fn get_or<BK>(&self, key: &BK, default: V) -> V
+    where
+        BK: Hash + Eq + ?Sized,
+        K: Borrow<BK>,
+        V: Clone,
+    {
+        self.get(key).cloned().unwrap_or(default)
+    }
+
+    /// Get a copy of the value for a key, or call a function to make a
+    /// default value if the key isn't in the map.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::HashMap;
+    /// let map = hashmap!{"host" => "example.com".to_string()};
+    /// assert_eq!("localhost", map.get_or_else("proxy", || "localhost".to_string()));
+    /// ```
+    #[must_use]
+    pub fn get_or_else<BK, F>(&self, key: &BK, f: F) -> V
+    where
+        BK: Hash + Eq + ?Sized,
+        K: Borrow<BK>,
+        V: Clone,
+        F: FnOnce() -> V,
+    {
+        self.get(key).cloned().unwrap_or_else(f)
+    }
+
     /// Test for the presence of a key in a hash map.
     ///
     /// Time: O(log n)
@@ -481,8 +865,12 @@ where
         F: FnMut(&V, &B) -> bool,
         RM: Borrow<HashMap<K, B, S>>,
     {
+        let other = other.borrow();
+        if other.len() < self.len() {
+            return false;
+        }
         self.iter()
-            .all(|(k, v)| other.borrow().get(k).map(|ov| cmp(v, ov)).unwrap_or(false))
+            .all(|(k, v)| other.get(k).map(|ov| cmp(v, ov)).unwrap_or(false))
     }
 
     /// Test whether a map is a proper submap of another map, meaning
@@ -556,6 +944,45 @@ where
     {
         self.is_proper_submap_by(other.borrow(), PartialEq::eq)
     }
+
+    /// Test whether a map is a supermap of another map, meaning that
+    /// all keys in the other map must also be in our map, with the
+    /// same values.
+    ///
+    /// Time: O(m log n) where m is the size of the other map
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::HashMap;
+    /// let map1 = hashmap!{1 => 1, 2 => 2, 3 => 3};
+    /// let map2 = hashmap!{1 => 1, 2 => 2};
+    /// assert!(map1.is_supermap(map2));
+    /// ```
+    #[must_use]
+    pub fn is_supermap<RM>(&self, other: RM) -> bool
+    where
+        V: PartialEq,
+        RM: Borrow<Self>,
+    {
+        other.borrow().is_submap(self)
+    }
+
+    /// Test whether a map is a proper supermap of another map, meaning
+    /// that all keys in the other map must also be in our map, with
+    /// the same values. To be a proper supermap, ours must also contain
+    /// more keys than the other map.
+    ///
+    /// Time: O(m log n) where m is the size of the other map
+    #[must_use]
+    pub fn is_proper_supermap<RM>(&self, other: RM) -> bool
+    where
+        V: PartialEq,
+        RM: Borrow<Self>,
+    {
+        other.borrow().is_proper_submap(self)
+    }
 }
 
 impl<K, V, S> HashMap<K, V, S>
@@ -643,12 +1070,13 @@ where
         result.map(|(_, v)| v)
     }
 
-    /// Remove a key/value pair from a map, if it exists, and return
-    /// the removed value.
+    /// Insert a key/value mapping into a map, combining it with the
+    /// existing value if the map already has a mapping for the key.
     ///
-    /// This is a copy-on-write operation, so that the parts of the
-    /// set's structure which are shared with other sets will be
-    /// safely copied before mutating.
+    /// If the key is already in the map, the provided function is
+    /// called with the old value and the new value, and the result
+    /// replaces the old value. Otherwise, the new value is inserted as
+    /// is.
     ///
     /// Time: O(log n)
     ///
@@ -657,16 +1085,149 @@ where
     /// ```
     /// # #[macro_use] extern crate im;
     /// # use im::hashmap::HashMap;
-    /// let mut map = hashmap!{123 => "123", 456 => "456"};
-    /// assert_eq!(Some("123"), map.remove(&123));
-    /// assert_eq!(Some("456"), map.remove(&456));
-    /// assert_eq!(None, map.remove(&789));
-    /// assert!(map.is_empty());
+    /// let mut counts = hashmap!{};
+    /// for word in "a b a c a b".split(' ') {
+    ///     counts.insert_with(word, 1, |old, new| old + new);
+    /// }
+    /// assert_eq!(hashmap!{"a" => 3, "b" => 2, "c" => 1}, counts);
     /// ```
-    pub fn remove<BK>(&mut self, k: &BK) -> Option<V>
+    pub fn insert_with<F>(&mut self, key: K, value: V, f: F)
     where
-        BK: Hash + Eq + ?Sized,
-        K: Borrow<BK>,
+        F: FnOnce(V, V) -> V,
+    {
+        self.insert_with_key(key, value, |_, old, new| f(old, new))
+    }
+
+    /// Insert a key/value mapping into a map, combining it with the
+    /// existing value if the map already has a mapping for the key.
+    ///
+    /// Like [`insert_with`][insert_with], but the function also gets
+    /// the key.
+    ///
+    /// Time: O(log n)
+    ///
+    /// [insert_with]: #method.insert_with
+    pub fn insert_with_key<F>(&mut self, key: K, value: V, f: F)
+    where
+        F: FnOnce(&K, V, V) -> V,
+    {
+        if let Some(existing) = self.get_mut(&key) {
+            let old = existing.clone();
+            *existing = f(&key, old, value);
+            return;
+        }
+        self.insert(key, value);
+    }
+
+    /// Construct a map from an iterator of key/value pairs, using a
+    /// function to combine the values when a key occurs more than once.
+    ///
+    /// The function is called with the value collected for the key so
+    /// far and the next value for it, in the order the iterator
+    /// produces them, and its result becomes the value for the key. Pass
+    /// `|first, _| first` to keep the first value for each key, or
+    /// `|_, last| last` to keep the last one, which is what
+    /// [`collect()`][collect] does.
+    ///
+    /// Time: O(n log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::HashMap;
+    /// let pairs = vec![("a", 1), ("b", 2), ("a", 3)];
+    /// let first: HashMap<_, _> = HashMap::from_iter_with(pairs.clone(), |first, _| first);
+    /// assert_eq!(hashmap!{"a" => 1, "b" => 2}, first);
+    /// let sum: HashMap<_, _> = HashMap::from_iter_with(pairs, |sum, next| sum + next);
+    /// assert_eq!(hashmap!{"a" => 4, "b" => 2}, sum);
+    /// ```
+    ///
+    /// [collect]: https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.collect
+    #[must_use]
+    pub fn from_iter_with<I, F>(iter: I, mut f: F) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        F: FnMut(V, V) -> V,
+        S: Default,
+    {
+        let mut out = Self::default();
+        for (key, value) in iter {
+            out.insert_with(key, value, &mut f);
+        }
+        out
+    }
+
+    /// Construct a map of groups from an iterator of key/value pairs,
+    /// collecting the values for each key into a collection.
+    ///
+    /// The map's value type is the collection the values go into, which
+    /// can be anything with a [`Default`][Default] and an
+    /// [`Extend`][Extend] implementation, such as a
+    /// [`Vector`][Vector] or a `Vec`. Values are added to their groups in
+    /// the order the iterator produces them.
+    ///
+    /// Time: O(n log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::HashMap;
+    /// # use im::vector::Vector;
+    /// let words = vec!["apple", "avocado", "banana", "apricot"];
+    /// let groups: HashMap<char, Vector<&str>> =
+    ///     HashMap::group_by(words.into_iter().map(|word| (word.chars().next().unwrap(), word)));
+    /// assert_eq!(
+    ///     hashmap!{
+    ///         'a' => vector!["apple", "avocado", "apricot"],
+    ///         'b' => vector!["banana"]
+    ///     },
+    ///     groups
+    /// );
+    /// ```
+    ///
+    /// [Default]: https://doc.rust-lang.org/std/default/trait.Default.html
+    /// [Extend]: https://doc.rust-lang.org/std/iter/trait.Extend.html
+    /// [Vector]: ../vector/struct.Vector.html
+    #[must_use]
+    pub fn group_by<I, A>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K, A)>,
+        V: Default + Extend<A>,
+        S: Default,
+    {
+        let mut out = Self::default();
+        for (key, value) in iter {
+            out.entry(key).or_default().extend(Some(value));
+        }
+        out
+    }
+
+    /// Remove a key/value pair from a map, if it exists, and return
+    /// the removed value.
+    ///
+    /// This is a copy-on-write operation, so that the parts of the
+    /// set's structure which are shared with other sets will be
+    /// safely copied before mutating.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::HashMap;
+    /// let mut map = hashmap!{123 => "123", 456 => "456"};
+    /// assert_eq!(Some("123"), map.remove(&123));
+    /// assert_eq!(Some("456"), map.remove(&456));
+    /// assert_eq!(None, map.remove(&789));
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn remove<BK>(&mut self, k: &BK) -> Option<V>
+    where
+        BK: Hash + Eq + ?Sized,
+        K: Borrow<BK>,
     {
         self.remove_with_key(k).map(|(_, v)| v)
     }
@@ -749,6 +1310,49 @@ where
         out
     }
 
+    /// Construct a new map by inserting a key/value mapping into a
+    /// map, failing if the map already has a mapping for the key.
+    ///
+    /// Unlike [`update()`][update], this never overwrites an existing
+    /// value. If the key is already in the map, you get back an
+    /// [`OccupiedError`][OccupiedError] holding the key and value you
+    /// tried to insert along with the existing value.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::{HashMap, OccupiedError};
+    /// let map = hashmap!{123 => "123"};
+    /// assert_eq!(
+    ///     Ok(hashmap!{123 => "123", 456 => "456"}),
+    ///     map.try_insert(456, "456")
+    /// );
+    /// assert_eq!(
+    ///     Err(OccupiedError {
+    ///         key: 123,
+    ///         value: "new",
+    ///         existing: "123"
+    ///     }),
+    ///     map.try_insert(123, "new")
+    /// );
+    /// ```
+    ///
+    /// [update]: #method.update
+    /// [OccupiedError]: ./struct.OccupiedError.html
+    pub fn try_insert(&self, k: K, v: V) -> Result<Self, OccupiedError<K, V>> {
+        match self.get(&k) {
+            Some(existing) => Err(OccupiedError {
+                key: k,
+                value: v,
+                existing: existing.clone(),
+            }),
+            None => Ok(self.update(k, v)),
+        }
+    }
+
     /// Construct a new hash map by inserting a key/value mapping into
     /// a map.
     ///
@@ -824,6 +1428,18 @@ where
     ///
     /// Time: O(log n)
     ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::HashMap;
+    /// let count = |n: Option<i32>| Some(n.map_or(1, |n| n + 1));
+    /// let map = hashmap!{"a" => 1};
+    /// assert_eq!(hashmap!{"a" => 2}, map.alter(count, "a"));
+    /// assert_eq!(hashmap!{"a" => 1, "b" => 1}, map.alter(count, "b"));
+    /// assert_eq!(hashmap!{}, map.alter(|_| None, "a"));
+    /// ```
+    ///
     /// [std::option::Option]: https://doc.rust-lang.org/std/option/enum.Option.html
     #[must_use]
     pub fn alter<F>(&self, f: F, k: K) -> Self
@@ -839,6 +1455,68 @@ where
         }
     }
 
+    /// Construct a new map with the value for a given key replaced by
+    /// the result of calling a function on it, or a copy of the map if
+    /// the key isn't in it.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::HashMap;
+    /// let map = hashmap!{"a" => 1};
+    /// assert_eq!(hashmap!{"a" => 11}, map.adjust("a", |v| v + 10));
+    /// assert_eq!(map, map.adjust("b", |v| v + 10));
+    /// ```
+    #[must_use]
+    pub fn adjust<BK, F>(&self, key: &BK, f: F) -> Self
+    where
+        BK: Hash + Eq + ?Sized,
+        K: Borrow<BK>,
+        F: FnOnce(&V) -> V,
+    {
+        self.adjust_with_key(key, |_, value| f(value))
+    }
+
+    /// Construct a new map with the value for a given key replaced by
+    /// the result of calling a function on the key and the value, or a
+    /// copy of the map sharing all of its nodes if the key isn't in it.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::HashMap;
+    /// let map = hashmap!{"a" => 1};
+    /// assert_eq!(
+    ///     hashmap!{"a" => 2},
+    ///     map.adjust_with_key("a", |k, v| v + k.len())
+    /// );
+    /// ```
+    #[must_use]
+    pub fn adjust_with_key<BK, F>(&self, key: &BK, f: F) -> Self
+    where
+        BK: Hash + Eq + ?Sized,
+        K: Borrow<BK>,
+        F: FnOnce(&K, &V) -> V,
+    {
+        let mut out = self.clone();
+        // Don't copy the path to a key that isn't there.
+        if !self.contains_key(key) {
+            return out;
+        }
+        let root = PoolRef::make_mut(&out.pool.0, &mut out.root);
+        let hash = hash_key(&*out.hasher, key);
+        if let Some((key, value)) = root.get_mut(&out.pool.0, hash, 0, key) {
+            *value = f(key, value);
+        }
+        out
+    }
+
     /// Construct a new map without the given key.
     ///
     /// Construct a map that's a copy of the current map, absent the
@@ -857,12 +1535,108 @@ where
         }
     }
 
+    /// Construct a new map with the same keys, applying a function to
+    /// every value.
+    ///
+    /// The new map uses the same hasher, and is built with the same
+    /// shape as the current one, without rehashing any keys.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::HashMap;
+    /// let map = hashmap!{1 => "one", 2 => "two"};
+    /// assert_eq!(hashmap!{1 => 3, 2 => 3}, map.map_values(|v| v.len()));
+    /// ```
+    #[must_use]
+    pub fn map_values<W, F>(&self, mut f: F) -> HashMap<K, W, S>
+    where
+        F: FnMut(&V) -> W,
+    {
+        self.map_with_key(|_, value| f(value))
+    }
+
+    /// Construct a new map with the same keys, applying a function to
+    /// every key and value pair and using the result as the new value.
+    ///
+    /// The new map uses the same hasher, and is built with the same
+    /// shape as the current one, without rehashing any keys.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::HashMap;
+    /// let map = hashmap!{1 => 10, 2 => 20};
+    /// assert_eq!(hashmap!{1 => 11, 2 => 22}, map.map_with_key(|k, v| k + v));
+    /// ```
+    #[must_use]
+    pub fn map_with_key<W, F>(&self, mut f: F) -> HashMap<K, W, S>
+    where
+        F: FnMut(&K, &V) -> W,
+    {
+        let pool = HashMapPool::default();
+        let root = self.root.map_values(&pool.0, &mut |(key, value): &(K, V)| {
+            (key.clone(), f(key, value))
+        });
+        HashMap {
+            size: self.size,
+            root: PoolRef::new(&pool.0, root),
+            pool,
+            hasher: self.hasher.clone(),
+        }
+    }
+
+    /// Construct a new map with the same values, applying a function to
+    /// every key and using the result as the new key.
+    ///
+    /// If more than one key maps to the same new key, `resolve` is called
+    /// with the new key, the value collected for it so far and the next
+    /// value for it, and its result becomes the value for the new key.
+    /// Values are visited in the order the map iterates over them, and
+    /// the new map uses the same hasher as the current one.
+    ///
+    /// Time: O(n log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::HashMap;
+    /// let map = hashmap!{"A" => 1, "a" => 2, "b" => 3};
+    /// let lower = map.map_keys(|k| k.to_lowercase(), |_, v1, v2| v1 + v2);
+    /// assert_eq!(hashmap!{"a".to_string() => 3, "b".to_string() => 3}, lower);
+    /// ```
+    #[must_use]
+    pub fn map_keys<K2, F, R>(&self, mut f: F, mut resolve: R) -> HashMap<K2, V, S>
+    where
+        K2: Hash + Eq + Clone,
+        F: FnMut(&K) -> K2,
+        R: FnMut(&K2, V, V) -> V,
+    {
+        let mut out = HashMap::with_hasher(self.hasher.clone());
+        for (key, value) in self.iter() {
+            let key = f(key);
+            let value = match out.remove(&key) {
+                None => value.clone(),
+                Some(previous) => resolve(&key, previous, value.clone()),
+            };
+            out.insert(key, value);
+        }
+        out
+    }
+
     /// Filter out values from a map which don't satisfy a predicate.
     ///
-    /// This is slightly more efficient than filtering using an
-    /// iterator, in that it doesn't need to rehash the retained
-    /// values, but it still needs to reconstruct the entire tree
-    /// structure of the map.
+    /// This is more efficient than filtering using an iterator, in
+    /// that it doesn't need to rehash the retained values, and if the
+    /// map isn't shared with any other map, the removed values are
+    /// removed in place instead of reconstructing the tree.
     ///
     /// Time: O(n log n)
     ///
@@ -880,15 +1654,182 @@ where
     where
         F: FnMut(&K, &V) -> bool,
     {
-        let old_root = self.root.clone();
+        let removed: Vec<(K, HashBits)> = NodeIter::new(&self.root, self.size)
+            .filter(|((key, value), _)| !f(key, value))
+            .map(|((key, _), hash)| (key.clone(), hash))
+            .collect();
+        if removed.is_empty() {
+            return;
+        }
         let root = PoolRef::make_mut(&self.pool.0, &mut self.root);
-        for ((key, value), hash) in NodeIter::new(&old_root, self.size) {
-            if !f(key, value) && root.remove(&self.pool.0, hash, 0, key).is_some() {
+        for (key, hash) in removed {
+            if root.remove(&self.pool.0, hash, 0, &key).is_some() {
                 self.size -= 1;
             }
         }
     }
 
+    /// Remove the key/value pairs which satisfy a predicate from a map,
+    /// and return them in a new map.
+    ///
+    /// Like [`retain`][retain], this removes the pairs in place if the
+    /// map isn't shared with any other map.
+    ///
+    /// Time: O(n log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::HashMap;
+    /// let mut map = hashmap!{1 => 1, 2 => 2, 3 => 3};
+    /// let removed = map.drain_filter(|k, v| *k > 1);
+    /// assert_eq!(hashmap!{1 => 1}, map);
+    /// assert_eq!(hashmap!{2 => 2, 3 => 3}, removed);
+    /// ```
+    ///
+    /// [retain]: #method.retain
+    pub fn drain_filter<F>(&mut self, mut f: F) -> Self
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        let mut removed = self.new_from();
+        self.retain(|key, value| {
+            if f(key, value) {
+                removed.insert(key.clone(), value.clone());
+                false
+            } else {
+                true
+            }
+        });
+        removed
+    }
+
+    /// Construct a new map containing only the key/value pairs of the
+    /// current map which satisfy a predicate.
+    ///
+    /// The new map shares the parts of its tree which no pairs were
+    /// removed from with the current map.
+    ///
+    /// Time: O(n + m log n) where m is the number of removed pairs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::HashMap;
+    /// let map = hashmap!{1 => 1, 2 => 2, 3 => 3};
+    /// assert_eq!(hashmap!{2 => 2, 3 => 3}, map.filter(|k, v| *k > 1));
+    /// assert_eq!(3, map.len());
+    /// ```
+    #[must_use]
+    pub fn filter<F>(&self, f: F) -> Self
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        let mut out = self.clone();
+        out.retain(f);
+        out
+    }
+
+    /// Split a map into two maps, the first containing the key/value
+    /// pairs which satisfy a predicate and the second containing the
+    /// pairs which don't.
+    ///
+    /// This visits each pair once, and the second map shares the parts
+    /// of its tree which no pairs were removed from with the current
+    /// map.
+    ///
+    /// Time: O(n + m log n) where m is the number of pairs satisfying
+    /// the predicate
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::HashMap;
+    /// let map = hashmap!{1 => 1, 2 => 2, 3 => 3};
+    /// let (big, small) = map.partition(|k, v| *k > 1);
+    /// assert_eq!(hashmap!{2 => 2, 3 => 3}, big);
+    /// assert_eq!(hashmap!{1 => 1}, small);
+    /// ```
+    #[must_use]
+    pub fn partition<F>(&self, f: F) -> (Self, Self)
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        let mut failed = self.clone();
+        let passed = failed.drain_filter(f);
+        (passed, failed)
+    }
+
+    /// Construct a new map containing only the key/value pairs whose
+    /// keys are in a set.
+    ///
+    /// The keys of whichever of the map and the set is smaller are
+    /// looked up in the other one.
+    ///
+    /// Time: O(min(n, m) log max(n, m))
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::HashMap;
+    /// let map = hashmap!{1 => "a", 2 => "b", 3 => "c"};
+    /// assert_eq!(hashmap!{1 => "a", 3 => "c"}, map.restrict_keys(&hashset!{1, 3, 5}));
+    /// ```
+    #[must_use]
+    pub fn restrict_keys<S2>(&self, keys: &HashSet<K, S2>) -> Self
+    where
+        S2: BuildHasher,
+    {
+        if keys.len() < self.len() {
+            let mut out = HashMap::with_hasher(self.hasher.clone());
+            for key in keys {
+                if let Some((key, value)) = self.get_key_value(key) {
+                    out.insert(key.clone(), value.clone());
+                }
+            }
+            out
+        } else {
+            self.filter(|key, _| keys.contains(key))
+        }
+    }
+
+    /// Construct a new map without the key/value pairs whose keys are in
+    /// a set.
+    ///
+    /// If the set is smaller than the map, its keys are removed from a
+    /// copy of the map, which keeps sharing the parts of its tree they
+    /// don't touch. Otherwise, the map's keys are looked up in the set.
+    ///
+    /// Time: O(min(n, m) log max(n, m))
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::HashMap;
+    /// let map = hashmap!{1 => "a", 2 => "b", 3 => "c"};
+    /// assert_eq!(hashmap!{2 => "b"}, map.without_keys(&hashset!{1, 3, 5}));
+    /// ```
+    #[must_use]
+    pub fn without_keys<S2>(&self, keys: &HashSet<K, S2>) -> Self
+    where
+        S2: BuildHasher,
+    {
+        if keys.len() < self.len() {
+            let mut out = self.clone();
+            for key in keys {
+                out.remove(key);
+            }
+            out
+        } else {
+            self.filter(|key, _| !keys.contains(key))
+        }
+    }
+
     /// Remove a key/value pair from a map, if it exists, and return
     /// the removed value as well as the updated map.
     ///
@@ -916,8 +1857,50 @@ where
         out.remove_with_key(k).map(|(k, v)| (k, v, out))
     }
 
-    /// Construct the union of two maps, keeping the values in the
-    /// current map when keys exist in both maps.
+    /// Construct the union of two maps, keeping the values in the
+    /// current map when keys exist in both maps.
+    ///
+    /// If both maps use the same hasher (ie. one was derived from the
+    /// other, or they were created using [`new_from`][new_from]), the
+    /// union is performed by merging their tries directly: parts of
+    /// the trie present in only one map and parts the two maps share
+    /// are reused as is, without being rehashed or copied.
+    ///
+    /// Time: O(n log n), or O(m) for maps sharing a hasher, where m is
+    /// the number of values under trie slots occupied in both maps,
+    /// since the values in shared subtrees are still counted
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::HashMap;
+    /// let map1 = hashmap!{1 => 1, 3 => 3};
+    /// let map2 = hashmap!{2 => 2, 3 => 4};
+    /// let expected = hashmap!{1 => 1, 2 => 2, 3 => 3};
+    /// assert_eq!(expected, map1.union(map2));
+    /// ```
+    ///
+    /// [new_from]: #method.new_from
+    #[must_use]
+    pub fn union(mut self, other: Self) -> Self {
+        if Ref::ptr_eq(&self.hasher, &other.hasher) {
+            return self.merge_tries(other, Identical::Keep, |_, v, _| Some(v));
+        }
+        for (k, v) in other {
+            self.entry(k).or_insert(v);
+        }
+        self
+    }
+
+    /// Merge two maps deeply, recursing into values present in both
+    /// maps rather than replacing one with the other.
+    ///
+    /// Values present in only one of the maps are kept as is. When a
+    /// key is present in both, the two values are combined using
+    /// their [`Merge`][Merge] implementation, which for nested maps
+    /// means merging them in turn, and for leaf values means picking
+    /// one according to `strategy`.
     ///
     /// Time: O(n log n)
     ///
@@ -926,17 +1909,22 @@ where
     /// ```
     /// # #[macro_use] extern crate im;
     /// # use im::hashmap::HashMap;
-    /// let map1 = hashmap!{1 => 1, 3 => 3};
-    /// let map2 = hashmap!{2 => 2, 3 => 4};
-    /// let expected = hashmap!{1 => 1, 2 => 2, 3 => 3};
-    /// assert_eq!(expected, map1.union(map2));
+    /// # use im::merge::MergeStrategy;
+    /// let defaults = hashmap!{"a" => hashmap!{"x" => 1, "y" => 2}};
+    /// let file = hashmap!{"a" => hashmap!{"y" => 3}, "b" => hashmap!{"z" => 4}};
+    /// assert_eq!(
+    ///     hashmap!{"a" => hashmap!{"x" => 1, "y" => 3}, "b" => hashmap!{"z" => 4}},
+    ///     defaults.merge_deep(&file, MergeStrategy::PreferRight)
+    /// );
     /// ```
+    ///
+    /// [Merge]: ../merge/trait.Merge.html
     #[must_use]
-    pub fn union(mut self, other: Self) -> Self {
-        for (k, v) in other {
-            self.entry(k).or_insert(v);
-        }
-        self
+    pub fn merge_deep(&self, other: &Self, strategy: MergeStrategy) -> Self
+    where
+        V: Merge,
+    {
+        self.clone().merge(other.clone(), strategy)
     }
 
     /// Construct the union of two maps, using a function to decide
@@ -947,7 +1935,13 @@ where
     /// and the value from the other map as the second. It should
     /// return the value to be inserted in the resulting map.
     ///
+    /// Like [`union`][union], this merges the tries of maps sharing a
+    /// hasher directly, but the function is still called for every
+    /// key in both maps.
+    ///
     /// Time: O(n log n)
+    ///
+    /// [union]: #method.union
     #[inline]
     #[must_use]
     pub fn union_with<F>(self, other: Self, mut f: F) -> Self
@@ -986,6 +1980,9 @@ where
     where
         F: FnMut(&K, V, V) -> V,
     {
+        if Ref::ptr_eq(&self.hasher, &other.hasher) {
+            return self.merge_tries(other, Identical::Visit, |k, v1, v2| Some(f(k, v1, v2)));
+        }
         for (key, right_value) in other {
             match self.remove(&key) {
                 None => {
@@ -1111,6 +2108,9 @@ where
     #[inline]
     #[must_use]
     pub fn symmetric_difference(self, other: Self) -> Self {
+        if Ref::ptr_eq(&self.hasher, &other.hasher) {
+            return self.merge_tries(other, Identical::Drop, |_, _, _| None);
+        }
         self.symmetric_difference_with_key(other, |_, _, _| None)
     }
 
@@ -1182,6 +2182,10 @@ where
     /// to decide what to do if a key occurs in both. The function
     /// receives the key as well as both values.
     ///
+    /// If both maps use the same hasher, this merges their tries
+    /// directly, reusing any part of the trie which is only present in
+    /// one of them.
+    ///
     /// Time: O(n log n)
     ///
     /// # Examples
@@ -1202,6 +2206,9 @@ where
     where
         F: FnMut(&K, V, V) -> Option<V>,
     {
+        if Ref::ptr_eq(&self.hasher, &other.hasher) {
+            return self.merge_tries(other, Identical::Visit, f);
+        }
         let mut out = self.new_from();
         for (key, right_value) in other {
             match self.remove(&key) {
@@ -1283,6 +2290,10 @@ where
     /// with the key and both values for each key and using the result
     /// as the value for the key.
     ///
+    /// If both maps use the same hasher, this walks their tries side
+    /// by side, skipping any part of the trie which is only present in
+    /// one of them.
+    ///
     /// Time: O(n log n)
     ///
     /// # Examples
@@ -1309,6 +2320,27 @@ where
         C: Clone,
         F: FnMut(&K, V, B) -> C,
     {
+        if Ref::ptr_eq(&self.hasher, &other.hasher) {
+            let pool = HashMapPool::default();
+            let mut size = 0;
+            let root = Node::intersect(
+                &pool.0,
+                &self.root,
+                &other.root,
+                0,
+                &mut |(k, v1), (_, v2)| {
+                    let v = f(&k, v1, v2);
+                    (k, v)
+                },
+                &mut size,
+            );
+            return HashMap {
+                size,
+                root: PoolRef::new(&pool.0, root),
+                pool,
+                hasher: self.hasher,
+            };
+        }
         let mut out = self.new_from();
         for (key, right_value) in other {
             match self.remove(&key) {
@@ -1321,6 +2353,128 @@ where
         }
         out
     }
+
+    /// Merge two maps, deciding what to do with each key with one of
+    /// three functions, depending on whether the key is in both maps,
+    /// only in the current map, or only in the other map.
+    ///
+    /// Each function receives the key and the value or values for it,
+    /// and returns the value for the key in the resulting map, or `None`
+    /// to leave the key out. The more specific operations, like
+    /// [`union_with_key`][union_with_key],
+    /// [`intersection_with_key`][intersection_with_key] and
+    /// [`relative_complement`][relative_complement], can all be
+    /// expressed in terms of this one.
+    ///
+    /// If both maps use the same hasher, this walks their tries side
+    /// by side in a single pass, without hashing any keys.
+    ///
+    /// Time: O(n log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashmap::HashMap;
+    /// let ours = hashmap!{"a" => 1, "b" => 2, "c" => 3};
+    /// let theirs = hashmap!{"b" => 20, "c" => 30, "d" => 40};
+    /// let merged = ours.merge_with_key(
+    ///     theirs,
+    ///     |_, ours, theirs| if ours == theirs / 10 { Some(theirs) } else { None },
+    ///     |_, ours| Some(ours),
+    ///     |_, theirs| Some(theirs),
+    /// );
+    /// assert_eq!(hashmap!{"a" => 1, "b" => 20, "c" => 30, "d" => 40}, merged);
+    /// ```
+    ///
+    /// [union_with_key]: #method.union_with_key
+    /// [intersection_with_key]: #method.intersection_with_key
+    /// [relative_complement]: #method.relative_complement
+    #[must_use]
+    pub fn merge_with_key<B, C, FB, FL, FR>(
+        self,
+        mut other: HashMap<K, B, S>,
+        mut both: FB,
+        mut left_only: FL,
+        mut right_only: FR,
+    ) -> HashMap<K, C, S>
+    where
+        B: Clone,
+        C: Clone,
+        FB: FnMut(&K, V, B) -> Option<C>,
+        FL: FnMut(&K, V) -> Option<C>,
+        FR: FnMut(&K, B) -> Option<C>,
+    {
+        if Ref::ptr_eq(&self.hasher, &other.hasher) {
+            let pool = HashMapPool::default();
+            let mut size = 0;
+            let root = Node::merge_with(
+                &pool.0,
+                &self.root,
+                &other.root,
+                0,
+                &mut |merged| match merged {
+                    Merged::Left((k, v)) => left_only(&k, v).map(|v| (k, v)),
+                    Merged::Right((k, v)) => right_only(&k, v).map(|v| (k, v)),
+                    Merged::Both((k, v1), (_, v2)) => both(&k, v1, v2).map(|v| (k, v)),
+                },
+                &mut size,
+            );
+            return HashMap {
+                size,
+                root: PoolRef::new(&pool.0, root),
+                pool,
+                hasher: self.hasher,
+            };
+        }
+        let mut out = self.new_from();
+        for (key, left_value) in self {
+            let value = match other.remove(&key) {
+                Some(right_value) => both(&key, left_value, right_value),
+                None => left_only(&key, left_value),
+            };
+            if let Some(value) = value {
+                out.insert(key, value);
+            }
+        }
+        for (key, right_value) in other {
+            if let Some(value) = right_only(&key, right_value) {
+                out.insert(key, value);
+            }
+        }
+        out
+    }
+
+    /// Merge the tries of two maps sharing a hasher, calling `f` for
+    /// keys present in both maps and keeping the rest.
+    fn merge_tries<F>(self, other: Self, identical: Identical, mut f: F) -> Self
+    where
+        F: FnMut(&K, V, V) -> Option<V>,
+    {
+        if PoolRef::ptr_eq(&self.root, &other.root) {
+            match identical {
+                Identical::Keep => return self,
+                Identical::Drop => return self.new_from(),
+                Identical::Visit => {}
+            }
+        }
+        let mut removed = 0;
+        let root = Node::merge(
+            &self.pool.0,
+            &self.root,
+            &other.root,
+            0,
+            identical,
+            &mut |(k, v1), (_, v2)| f(&k, v1, v2).map(|v| (k, v)),
+            &mut removed,
+        );
+        HashMap {
+            size: self.size + other.size - removed,
+            root: PoolRef::new(&self.pool.0, root),
+            pool: self.pool,
+            hasher: self.hasher,
+        }
+    }
 }
 
 // Entries
@@ -1590,6 +2744,29 @@ where
 {
 }
 
+impl<K, V, S, RS> PartialEq<collections::HashMap<K, V, RS>> for HashMap<K, V, S>
+where
+    K: Hash + Eq,
+    V: PartialEq,
+    S: BuildHasher,
+    RS: BuildHasher,
+{
+    fn eq(&self, other: &collections::HashMap<K, V, RS>) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl<K, V, S> PartialEq<collections::BTreeMap<K, V>> for HashMap<K, V, S>
+where
+    K: Hash + Eq + Ord,
+    V: PartialEq,
+    S: BuildHasher,
+{
+    fn eq(&self, other: &collections::BTreeMap<K, V>) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
 impl<K, V, S> PartialOrd for HashMap<K, V, S>
 where
     K: Hash + Eq + Clone + PartialOrd,
@@ -1677,6 +2854,18 @@ where
     }
 }
 
+impl<K, V, S> Merge for HashMap<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Merge + Clone,
+    S: BuildHasher,
+{
+    /// Merge two maps, merging the values of keys present in both.
+    fn merge(self, other: Self, strategy: MergeStrategy) -> Self {
+        self.union_with(other, |left, right| left.merge(right, strategy))
+    }
+}
+
 impl<K, V, S> Sum for HashMap<K, V, S>
 where
     K: Hash + Eq + Clone,
@@ -1812,6 +3001,41 @@ impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {}
 
 impl<'a, K, V> FusedIterator for Iter<'a, K, V> {}
 
+/// An iterator over clones of the key/value pairs of a map.
+pub struct ClonedIter<'a, K, V> {
+    it: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for ClonedIter<'a, K, V>
+where
+    K: Clone,
+    V: Clone,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.it.next().map(|(k, v)| (k.clone(), v.clone()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for ClonedIter<'a, K, V>
+where
+    K: Clone,
+    V: Clone,
+{
+}
+
+impl<'a, K, V> FusedIterator for ClonedIter<'a, K, V>
+where
+    K: Clone,
+    V: Clone,
+{
+}
+
 /// A mutable iterator over the elements of a map.
 pub struct IterMut<'a, K, V>
 where
@@ -1875,6 +3099,64 @@ impl<A> ExactSizeIterator for ConsumingIter<A> where A: HashValue + Clone {}
 
 impl<A> FusedIterator for ConsumingIter<A> where A: HashValue + Clone {}
 
+/// An iterator over the differences between two maps.
+pub struct DiffIter<'a, K, V> {
+    it: DiffIterInner<'a, K, V>,
+}
+
+enum DiffIterInner<'a, K, V> {
+    Trie(NodeDiffIter<'a, (K, V)>),
+    Lookup(std::vec::IntoIter<DiffItem<'a, K, V>>),
+}
+
+/// A description of a difference between two hash maps.
+#[derive(PartialEq, Eq, Debug)]
+pub enum DiffItem<'a, K, V> {
+    /// This value has been added to the new map.
+    Add(&'a K, &'a V),
+    /// This value has been changed between the two maps.
+    Update {
+        /// The old value.
+        old: (&'a K, &'a V),
+        /// The new value.
+        new: (&'a K, &'a V),
+    },
+    /// This value has been removed from the new map.
+    Remove(&'a K, &'a V),
+}
+
+impl<'a, K, V> Iterator for DiffIter<'a, K, V>
+where
+    K: Eq,
+    V: PartialEq,
+{
+    type Item = DiffItem<'a, K, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.it {
+            DiffIterInner::Trie(it) => it.next().map(|item| match item {
+                NodeDiffItem::Add((k, v)) => DiffItem::Add(k, v),
+                NodeDiffItem::Update {
+                    old: (oldk, oldv),
+                    new: (newk, newv),
+                } => DiffItem::Update {
+                    old: (oldk, oldv),
+                    new: (newk, newv),
+                },
+                NodeDiffItem::Remove((k, v)) => DiffItem::Remove(k, v),
+            }),
+            DiffIterInner::Lookup(it) => it.next(),
+        }
+    }
+}
+
+impl<'a, K, V> FusedIterator for DiffIter<'a, K, V>
+where
+    K: Eq,
+    V: PartialEq,
+{
+}
+
 /// An iterator over the keys of a map.
 pub struct Keys<'a, K, V> {
     it: NodeIter<'a, (K, V)>,
@@ -1956,6 +3238,13 @@ where
     V: Clone,
     S: BuildHasher + Default,
 {
+    /// Collect key/value pairs into a map.
+    ///
+    /// If a key occurs more than once, the last value for it wins. Use
+    /// [`HashMap::from_iter_with()`][from_iter_with] to choose what
+    /// happens instead.
+    ///
+    /// [from_iter_with]: ./struct.HashMap.html#method.from_iter_with
     fn from_iter<T>(i: T) -> Self
     where
         T: IntoIterator<Item = (K, V)>,
@@ -2068,6 +3357,80 @@ where
     }
 }
 
+impl<K, V, S> From<HashMap<K, V, S>> for Vec<(K, V)>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher,
+{
+    /// Create a [`std::vec::Vec`][vec] from a map, with the key/value
+    /// pairs in the map's iteration order.
+    ///
+    /// Time: O(n)
+    ///
+    /// [vec]: https://doc.rust-lang.org/std/vec/struct.Vec.html
+    fn from(map: HashMap<K, V, S>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl<K, V, S> From<&HashMap<K, V, S>> for Vec<(K, V)>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher,
+{
+    fn from(map: &HashMap<K, V, S>) -> Self {
+        map.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+    }
+}
+
+impl<K, V, S> From<HashMap<K, V, S>> for collections::BTreeMap<K, V>
+where
+    K: Ord + Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher,
+{
+    fn from(map: HashMap<K, V, S>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl<K, V, S> From<&HashMap<K, V, S>> for collections::BTreeMap<K, V>
+where
+    K: Ord + Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher,
+{
+    fn from(map: &HashMap<K, V, S>) -> Self {
+        map.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+    }
+}
+
+impl<K, V, S, SB> From<HashMap<K, V, S>> for collections::HashMap<K, V, SB>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher,
+    SB: BuildHasher + Default,
+{
+    fn from(map: HashMap<K, V, S>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl<K, V, S, SB> From<&HashMap<K, V, S>> for collections::HashMap<K, V, SB>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher,
+    SB: BuildHasher + Default,
+{
+    fn from(map: &HashMap<K, V, S>) -> Self {
+        map.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+    }
+}
+
 // impl<K: Ord + Hash + Eq, V, S> From<OrdMap<K, V>> for HashMap<K, V, S>
 // where
 //     S: BuildHasher + Default,
@@ -2103,9 +3466,11 @@ pub mod proptest {
 mod test {
     use super::*;
     use crate::test::LolHasher;
+    use ::proptest::bool;
     use ::proptest::num::{i16, usize};
     use ::proptest::{collection, proptest};
     use std::hash::BuildHasherDefault;
+    use typenum::U8;
 
     #[test]
     fn safe_mutation() {
@@ -2193,6 +3558,18 @@ mod test {
         }
     }
 
+    #[test]
+    fn into_std() {
+        let map = hashmap! {1 => "a", 2 => "b", 3 => "c"};
+        let mut vec = Vec::from(&map);
+        vec.sort();
+        assert_eq!(vec![(1, "a"), (2, "b"), (3, "c")], vec);
+        let btree_map: collections::BTreeMap<i32, &str> = map.clone().into();
+        assert_eq!(map, HashMap::from(btree_map));
+        let hash_map: collections::HashMap<i32, &str> = map.clone().into();
+        assert_eq!(map, HashMap::from(hash_map));
+    }
+
     #[test]
     fn entry_api() {
         let mut map = hashmap! {"bar" => 5};
@@ -2230,7 +3607,214 @@ mod test {
         }
     }
 
+    fn check_merges<S>(
+        base: &collections::HashMap<i16, i16>,
+        left: &collections::HashMap<i16, i16>,
+        right: &collections::HashMap<i16, i16>,
+    ) where
+        S: BuildHasher + Default,
+    {
+        let mut base_map: HashMap<i16, i16, S> = HashMap::with_hasher(S::default());
+        base_map.extend(base.clone());
+        let mut map1 = base_map.clone();
+        map1.extend(left.clone());
+        let mut map2 = base_map;
+        map2.extend(right.clone());
+        // The same contents as map2, but with a hasher of its own, which
+        // forces the merges to go through the non-structural code path.
+        let mut fresh2: HashMap<i16, i16, S> = HashMap::with_hasher(S::default());
+        fresh2.extend(map2.clone());
+
+        fn check<K: Hash + Eq + Clone, V: PartialEq + Clone + Debug, S: BuildHasher>(
+            map: &HashMap<K, V, S>,
+        ) {
+            assert_eq!(map.len(), map.iter().count());
+            let mut map = map.clone();
+            let keys: Vec<K> = map.keys().cloned().collect();
+            for key in keys {
+                assert!(map.remove(&key).is_some());
+            }
+            assert!(map.is_empty());
+        }
+
+        let structural = map1.clone().union(map2.clone());
+        check(&structural);
+        assert_eq!(map1.clone().union(fresh2.clone()), structural);
+
+        let add = |_: &i16, a: i16, b: i16| a.wrapping_add(b);
+        let structural = map1.clone().union_with_key(map2.clone(), add);
+        check(&structural);
+        assert_eq!(map1.clone().union_with_key(fresh2.clone(), add), structural);
+
+        let structural = map1.clone().symmetric_difference(map2.clone());
+        check(&structural);
+        assert_eq!(
+            map1.clone().symmetric_difference(fresh2.clone()),
+            structural
+        );
+
+        let even = |_: &i16, a: i16, b: i16| if (a ^ b) & 1 == 0 { Some(a) } else { None };
+        let structural = map1
+            .clone()
+            .symmetric_difference_with_key(map2.clone(), even);
+        check(&structural);
+        assert_eq!(
+            map1.clone()
+                .symmetric_difference_with_key(fresh2.clone(), even),
+            structural
+        );
+
+        let odd = |_: &i16, a: i16| if a & 1 == 1 { Some(i32::from(a)) } else { None };
+        let diff = |_: &i16, a: i16, b: i16| Some(i32::from(a) - i32::from(b));
+        let structural = map1.clone().merge_with_key(map2.clone(), diff, odd, odd);
+        check(&structural);
+        assert_eq!(
+            map1.clone().merge_with_key(fresh2.clone(), diff, odd, odd),
+            structural
+        );
+
+        let sum = |_: &i16, a: i16, b: i16| i32::from(a) + i32::from(b);
+        let structural = map1.clone().intersection_with_key(map2.clone(), sum);
+        check(&structural);
+        assert_eq!(map1.intersection_with_key(fresh2, sum), structural);
+    }
+
+    #[test]
+    fn merge_identical_maps() {
+        let map: HashMap<usize, usize> = HashMap::from_iter((0..10_000).map(|i| (i, i)));
+        assert!(map.clone().union(map.clone()).ptr_eq(&map));
+        assert!(map.clone().symmetric_difference(map.clone()).is_empty());
+        let mut other = map.clone();
+        other.insert(5, 6);
+        other.insert(10_000, 10_000);
+        let union = map.clone().union(other.clone());
+        assert_eq!(10_001, union.len());
+        assert_eq!(Some(&5), union.get(&5));
+        let diff = map.symmetric_difference(other);
+        assert_eq!(1, diff.len());
+        assert_eq!(Some(&10_000), diff.get(&10_000));
+    }
+
+    fn check_diff<S>(old: &HashMap<i16, i16, S>, new: &HashMap<i16, i16, S>)
+    where
+        S: BuildHasher,
+    {
+        let mut diff: Vec<_> = old.diff(new).collect();
+        let mut expected: Vec<_> = old
+            .iter()
+            .filter_map(|(k, v)| match new.get(k) {
+                None => Some(DiffItem::Remove(k, v)),
+                Some(new_v) if new_v != v => Some(DiffItem::Update {
+                    old: (k, v),
+                    new: (k, new_v),
+                }),
+                _ => None,
+            })
+            .chain(
+                new.iter()
+                    .filter(|(k, _)| !old.contains_key(k))
+                    .map(|(k, v)| DiffItem::Add(k, v)),
+            )
+            .collect();
+        let key = |item: &DiffItem<'_, i16, i16>| match item {
+            DiffItem::Add(k, _) | DiffItem::Remove(k, _) => **k,
+            DiffItem::Update { old: (k, _), .. } => **k,
+        };
+        diff.sort_by_key(key);
+        expected.sort_by_key(key);
+        assert_eq!(expected, diff);
+    }
+
+    #[test]
+    fn diff_shared_structure() {
+        let map1: HashMap<usize, usize> = HashMap::from_iter((0..100_000).map(|i| (i, i)));
+        assert_eq!(0, map1.diff(&map1.clone()).count());
+        let mut map2 = map1.clone();
+        map2.insert(50_000, 0);
+        map2.remove(&1234);
+        map2.insert(100_000, 0);
+        assert_eq!(3, map1.diff(&map2).count());
+    }
+
+    #[test]
+    fn adjust_missing_key_shares_tree() {
+        let map = hashmap! {1 => 1, 2 => 2};
+        assert!(map.ptr_eq(&map.adjust(&3, |value| value + 1)));
+        assert_eq!(hashmap! {1 => 1, 2 => 3}, map.adjust(&2, |value| value + 1));
+    }
+
     proptest! {
+        #[test]
+        fn diff_all_values(
+            ref base in collection::hash_map(i16::ANY, i16::ANY, 0..100),
+            ref changes in collection::vec((i16::ANY, i16::ANY, bool::ANY), 0..100)
+        ) {
+            fn check<S: BuildHasher + Default>(
+                base: &collections::HashMap<i16, i16>,
+                changes: &[(i16, i16, bool)],
+            ) {
+                let mut old: HashMap<i16, i16, S> = HashMap::with_hasher(S::default());
+                old.extend(base.clone());
+                let mut new = old.clone();
+                for &(k, v, remove) in changes {
+                    if remove {
+                        new.remove(&k);
+                    } else {
+                        new.insert(k, v);
+                    }
+                }
+                check_diff(&old, &new);
+                check_diff(&new, &old);
+                // Same contents with an unrelated hasher.
+                let mut fresh: HashMap<i16, i16, S> = HashMap::with_hasher(S::default());
+                fresh.extend(new.clone());
+                check_diff(&old, &fresh);
+            }
+            check::<RandomState>(base, changes);
+            check::<BuildHasherDefault<LolHasher>>(base, changes);
+            check::<BuildHasherDefault<LolHasher<U8>>>(base, changes);
+        }
+
+        #[test]
+        fn structural_merges(
+            ref base in collection::hash_map(i16::ANY, i16::ANY, 0..100),
+            ref left in collection::hash_map(i16::ANY, i16::ANY, 0..100),
+            ref right in collection::hash_map(i16::ANY, i16::ANY, 0..100)
+        ) {
+            check_merges::<RandomState>(base, left, right);
+            check_merges::<BuildHasherDefault<LolHasher>>(base, left, right);
+            check_merges::<BuildHasherDefault<LolHasher<U8>>>(base, left, right);
+        }
+
+        #[test]
+        fn internal_iteration(ref m in collection::hash_map(i16::ANY, i16::ANY, 0..100)) {
+            let mut map: HashMap<i16, i16, BuildHasherDefault<LolHasher<U8>>> = Default::default();
+            map.extend(m.clone());
+            let order: Vec<(&i16, &i16)> = map.iter().collect();
+            let folded = map.fold(Vec::new(), |mut out, pair| {
+                out.push(pair);
+                out
+            });
+            assert_eq!(order, folded);
+            for (key, value) in m {
+                assert_eq!(Some((key, value)), map.find(|(k, _)| k == key));
+                assert!(map.any(|(k, _)| k == key));
+                assert!(!map.all(|(k, _)| k != key));
+            }
+            assert!(map.all(|(key, value)| m.get(key) == Some(value)));
+        }
+
+        #[test]
+        fn map_with_key(ref m in collection::hash_map(i16::ANY, i16::ANY, 0..100)) {
+            let mut map: HashMap<i16, i16, BuildHasherDefault<LolHasher<U8>>> = Default::default();
+            map.extend(m.clone());
+            let mapped = map.map_with_key(|key, value| i32::from(*key) - i32::from(*value));
+            assert_eq!(m.len(), mapped.len());
+            for (key, value) in m {
+                assert_eq!(Some(&(i32::from(*key) - i32::from(*value))), mapped.get(key));
+            }
+        }
+
         #[test]
         fn update_and_length(ref m in collection::hash_map(i16::ANY, i16::ANY, 0..100)) {
             let mut map: HashMap<i16, i16, BuildHasherDefault<LolHasher>> = Default::default();
@@ -2261,6 +3845,17 @@ mod test {
             assert_eq!(map1, map2);
         }
 
+        #[test]
+        fn equality_with_std(ref m in collection::hash_map(i16::ANY, i16::ANY, 0..100)) {
+            let map: HashMap<i16, i16> = FromIterator::from_iter(m.iter().map(|(k, v)| (*k, *v)));
+            let tree: collections::BTreeMap<i16, i16> = m.clone().into_iter().collect();
+            assert_eq!(&map, m);
+            assert_eq!(map, tree);
+            if let Some(key) = m.keys().next() {
+                assert_ne!(map.without(key), tree);
+            }
+        }
+
         #[test]
         fn lookup(ref m in collection::hash_map(i16::ANY, i16::ANY, 0..100)) {
             let map: HashMap<i16, i16> = FromIterator::from_iter(m.iter().map(|(k, v)| (*k, *v)));
@@ -27,14 +27,18 @@ use std::collections::hash_map::RandomState;
 use std::collections::{self, BTreeSet};
 use std::fmt::{Debug, Error, Formatter};
 use std::hash::{BuildHasher, Hash, Hasher};
-use std::iter::FusedIterator;
+use std::iter::{Cloned, FusedIterator};
 use std::iter::{FromIterator, IntoIterator, Sum};
-use std::ops::{Add, Deref, Mul};
+use std::ops::{Add, BitAnd, BitOr, BitXor, Deref, Mul, Sub};
 
-use crate::nodes::hamt::{hash_key, Drain as NodeDrain, HashValue, Iter as NodeIter, Node};
+use crate::nodes::hamt::{
+    hash_key, Drain as NodeDrain, HashBits, HashValue, Identical, Iter as NodeIter, Node,
+};
 use crate::ordset::OrdSet;
 use crate::util::{Pool, PoolRef, Ref};
 
+pub use crate::error::set::OccupiedError;
+
 /// Construct a set from a sequence of values.
 ///
 /// # Examples
@@ -323,6 +327,147 @@ impl<A, S> HashSet<A, S> {
             it: NodeIter::new(&self.root, self.size),
         }
     }
+
+    /// Get an iterator over clones of the values in a hash set.
+    ///
+    /// The values come out in the same order as they do from
+    /// [`iter()`][iter].
+    ///
+    /// [iter]: #method.iter
+    pub fn cloned_iter(&self) -> Cloned<Iter<'_, A>>
+    where
+        A: Clone,
+    {
+        self.iter().cloned()
+    }
+
+    /// Fold the values of a set into an accumulator.
+    ///
+    /// This walks the tree directly rather than going through an
+    /// iterator, which makes it faster than `iter().fold()`.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashset::HashSet;
+    /// let set = hashset!{1, 2, 3};
+    /// assert_eq!(6, set.fold(0, |sum, value| sum + value));
+    /// ```
+    pub fn fold<'a, B, F>(&'a self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, &'a A) -> B,
+    {
+        match self
+            .root
+            .fold_while(init, &mut |acc, value| Ok(f(acc, value)))
+        {
+            Ok(acc) | Err(acc) => acc,
+        }
+    }
+
+    /// Combine the values of a set using `f`, or return `None`
+    /// if the set is empty.
+    ///
+    /// Time: O(n)
+    pub fn reduce<F>(&self, mut f: F) -> Option<A>
+    where
+        A: Clone,
+        F: FnMut(A, &A) -> A,
+    {
+        self.fold(None, |acc, value| match acc {
+            None => Some(value.clone()),
+            Some(acc) => Some(f(acc, value)),
+        })
+    }
+
+    /// Test whether any value in a set satisfies a predicate.
+    ///
+    /// Time: O(n)
+    pub fn any<F>(&self, mut f: F) -> bool
+    where
+        F: FnMut(&A) -> bool,
+    {
+        self.find(|value| f(value)).is_some()
+    }
+
+    /// Test whether every value in a set satisfies a predicate.
+    ///
+    /// Time: O(n)
+    pub fn all<F>(&self, mut f: F) -> bool
+    where
+        F: FnMut(&A) -> bool,
+    {
+        !self.any(|value| !f(value))
+    }
+
+    /// Find a value in a set which satisfies a predicate.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashset::HashSet;
+    /// let set = hashset!{1, 2, 3};
+    /// assert_eq!(Some(&2), set.find(|value| value % 2 == 0));
+    /// assert_eq!(None, set.find(|value| *value > 3));
+    /// ```
+    #[must_use]
+    pub fn find<F>(&self, mut f: F) -> Option<&A>
+    where
+        F: FnMut(&A) -> bool,
+    {
+        match self.root.fold_while(None, &mut |_, value| {
+            if f(value) {
+                Err(Some(value))
+            } else {
+                Ok(None)
+            }
+        }) {
+            Ok(_) => None,
+            Err(found) => found.map(Deref::deref),
+        }
+    }
+
+    /// Pick a value from the set at random.
+    ///
+    /// `sample` is called with the size of the set and should return a
+    /// random index below it, which is how any random number generator
+    /// can be plugged in. The value at that position in the set's
+    /// iteration order is returned, so the values are as equally likely
+    /// to be picked as the indices `sample` returns.
+    ///
+    /// If the set is empty, or `sample` returns an index out of bounds,
+    /// returns `None`.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashset::HashSet;
+    /// use rand::{rngs::SmallRng, Rng, SeedableRng};
+    ///
+    /// let mut rng = SmallRng::seed_from_u64(1);
+    /// let set = hashset![1, 2, 3];
+    /// let value = set.choose(|len| rng.gen_range(0, len)).unwrap();
+    /// assert!(set.contains(value));
+    /// ```
+    #[must_use]
+    pub fn choose<F>(&self, mut sample: F) -> Option<&A>
+    where
+        F: FnMut(usize) -> usize,
+    {
+        if self.is_empty() {
+            return None;
+        }
+        self.iter().nth(sample(self.len()))
+    }
 }
 
 impl<A, S> HashSet<A, S>
@@ -371,6 +516,12 @@ where
         RS: Borrow<Self>,
     {
         let o = other.borrow();
+        if o.len() < self.len() {
+            return false;
+        }
+        if self.ptr_eq(o) {
+            return true;
+        }
         self.iter().all(|a| o.contains(&a))
     }
 
@@ -386,6 +537,74 @@ where
     {
         self.len() != other.borrow().len() && self.is_subset(other)
     }
+
+    /// Test whether a set is a superset of another set, meaning that
+    /// all values in the other set must also be in our set.
+    ///
+    /// Time: O(m log n) where m is the size of the other set
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashset::HashSet;
+    /// let set = hashset![1, 2, 3];
+    /// assert!(set.is_superset(hashset![1, 3]));
+    /// assert!(!set.is_superset(hashset![1, 4]));
+    /// ```
+    #[must_use]
+    pub fn is_superset<RS>(&self, other: RS) -> bool
+    where
+        RS: Borrow<Self>,
+    {
+        other.borrow().is_subset(self)
+    }
+
+    /// Test whether a set is a proper superset of another set, meaning
+    /// that all values in the other set must also be in our set. A
+    /// proper superset must also be larger than the other set.
+    ///
+    /// Time: O(m log n) where m is the size of the other set
+    #[must_use]
+    pub fn is_proper_superset<RS>(&self, other: RS) -> bool
+    where
+        RS: Borrow<Self>,
+    {
+        other.borrow().is_proper_subset(self)
+    }
+
+    /// Test whether two sets have no values in common.
+    ///
+    /// Time: O(m log n) where m is the size of the smaller set
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashset::HashSet;
+    /// let set = hashset![1, 2, 3];
+    /// assert!(set.is_disjoint(hashset![4, 5]));
+    /// assert!(!set.is_disjoint(hashset![3, 4]));
+    /// ```
+    #[must_use]
+    pub fn is_disjoint<RS>(&self, other: RS) -> bool
+    where
+        RS: Borrow<Self>,
+    {
+        let other = other.borrow();
+        let (small, large) = if self.len() <= other.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        if small.is_empty() {
+            return true;
+        }
+        if self.ptr_eq(other) {
+            return false;
+        }
+        small.iter().all(|value| !large.contains(value))
+    }
 }
 
 impl<A, S> HashSet<A, S>
@@ -449,6 +668,35 @@ where
         out
     }
 
+    /// Construct a new set from the current set with the given value
+    /// added, failing if the value is already in the set.
+    ///
+    /// Unlike [`update()`][update], this never replaces a value that's
+    /// already there. If the value is in the set, you get it back in an
+    /// [`OccupiedError`][OccupiedError].
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashset::{HashSet, OccupiedError};
+    /// let set = hashset![456];
+    /// assert_eq!(Ok(hashset![123, 456]), set.try_insert(123));
+    /// assert_eq!(Err(OccupiedError { value: 456 }), set.try_insert(456));
+    /// ```
+    ///
+    /// [update]: #method.update
+    /// [OccupiedError]: ./struct.OccupiedError.html
+    pub fn try_insert(&self, a: A) -> Result<Self, OccupiedError<A>> {
+        if self.contains(&a) {
+            Err(OccupiedError { value: a })
+        } else {
+            Ok(self.update(a))
+        }
+    }
+
     /// Construct a new set with the given value removed if it's in
     /// the set.
     ///
@@ -464,12 +712,69 @@ where
         out
     }
 
+    /// Remove a value from a set, if it's there, and return the
+    /// removed value as well as the updated set.
+    ///
+    /// The value is looked up and removed in a single walk down the
+    /// tree, and the value handed back is the one stored in the set,
+    /// not the one you searched for.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashset::HashSet;
+    /// let set = hashset!["foo".to_string(), "bar".to_string()];
+    /// assert_eq!(
+    ///   Some(("foo".to_string(), hashset!["bar".to_string()])),
+    ///   set.extract("foo")
+    /// );
+    /// assert_eq!(None, set.extract("baz"));
+    /// ```
+    #[must_use]
+    pub fn extract<BA>(&self, a: &BA) -> Option<(A, Self)>
+    where
+        BA: Hash + Eq + ?Sized,
+        A: Borrow<BA>,
+    {
+        let mut out = self.clone();
+        let result = out.remove(a);
+        result.map(|value| (value, out))
+    }
+
+    /// Remove an arbitrary value from a set, and return that value along
+    /// with the updated set, or `None` if the set is empty.
+    ///
+    /// The value removed is the first one the set iterates over.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashset::HashSet;
+    /// let mut work = hashset![1, 2, 3];
+    /// let mut total = 0;
+    /// while let Some((next, rest)) = work.pop() {
+    ///     total += next;
+    ///     work = rest;
+    /// }
+    /// assert_eq!(6, total);
+    /// ```
+    #[must_use]
+    pub fn pop(&self) -> Option<(A, Self)> {
+        self.iter().next().and_then(|value| self.extract(value))
+    }
+
     /// Filter out values from a set which don't satisfy a predicate.
     ///
-    /// This is slightly more efficient than filtering using an
-    /// iterator, in that it doesn't need to rehash the retained
-    /// values, but it still needs to reconstruct the entire tree
-    /// structure of the set.
+    /// This is more efficient than filtering using an iterator, in
+    /// that it doesn't need to rehash the retained values, and if the
+    /// set isn't shared with any other set, the removed values are
+    /// removed in place instead of reconstructing the tree.
     ///
     /// Time: O(n log n)
     ///
@@ -487,15 +792,115 @@ where
     where
         F: FnMut(&A) -> bool,
     {
-        let old_root = self.root.clone();
+        let removed: Vec<(A, HashBits)> = NodeIter::new(&self.root, self.size)
+            .filter(|(value, _)| !f(value))
+            .map(|(value, hash)| (value.0.clone(), hash))
+            .collect();
+        if removed.is_empty() {
+            return;
+        }
         let root = PoolRef::make_mut(&self.pool.0, &mut self.root);
-        for (value, hash) in NodeIter::new(&old_root, self.size) {
-            if !f(value) && root.remove(&self.pool.0, hash, 0, value).is_some() {
+        for (value, hash) in removed {
+            if root.remove(&self.pool.0, hash, 0, &value).is_some() {
                 self.size -= 1;
             }
         }
     }
 
+    /// Remove the values which satisfy a predicate from a set, and
+    /// return them in a new set.
+    ///
+    /// Like [`retain`][retain], this removes the values in place if the
+    /// set isn't shared with any other set.
+    ///
+    /// Time: O(n log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::HashSet;
+    /// let mut set = hashset![1, 2, 3];
+    /// let removed = set.drain_filter(|v| *v > 1);
+    /// assert_eq!(hashset![1], set);
+    /// assert_eq!(hashset![2, 3], removed);
+    /// ```
+    ///
+    /// [retain]: #method.retain
+    pub fn drain_filter<F>(&mut self, mut f: F) -> Self
+    where
+        F: FnMut(&A) -> bool,
+    {
+        let mut removed = self.new_from();
+        self.retain(|value| {
+            if f(value) {
+                removed.insert(value.clone());
+                false
+            } else {
+                true
+            }
+        });
+        removed
+    }
+
+    /// Construct a new set containing only the values of the current
+    /// set which satisfy a predicate.
+    ///
+    /// The new set shares the parts of its tree which no values were
+    /// removed from with the current set.
+    ///
+    /// Time: O(n + m log n) where m is the number of removed values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashset::HashSet;
+    /// let set = hashset![1, 2, 3, 4];
+    /// assert_eq!(hashset![2, 4], set.filter(|v| *v % 2 == 0));
+    /// assert_eq!(hashset![1, 2, 3, 4], set);
+    /// ```
+    #[must_use]
+    pub fn filter<F>(&self, f: F) -> Self
+    where
+        F: FnMut(&A) -> bool,
+    {
+        let mut out = self.clone();
+        out.retain(f);
+        out
+    }
+
+    /// Construct a new set by applying a function to every value of the
+    /// current set.
+    ///
+    /// If the function maps several values to the same value, the new
+    /// set keeps only one of them, so it can be smaller than the
+    /// current set. The new set uses the same hasher as the current
+    /// set.
+    ///
+    /// Time: O(n log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashset::HashSet;
+    /// let set = hashset![-2, 1, 2];
+    /// assert_eq!(hashset![1, 4], set.map(|v| v * v));
+    /// ```
+    #[must_use]
+    pub fn map<B, F>(&self, mut f: F) -> HashSet<B, S>
+    where
+        B: Hash + Eq + Clone,
+        F: FnMut(&A) -> B,
+    {
+        let mut out = self.new_from();
+        for value in self {
+            out.insert(f(value));
+        }
+        out
+    }
+
     /// Construct the union of two sets.
     ///
     /// Time: O(n log n)
@@ -530,6 +935,41 @@ where
         i.into_iter().fold(Self::default(), Self::union)
     }
 
+    /// Construct the intersection of multiple sets.
+    ///
+    /// The sets are intersected starting from the smallest, and it stops
+    /// as soon as the intersection is empty. Returns `None` if there are
+    /// no sets to intersect, since there's no set to start from.
+    ///
+    /// Time: O(n log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashset::HashSet;
+    /// let sets = vec![hashset!{1, 2, 3}, hashset!{2, 3, 4}, hashset!{3, 4, 5}];
+    /// assert_eq!(Some(hashset!{3}), HashSet::intersections(sets));
+    /// assert_eq!(None, HashSet::<i32>::intersections(vec![]));
+    /// ```
+    #[must_use]
+    pub fn intersections<I>(i: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        let mut sets: Vec<Self> = i.into_iter().collect();
+        sets.sort_by_key(Self::len);
+        let mut sets = sets.into_iter();
+        let mut out = sets.next()?;
+        for set in sets {
+            if out.is_empty() {
+                break;
+            }
+            out.retain(|value| set.contains(value));
+        }
+        Some(out)
+    }
+
     /// Construct the symmetric difference between two sets.
     ///
     /// This is an alias for the
@@ -556,6 +996,10 @@ where
 
     /// Construct the symmetric difference between two sets.
     ///
+    /// If both sets share a hasher, their tries are merged directly,
+    /// dropping any subtrees they share without merging them, though
+    /// their values still have to be counted.
+    ///
     /// Time: O(n log n)
     ///
     /// # Examples
@@ -570,6 +1014,27 @@ where
     /// ```
     #[must_use]
     pub fn symmetric_difference(mut self, other: Self) -> Self {
+        if Ref::ptr_eq(&self.hasher, &other.hasher) {
+            if PoolRef::ptr_eq(&self.root, &other.root) {
+                return self.new_from();
+            }
+            let mut removed = 0;
+            let root = Node::merge(
+                &self.pool.0,
+                &self.root,
+                &other.root,
+                0,
+                Identical::Drop,
+                &mut |_, _| None,
+                &mut removed,
+            );
+            return HashSet {
+                size: self.size + other.size - removed,
+                root: PoolRef::new(&self.pool.0, root),
+                pool: self.pool,
+                hasher: self.hasher,
+            };
+        }
         for value in other {
             if self.remove(&value).is_none() {
                 self.insert(value);
@@ -625,6 +1090,49 @@ where
         }
         out
     }
+
+    /// Construct the cartesian product of two sets, that is the set of
+    /// every pair `(a, b)` where `a` is in `self` and `b` is in
+    /// `other`.
+    ///
+    /// The product set uses the same hasher as `self`.
+    ///
+    /// Time: O(n m)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size of the product would overflow a `usize`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::hashset::HashSet;
+    /// let set1 = hashset!{1, 2};
+    /// let set2 = hashset!{"a", "b"};
+    /// let expected = hashset!{(1, "a"), (1, "b"), (2, "a"), (2, "b")};
+    /// assert_eq!(expected, set1.product(&set2));
+    /// ```
+    #[must_use]
+    pub fn product<B, S2>(&self, other: &HashSet<B, S2>) -> HashSet<(A, B), S>
+    where
+        B: Hash + Eq + Clone,
+    {
+        let size = self
+            .len()
+            .checked_mul(other.len())
+            .expect("HashSet::product: size of the product overflows usize");
+        let mut out = self.new_from();
+        if size == 0 {
+            return out;
+        }
+        for left in self {
+            for right in other.iter() {
+                out.insert((left.clone(), right.clone()));
+            }
+        }
+        out
+    }
 }
 
 // Core traits
@@ -664,6 +1172,27 @@ where
 {
 }
 
+impl<A, S, RS> PartialEq<collections::HashSet<A, RS>> for HashSet<A, S>
+where
+    A: Hash + Eq,
+    S: BuildHasher,
+    RS: BuildHasher,
+{
+    fn eq(&self, other: &collections::HashSet<A, RS>) -> bool {
+        self.len() == other.len() && self.iter().all(|value| other.contains(value))
+    }
+}
+
+impl<A, S> PartialEq<BTreeSet<A>> for HashSet<A, S>
+where
+    A: Hash + Eq + Ord,
+    S: BuildHasher,
+{
+    fn eq(&self, other: &BTreeSet<A>) -> bool {
+        self.len() == other.len() && self.iter().all(|value| other.contains(value))
+    }
+}
+
 impl<A, S> PartialOrd for HashSet<A, S>
 where
     A: Hash + Eq + Clone + PartialOrd,
@@ -769,6 +1298,102 @@ where
     }
 }
 
+impl<A, S> BitOr for HashSet<A, S>
+where
+    A: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    type Output = HashSet<A, S>;
+
+    fn bitor(self, other: Self) -> Self::Output {
+        self.union(other)
+    }
+}
+
+impl<A, S> BitOr for &HashSet<A, S>
+where
+    A: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    type Output = HashSet<A, S>;
+
+    fn bitor(self, other: Self) -> Self::Output {
+        self.clone().union(other.clone())
+    }
+}
+
+impl<A, S> BitAnd for HashSet<A, S>
+where
+    A: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    type Output = HashSet<A, S>;
+
+    fn bitand(self, other: Self) -> Self::Output {
+        self.intersection(other)
+    }
+}
+
+impl<A, S> BitAnd for &HashSet<A, S>
+where
+    A: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    type Output = HashSet<A, S>;
+
+    fn bitand(self, other: Self) -> Self::Output {
+        self.clone().intersection(other.clone())
+    }
+}
+
+impl<A, S> BitXor for HashSet<A, S>
+where
+    A: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    type Output = HashSet<A, S>;
+
+    fn bitxor(self, other: Self) -> Self::Output {
+        self.symmetric_difference(other)
+    }
+}
+
+impl<A, S> BitXor for &HashSet<A, S>
+where
+    A: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    type Output = HashSet<A, S>;
+
+    fn bitxor(self, other: Self) -> Self::Output {
+        self.clone().symmetric_difference(other.clone())
+    }
+}
+
+impl<A, S> Sub for HashSet<A, S>
+where
+    A: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    type Output = HashSet<A, S>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self.relative_complement(other)
+    }
+}
+
+impl<A, S> Sub for &HashSet<A, S>
+where
+    A: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    type Output = HashSet<A, S>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self.clone().relative_complement(other.clone())
+    }
+}
+
 impl<A, S> Sum for HashSet<A, S>
 where
     A: Hash + Eq + Clone,
@@ -1024,6 +1649,74 @@ where
     }
 }
 
+impl<A, S> From<HashSet<A, S>> for Vec<A>
+where
+    A: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    /// Create a [`std::vec::Vec`][vec] from a set, with the values in
+    /// the set's iteration order.
+    ///
+    /// Time: O(n)
+    ///
+    /// [vec]: https://doc.rust-lang.org/std/vec/struct.Vec.html
+    fn from(set: HashSet<A, S>) -> Self {
+        set.into_iter().collect()
+    }
+}
+
+impl<A, S> From<&HashSet<A, S>> for Vec<A>
+where
+    A: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    fn from(set: &HashSet<A, S>) -> Self {
+        set.iter().cloned().collect()
+    }
+}
+
+impl<A, S> From<HashSet<A, S>> for BTreeSet<A>
+where
+    A: Ord + Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    fn from(set: HashSet<A, S>) -> Self {
+        set.into_iter().collect()
+    }
+}
+
+impl<A, S> From<&HashSet<A, S>> for BTreeSet<A>
+where
+    A: Ord + Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    fn from(set: &HashSet<A, S>) -> Self {
+        set.iter().cloned().collect()
+    }
+}
+
+impl<A, S, SB> From<HashSet<A, S>> for collections::HashSet<A, SB>
+where
+    A: Hash + Eq + Clone,
+    S: BuildHasher,
+    SB: BuildHasher + Default,
+{
+    fn from(set: HashSet<A, S>) -> Self {
+        set.into_iter().collect()
+    }
+}
+
+impl<A, S, SB> From<&HashSet<A, S>> for collections::HashSet<A, SB>
+where
+    A: Hash + Eq + Clone,
+    S: BuildHasher,
+    SB: BuildHasher + Default,
+{
+    fn from(set: &HashSet<A, S>) -> Self {
+        set.iter().cloned().collect()
+    }
+}
+
 // Proptest
 #[cfg(any(test, feature = "proptest"))]
 #[doc(hidden)]
@@ -1040,6 +1733,7 @@ mod test {
     use super::proptest::*;
     use super::*;
     use crate::test::LolHasher;
+    use ::proptest::collection;
     use ::proptest::num::i16;
     use ::proptest::proptest;
     use std::hash::BuildHasherDefault;
@@ -1072,6 +1766,15 @@ mod test {
         assert_eq!(set1, set2);
     }
 
+    #[test]
+    fn equality_with_std() {
+        let set = hashset![1, 2, 3];
+        assert_eq!(set, collections::HashSet::<_>::from_iter(vec![3, 2, 1]));
+        assert_eq!(set, BTreeSet::from_iter(vec![1, 2, 3]));
+        assert_ne!(set, collections::HashSet::<_>::from_iter(vec![1, 2]));
+        assert_ne!(set, BTreeSet::from_iter(vec![1, 2, 4]));
+    }
+
     #[test]
     fn issue_60_drain_iterator_memory_corruption() {
         use crate::test::MetroHashBuilder;
@@ -1104,5 +1807,24 @@ mod test {
             assert!(s.len() < 100);
             assert!(s.len() >= 10);
         }
+
+        #[test]
+        fn symmetric_difference(ref left in collection::vec(i16::ANY, 0..1000), ref right in collection::vec(i16::ANY, 0..1000)) {
+            let set: HashSet<i16> = left.iter().cloned().collect();
+            // Derive the other set from a clone so the two share a hasher
+            // and some of their nodes.
+            let mut other = set.clone();
+            for value in left.iter().step_by(3) {
+                other.remove(value);
+            }
+            other.extend(right.iter().cloned());
+            let expected: BTreeSet<i16> = BTreeSet::from_iter(set.iter().cloned())
+                .symmetric_difference(&BTreeSet::from_iter(other.iter().cloned()))
+                .cloned()
+                .collect();
+            let result = set.symmetric_difference(other);
+            assert_eq!(expected.len(), result.len());
+            assert_eq!(expected, BTreeSet::from_iter(result.iter().cloned()));
+        }
     }
 }
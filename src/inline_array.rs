@@ -0,0 +1,42 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! A small array stored entirely inline, in the space of another type.
+//!
+//! [`InlineArray<A, T>`][InlineArray] is a `Vec`-like array which never
+//! allocates: it keeps as many values of type `A` as it can fit in the
+//! memory taken up by a value of type `T`, minus a `usize` for its
+//! length. `Vector` uses it to store small vectors in the space its tree
+//! would otherwise occupy, and only moves its contents onto the heap
+//! when it outgrows it.
+//!
+//! You can use it to build the same kind of hybrid structure, with an
+//! enum of an `InlineArray` sized after your full data structure and the
+//! data structure itself, which takes up no more memory than the data
+//! structure alone.
+//!
+//! Pushing onto a full array panics, so check
+//! [`is_full()`][is_full] first if you're not sure.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[macro_use] extern crate im;
+//! # use im::inline_array::InlineArray;
+//! # use im::vector::Vector;
+//! let mut array: InlineArray<u8, Vector<u8>> = InlineArray::new();
+//! assert!(InlineArray::<u8, Vector<u8>>::CAPACITY > 4);
+//! array.push(1);
+//! array.push(3);
+//! array.insert(1, 2);
+//! assert_eq!(&[1, 2, 3][..], &array[..]);
+//! assert_eq!(Some(3), array.pop());
+//! assert_eq!(Some(1), array.remove(0));
+//! assert_eq!(1, array.len());
+//! ```
+//!
+//! [InlineArray]: struct.InlineArray.html
+//! [is_full]: struct.InlineArray.html#method.is_full
+
+pub use sized_chunks::inline_array::{Drain, InlineArray, Iter};
@@ -0,0 +1,158 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Counters for the structural work done by the data structures.
+//!
+//! With the `instrument` feature flag enabled, every data structure in
+//! `im` keeps count of the node allocations, path copies, reference count
+//! clones and rebalances it performs, so you can see how much structural
+//! churn an operation causes instead of having to guess from timings.
+//!
+//! The counters are kept per thread, and only count work done on the
+//! current thread. Node allocations made through a `refpool` memory pool
+//! with the `pool` feature flag aren't counted.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[macro_use] extern crate im;
+//! # use im::instrument;
+//! # use im::vector::Vector;
+//! let vec: Vector<i32> = (0..1000).collect();
+//! let (_, stats) = instrument::measure(|| {
+//!     let mut copy = vec.clone();
+//!     copy.set(500, 0);
+//!     copy
+//! });
+//! assert!(stats.path_copies > 0);
+//! ```
+
+use std::cell::Cell;
+
+/// Counts of the structural work done by the data structures.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Stats {
+    /// The number of nodes allocated, including copies made by path
+    /// copying.
+    pub allocations: usize,
+    /// The number of nodes copied because they were shared with another
+    /// data structure when they needed to be mutated.
+    pub path_copies: usize,
+    /// The number of times a reference counted pointer to a node was
+    /// cloned.
+    pub ref_clones: usize,
+    /// The number of times nodes were split, merged or redistributed to
+    /// keep a tree balanced.
+    pub rebalances: usize,
+}
+
+impl Stats {
+    fn since(self, start: Stats) -> Stats {
+        Stats {
+            allocations: self.allocations.saturating_sub(start.allocations),
+            path_copies: self.path_copies.saturating_sub(start.path_copies),
+            ref_clones: self.ref_clones.saturating_sub(start.ref_clones),
+            rebalances: self.rebalances.saturating_sub(start.rebalances),
+        }
+    }
+}
+
+thread_local! {
+    static STATS: Cell<Stats> = Cell::new(Stats::default());
+}
+
+fn update<F>(f: F)
+where
+    F: FnOnce(&mut Stats),
+{
+    STATS.with(|cell| {
+        let mut stats = cell.get();
+        f(&mut stats);
+        cell.set(stats);
+    })
+}
+
+/// Get the counts accumulated on the current thread since it started, or
+/// since the last call to [`reset()`][reset].
+///
+/// [reset]: fn.reset.html
+#[must_use]
+pub fn stats() -> Stats {
+    STATS.with(Cell::get)
+}
+
+/// Reset the counts for the current thread to zero.
+pub fn reset() {
+    STATS.with(|cell| cell.set(Stats::default()))
+}
+
+/// Run a function and return its result along with the work counted on
+/// the current thread while it ran.
+pub fn measure<F, R>(f: F) -> (R, Stats)
+where
+    F: FnOnce() -> R,
+{
+    let start = stats();
+    let result = f();
+    (result, stats().since(start))
+}
+
+pub(crate) fn record_allocation() {
+    update(|stats| stats.allocations += 1)
+}
+
+pub(crate) fn record_path_copy() {
+    update(|stats| {
+        stats.allocations += 1;
+        stats.path_copies += 1;
+    })
+}
+
+pub(crate) fn record_ref_clone() {
+    update(|stats| stats.ref_clones += 1)
+}
+
+pub(crate) fn record_rebalance() {
+    update(|stats| stats.rebalances += 1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ordmap::OrdMap;
+
+    // Allocations made through `refpool` bypass the counters.
+    #[cfg(not(feature = "pool"))]
+    #[test]
+    fn counts_path_copies() {
+        let map: OrdMap<i32, i32> = (0..1000).map(|i| (i, i)).collect();
+        let (_, stats) = measure(|| {
+            let mut map = map.clone();
+            map.insert(500, 0);
+            let mut copy = map.clone();
+            copy.insert(500, 1);
+            // The path was copied when `copy` was first changed, so this
+            // shouldn't copy anything.
+            let (_, again) = measure(|| copy.insert(500, 2));
+            assert_eq!(0, again.path_copies);
+        });
+        assert!(stats.path_copies > 0);
+        assert!(stats.ref_clones > 0);
+    }
+
+    #[cfg(not(feature = "pool"))]
+    #[test]
+    fn counts_rebalances() {
+        let (_, stats) = measure(|| (0..1000).map(|i| (i, i)).collect::<OrdMap<i32, i32>>());
+        assert!(stats.allocations > 0);
+        assert!(stats.rebalances > 0);
+    }
+
+    #[test]
+    fn reset_while_measuring() {
+        update(|stats| stats.allocations += 10);
+        let (_, stats) = measure(reset);
+        assert_eq!(Stats::default(), stats);
+    }
+}
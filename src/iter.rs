@@ -10,7 +10,9 @@
 /// The function is called with the current state as its argument, and should
 /// return an [`Option`][std::option::Option] of a tuple of the next value to
 /// yield from the iterator and the updated state. If the function returns
-/// [`None`][std::option::Option::None], the iterator ends.
+/// [`None`][std::option::Option::None], the iterator ends. Values are yielded
+/// in the order they're produced, so collecting them into a
+/// [`Vector`][Vector] puts the first value at the front.
 ///
 /// # Examples
 /// ```
@@ -26,6 +28,7 @@
 /// assert_eq!(numbers, vector![0, 1, 2, 3, 4]);
 /// ```
 ///
+/// [Vector]: ../vector/struct.Vector.html
 /// [std::option::Option]: https://doc.rust-lang.org/std/option/enum.Option.html
 /// [std::option::Option::None]: https://doc.rust-lang.org/std/option/enum.Option.html#variant.None
 pub fn unfold<F, S, A>(value: S, f: F) -> impl Iterator<Item = A>
@@ -40,3 +43,54 @@ where
         })
     })
 }
+
+/// Create an iterator of values where each value is computed from the one
+/// before it.
+///
+/// The iterator starts with `first`, if it's present, and then calls the
+/// function with a reference to the previous value to produce the next one,
+/// until the function returns [`None`][std::option::Option::None]. Values are
+/// yielded in the order they're produced, so collecting them into a
+/// [`Vector`][Vector] puts `first` at the front.
+///
+/// # Examples
+/// ```
+/// # #[macro_use] extern crate im;
+/// # use im::iter::successors;
+/// # use im::vector::Vector;
+/// // The powers of ten that fit in a u16.
+/// let powers: Vector<u16> = successors(Some(1u16), |n| n.checked_mul(10)).collect();
+/// assert_eq!(powers, vector![1, 10, 100, 1000, 10000]);
+/// ```
+///
+/// [Vector]: ../vector/struct.Vector.html
+/// [std::option::Option::None]: https://doc.rust-lang.org/std/option/enum.Option.html#variant.None
+pub fn successors<F, A>(first: Option<A>, mut f: F) -> impl Iterator<Item = A>
+where
+    F: FnMut(&A) -> Option<A>,
+{
+    let mut next = first;
+    std::iter::from_fn(move || {
+        let value = next.take()?;
+        next = f(&value);
+        Some(value)
+    })
+}
+
+/// Create an iterator which yields a clone of a value a given number of
+/// times.
+///
+/// # Examples
+/// ```
+/// # #[macro_use] extern crate im;
+/// # use im::iter::repeat_n;
+/// # use im::vector::Vector;
+/// let zeroes: Vector<i32> = repeat_n(0, 3).collect();
+/// assert_eq!(zeroes, vector![0, 0, 0]);
+/// ```
+pub fn repeat_n<A>(value: A, count: usize) -> impl Iterator<Item = A>
+where
+    A: Clone,
+{
+    (0..count).map(move |_| value.clone())
+}
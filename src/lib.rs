@@ -291,6 +291,27 @@
 //! [`Sync`][std::marker::Sync]. This yields approximately a 20-25% increase in
 //! general performance.
 //!
+//! ## Memory Allocation
+//!
+//! Every node in every data structure is allocated through the same
+//! reference counted pointer type. There's no allocator type parameter on
+//! the data structures: Rust's allocator API is still unstable, and
+//! threading an allocator through every node would add a type parameter to
+//! every data structure and every function which takes one.
+//!
+//! There are two ways to control where the memory comes from instead:
+//!
+//! * With `im-rc` and the `pool` feature flag, each data structure can be
+//!   constructed with a [`refpool`](https://crates.io/crates/refpool)
+//!   memory pool, eg. using `Vector::with_pool()`, and its nodes will be
+//!   allocated from and returned to that pool. A pool can be filled ahead
+//!   of time, and dropping it releases all the memory it's holding on to.
+//! * A [global allocator][std::alloc::GlobalAlloc] sees every node
+//!   allocation, so if you need to attribute allocations to requests in a
+//!   long running server, a tracking global allocator which records the
+//!   current request in a thread local will capture the memory used by
+//!   `im` along with everything else.
+//!
 //! ## Feature Flags
 //!
 //! `im` comes with optional support for the following crates through Cargo
@@ -306,9 +327,12 @@
 //! | [`pool`](https://crates.io/crates/refpool) | Constructors and pool types for [`refpool`](https://crates.io/crates/refpool) memory pools (only available in `im-rc`) |
 //! | [`proptest`](https://crates.io/crates/proptest) | Strategies for all `im` datatypes under a `proptest` namespace, eg. `im::vector::proptest::vector()` |
 //! | [`quickcheck`](https://crates.io/crates/quickcheck) | [`quickcheck::Arbitrary`](https://docs.rs/quickcheck/latest/quickcheck/trait.Arbitrary.html) implementations for all `im` datatypes (not available in `im-rc`) |
-//! | [`rayon`](https://crates.io/crates/rayon) | parallel iterator implementations for [`Vector`][vector::Vector] (not available in `im-rc`) |
+//! | [`rayon`](https://crates.io/crates/rayon) | parallel iterator implementations and parallel sorting for [`Vector`][vector::Vector] (not available in `im-rc`) |
 //! | [`serde`](https://crates.io/crates/serde) | [`Serialize`](https://docs.rs/serde/latest/serde/trait.Serialize.html) and [`Deserialize`](https://docs.rs/serde/latest/serde/trait.Deserialize.html) implementations for all `im` datatypes |
 //! | [`arbitrary`](https://crates.io/crates/arbitrary/) | [`arbitrary::Arbitrary`](https://docs.rs/arbitrary/latest/arbitrary/trait.Arbitrary.html) implementations for all `im` datatypes |
+//! | `delta` | A compact encoding of [`OrdSet<u64>`][ordset::OrdSet] as varint deltas under a `delta` namespace, eg. `im::delta::encode()` |
+//! | `instrument` | Per thread counters for node allocations, path copies and rebalances under an `instrument` namespace, eg. `im::instrument::measure()` |
+//! | `light-arc` | Use a reference counted pointer without weak reference support for the nodes of all `im` datatypes, saving a word per node (not available in `im-rc`) |
 //!
 //! [std::collections]: https://doc.rust-lang.org/std/collections/index.html
 //! [std::collections::VecDeque]: https://doc.rust-lang.org/std/collections/struct.VecDeque.html
@@ -316,6 +340,7 @@
 //! [std::string::String]: https://doc.rust-lang.org/std/string/struct.String.html
 //! [std::rc::Rc]: https://doc.rust-lang.org/std/rc/struct.Rc.html
 //! [std::sync::Arc]: https://doc.rust-lang.org/std/sync/struct.Arc.html
+//! [std::alloc::GlobalAlloc]: https://doc.rust-lang.org/std/alloc/trait.GlobalAlloc.html
 //! [std::cmp::Eq]: https://doc.rust-lang.org/std/cmp/trait.Eq.html
 //! [std::cmp::Ord]: https://doc.rust-lang.org/std/cmp/trait.Ord.html
 //! [std::clone::Clone]: https://doc.rust-lang.org/std/clone/trait.Clone.html
@@ -329,7 +354,7 @@
 //! [ordmap::OrdMap]: ./struct.OrdMap.html
 //! [ordset::OrdSet]: ./struct.OrdSet.html
 //! [vector::Vector]: ./struct.Vector.html
-//! [vector::Vector::push_back]: ./vector/enum.Vector.html#method.push_back
+//! [vector::Vector::push_back]: ./vector/struct.Vector.html#method.push_back
 //! [rrb-tree]: https://infoscience.epfl.ch/record/213452/files/rrbvector.pdf
 //! [hamt]: https://en.wikipedia.org/wiki/Hash_array_mapped_trie
 //! [b-tree]: https://en.wikipedia.org/wiki/B-tree
@@ -345,6 +370,7 @@
 extern crate pretty_assertions;
 
 mod config;
+mod error;
 mod nodes;
 mod sort;
 mod sync;
@@ -367,6 +393,22 @@ pub mod vector;
 
 pub mod iter;
 
+pub mod chunk;
+
+pub mod inline_array;
+
+pub mod merge;
+
+pub mod bimap;
+
+pub mod frequencymap;
+
+pub mod slotmap;
+
+pub mod psqueue;
+
+pub mod windowdeque;
+
 #[cfg(any(test, feature = "proptest"))]
 pub mod proptest;
 
@@ -385,6 +427,15 @@ pub mod quickcheck;
 #[cfg(any(threadsafe, not(feature = "pool")))]
 mod fakepool;
 
+#[cfg(feature = "light-arc")]
+mod arc;
+
+#[cfg(feature = "instrument")]
+pub mod instrument;
+
+#[cfg(any(test, feature = "delta"))]
+pub mod delta;
+
 #[cfg(all(threadsafe, feature = "pool"))]
 compile_error!(
     "The `pool` feature is not threadsafe but you've enabled it on a threadsafe version of `im`."
@@ -425,7 +476,7 @@ mod tests;
 /// # }
 /// ```
 ///
-/// [Vector]: ../vector/enum.Vector.html
+/// [Vector]: ../vector/struct.Vector.html
 /// [HashMap]: ../hashmap/struct.HashMap.html
 /// [OrdMap]: ../ordmap/struct.OrdMap.html
 #[macro_export]
@@ -459,7 +510,7 @@ macro_rules! update_in {
 /// # }
 /// ```
 ///
-/// [Vector]: ../vector/enum.Vector.html
+/// [Vector]: ../vector/struct.Vector.html
 /// [HashMap]: ../hashmap/struct.HashMap.html
 /// [OrdMap]: ../ordmap/struct.OrdMap.html
 #[macro_export]
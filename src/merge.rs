@@ -0,0 +1,112 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Deep merging of nested data structures.
+//!
+//! The [`Merge`][Merge] trait describes values which know how to combine
+//! themselves with another value of the same type. Maps implement it by
+//! merging their values key by key, recursing into values present in both
+//! maps, so that merging nested maps only replaces the leaves they disagree
+//! on. Leaf values, like numbers and strings, pick one side according to a
+//! [`MergeStrategy`][MergeStrategy].
+//!
+//! # Examples
+//!
+//! ```
+//! # #[macro_use] extern crate im;
+//! # use im::ordmap::OrdMap;
+//! # use im::merge::MergeStrategy;
+//! let defaults = ordmap!{
+//!     "server" => ordmap!{"host" => "localhost", "port" => "80"},
+//!     "log" => ordmap!{"level" => "info"}
+//! };
+//! let overrides = ordmap!{
+//!     "server" => ordmap!{"port" => "8080"}
+//! };
+//! let config = defaults.merge_deep(&overrides, MergeStrategy::PreferRight);
+//! assert_eq!(
+//!     ordmap!{
+//!         "server" => ordmap!{"host" => "localhost", "port" => "8080"},
+//!         "log" => ordmap!{"level" => "info"}
+//!     },
+//!     config
+//! );
+//! ```
+//!
+//! [Merge]: trait.Merge.html
+//! [MergeStrategy]: enum.MergeStrategy.html
+
+/// How to resolve a conflict between two leaf values during a merge.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum MergeStrategy {
+    /// Keep the value from the left hand side, ie. the value being
+    /// merged into.
+    PreferLeft,
+    /// Keep the value from the right hand side, ie. the value being
+    /// merged in.
+    PreferRight,
+}
+
+impl MergeStrategy {
+    /// Pick one of two values according to the strategy.
+    #[inline]
+    pub fn pick<A>(self, left: A, right: A) -> A {
+        match self {
+            MergeStrategy::PreferLeft => left,
+            MergeStrategy::PreferRight => right,
+        }
+    }
+}
+
+/// A value which can be merged with another value of the same type.
+///
+/// Collections implement this by merging their contents, recursing into
+/// values which occur on both sides. Values with no internal structure
+/// resolve the conflict using the [`MergeStrategy`][MergeStrategy] they're
+/// given.
+///
+/// [MergeStrategy]: enum.MergeStrategy.html
+pub trait Merge {
+    /// Merge `other` into `self`, using `strategy` to decide between
+    /// conflicting leaf values.
+    #[must_use]
+    fn merge(self, other: Self, strategy: MergeStrategy) -> Self;
+}
+
+macro_rules! impl_leaf_merge {
+    ($($t:ty),*) => {
+        $(
+            impl Merge for $t {
+                #[inline]
+                fn merge(self, other: Self, strategy: MergeStrategy) -> Self {
+                    strategy.pick(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_leaf_merge!((), bool, char, String);
+impl_leaf_merge!(u8, u16, u32, u64, u128, usize);
+impl_leaf_merge!(i8, i16, i32, i64, i128, isize);
+impl_leaf_merge!(f32, f64);
+
+impl Merge for &str {
+    #[inline]
+    fn merge(self, other: Self, strategy: MergeStrategy) -> Self {
+        strategy.pick(self, other)
+    }
+}
+
+impl<A: Merge> Merge for Option<A> {
+    /// Merge two options, keeping whichever side is present if only
+    /// one of them is, and merging their contents if both are.
+    fn merge(self, other: Self, strategy: MergeStrategy) -> Self {
+        match (self, other) {
+            (Some(left), Some(right)) => Some(left.merge(right, strategy)),
+            (left, None) => left,
+            (None, right) => right,
+        }
+    }
+}
@@ -40,6 +40,8 @@ pub trait BTreeValue {
 pub(crate) struct Node<A> {
     keys: Chunk<A, NodeSize>,
     children: Chunk<Option<PoolRef<Node<A>>>, Add1<NodeSize>>,
+    // The number of values in this node and all the nodes below it.
+    size: usize,
 }
 
 #[cfg(feature = "pool")]
@@ -56,6 +58,7 @@ impl<A> PoolDefault for Node<A> {
         Chunk::default_uninit(cast_uninit(&mut (*ptr).keys));
         Chunk::default_uninit(cast_uninit(&mut (*ptr).children));
         (*ptr).children.push_back(None);
+        (*ptr).size = 0;
     }
 }
 
@@ -70,6 +73,7 @@ where
             .clone_uninit(cast_uninit(&mut (*target.as_mut_ptr()).keys));
         self.children
             .clone_uninit(cast_uninit(&mut (*target.as_mut_ptr()).children));
+        (*target.as_mut_ptr()).size = self.size;
     }
 }
 
@@ -110,6 +114,7 @@ where
         Node {
             keys: self.keys.clone(),
             children: self.children.clone(),
+            size: self.size,
         }
     }
 }
@@ -119,6 +124,7 @@ impl<A> Default for Node<A> {
         Node {
             keys: Chunk::new(),
             children: Chunk::unit(None),
+            size: 0,
         }
     }
 }
@@ -144,6 +150,7 @@ impl<A> Node<A> {
         Node {
             keys: Chunk::unit(value),
             children: Chunk::pair(None, None),
+            size: 1,
         }
     }
 
@@ -155,6 +162,7 @@ impl<A> Node<A> {
         right: Node<A>,
     ) -> Self {
         Node {
+            size: left.size + right.size + 1,
             keys: Chunk::unit(median),
             children: Chunk::pair(
                 Some(PoolRef::new(pool, left)),
@@ -163,6 +171,136 @@ impl<A> Node<A> {
         }
     }
 
+    fn from_parts(
+        keys: Chunk<A, NodeSize>,
+        children: Chunk<Option<PoolRef<Node<A>>>, Add1<NodeSize>>,
+    ) -> Self {
+        let mut node = Node {
+            keys,
+            children,
+            size: 0,
+        };
+        node.recount();
+        node
+    }
+
+    // Update the size of this node from its keys and children, after
+    // moving them around.
+    fn recount(&mut self) {
+        self.size = self.keys.len()
+            + self
+                .children
+                .iter()
+                .flatten()
+                .map(|child| child.size)
+                .sum::<usize>();
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.size
+    }
+
+    // Check that every node's size matches the values under it.
+    #[cfg(test)]
+    pub(crate) fn check_sizes(&self) -> usize {
+        let size = self.keys.len()
+            + self
+                .children
+                .iter()
+                .flatten()
+                .map(|child| child.check_sizes())
+                .sum::<usize>();
+        assert_eq!(size, self.size);
+        size
+    }
+
+    /// Build a tree out of `count` values which are already in order and
+    /// contain no duplicates, without any comparisons or rebalancing.
+    ///
+    /// Every node but the root comes out at least half full, and none of
+    /// them come out full.
+    pub(crate) fn from_sorted<I>(pool: &Pool<Node<A>>, values: &mut I, count: usize) -> Self
+    where
+        I: Iterator<Item = A>,
+    {
+        let mut height = 0;
+        while Node::<A>::capacity(height) < count {
+            height += 1;
+        }
+        Node::build_sorted(pool, values, count, height)
+    }
+
+    /// Build a tree with the same shape as this one, applying a function
+    /// to each value in order. The function must preserve the order of
+    /// the values, or the new tree won't be searchable.
+    pub(crate) fn map_ordered<B, F>(&self, pool: &Pool<Node<B>>, f: &mut F) -> Node<B>
+    where
+        F: FnMut(&A) -> B,
+    {
+        let mut keys = Chunk::new();
+        let mut children = Chunk::new();
+        for (index, child) in self.children.iter().enumerate() {
+            children.push_back(
+                child
+                    .as_ref()
+                    .map(|child| PoolRef::new(pool, child.map_ordered(pool, f))),
+            );
+            if let Some(key) = self.keys.get(index) {
+                keys.push_back(f(key));
+            }
+        }
+        Node {
+            keys,
+            children,
+            size: self.size,
+        }
+    }
+
+    // The most values a tree of the given height is built with. Every
+    // node is left with room for one more value, because removing a value
+    // can move a value from one child into its sibling to rebalance them.
+    fn capacity(height: usize) -> usize {
+        (0..=height).fold(1usize, |cap, _| cap.saturating_mul(NODE_SIZE)) - 1
+    }
+
+    fn build_sorted<I>(pool: &Pool<Node<A>>, values: &mut I, count: usize, height: usize) -> Self
+    where
+        I: Iterator<Item = A>,
+    {
+        let mut node = Node {
+            keys: Chunk::new(),
+            children: Chunk::new(),
+            size: count,
+        };
+        if height == 0 {
+            node.children.push_back(None);
+            for value in values.take(count) {
+                node.keys.push_back(value);
+                node.children.push_back(None);
+            }
+            return node;
+        }
+        // Use as few children as the values will fit in, and spread the
+        // values evenly between them.
+        let child_capacity = Node::<A>::capacity(height - 1);
+        let children = count / (child_capacity + 1) + 1;
+        let per_child = (count + 1 - children) / children;
+        let extra = (count + 1 - children) % children;
+        for index in 0..children {
+            let size = if index < extra {
+                per_child + 1
+            } else {
+                per_child
+            };
+            let child = Node::build_sorted(pool, values, size, height - 1);
+            node.children.push_back(Some(PoolRef::new(pool, child)));
+            if index + 1 < children {
+                node.keys.push_back(values.next().unwrap());
+            }
+        }
+        node
+    }
+
     pub(crate) fn min(&self) -> Option<&A> {
         match self.children.first().unwrap() {
             None => self.keys.first(),
@@ -176,6 +314,25 @@ impl<A> Node<A> {
             Some(ref child) => child.max(),
         }
     }
+
+    /// Fold over the values in the tree in order, stopping as soon as
+    /// `f` returns `Err`.
+    pub(crate) fn fold_while<'a, B, F>(&'a self, init: B, f: &mut F) -> Result<B, B>
+    where
+        F: FnMut(B, &'a A) -> Result<B, B>,
+    {
+        let mut acc = init;
+        for (index, value) in self.keys.iter().enumerate() {
+            if let Some(ref child) = self.children[index] {
+                acc = child.fold_while(acc, f)?;
+            }
+            acc = f(acc, value)?;
+        }
+        match self.children.last().unwrap() {
+            None => Ok(acc),
+            Some(ref child) => child.fold_while(acc, f),
+        }
+    }
 }
 
 impl<A: BTreeValue> Node<A> {
@@ -211,6 +368,49 @@ impl<A: BTreeValue> Node<A> {
         }
     }
 
+    /// Find the value at a given position in the tree's ordering.
+    pub(crate) fn lookup_index(&self, mut index: usize) -> Option<&A> {
+        for (position, child) in self.children.iter().enumerate() {
+            if let Some(ref child) = child {
+                if index < child.size {
+                    return child.lookup_index(index);
+                }
+                index -= child.size;
+            }
+            if index == 0 {
+                return self.keys.get(position);
+            }
+            index -= 1;
+        }
+        None
+    }
+
+    /// Find the position of a key in the tree's ordering.
+    pub(crate) fn index_of<BK>(&self, key: &BK) -> Option<usize>
+    where
+        BK: Ord + ?Sized,
+        A::Key: Borrow<BK>,
+    {
+        if self.keys.is_empty() {
+            return None;
+        }
+        let (index, found) = match A::search_key(&self.keys, key) {
+            Ok(index) => (index, true),
+            Err(index) => (index, false),
+        };
+        let before = index
+            + self.children[..index]
+                .iter()
+                .map(Node::child_size)
+                .sum::<usize>();
+        if found {
+            Some(before + Node::child_size(&self.children[index]))
+        } else {
+            let child = self.children[index].as_ref()?;
+            child.index_of(key).map(|position| before + position)
+        }
+    }
+
     pub(crate) fn lookup_mut<BK>(&mut self, pool: &Pool<Node<A>>, key: &BK) -> Option<&mut A>
     where
         A: Clone,
@@ -235,7 +435,25 @@ impl<A: BTreeValue> Node<A> {
         }
     }
 
-    pub(crate) fn lookup_prev<'a, BK>(&'a self, key: &BK) -> Option<&A>
+    pub(crate) fn lookup_prev<BK>(&self, key: &BK) -> Option<&A>
+    where
+        BK: Ord + ?Sized,
+        A::Key: Borrow<BK>,
+    {
+        self.lookup_below(key, true)
+    }
+
+    pub(crate) fn lookup_next<BK>(&self, key: &BK) -> Option<&A>
+    where
+        BK: Ord + ?Sized,
+        A::Key: Borrow<BK>,
+    {
+        self.lookup_above(key, true)
+    }
+
+    /// Find the largest value less than `key`, or equal to it if
+    /// `inclusive` is set.
+    pub(crate) fn lookup_below<BK>(&self, key: &BK, inclusive: bool) -> Option<&A>
     where
         BK: Ord + ?Sized,
         A::Key: Borrow<BK>,
@@ -244,19 +462,21 @@ impl<A: BTreeValue> Node<A> {
             return None;
         }
         match A::search_key(&self.keys, key) {
-            Ok(index) => Some(&self.keys[index]),
-            Err(index) => match self.children[index] {
-                None if index == 0 => None,
-                None => match self.keys.get(index - 1) {
-                    Some(_) => Some(&self.keys[index - 1]),
-                    None => None,
-                },
-                Some(ref node) => node.lookup_prev(key),
+            Ok(index) if inclusive => Some(&self.keys[index]),
+            Ok(index) => match self.children[index] {
+                Some(ref node) => node.max(),
+                None => index.checked_sub(1).map(|index| &self.keys[index]),
             },
+            Err(index) => self.children[index]
+                .as_ref()
+                .and_then(|node| node.lookup_below(key, inclusive))
+                .or_else(|| index.checked_sub(1).map(|index| &self.keys[index])),
         }
     }
 
-    pub(crate) fn lookup_next<'a, BK>(&'a self, key: &BK) -> Option<&A>
+    /// Find the smallest value greater than `key`, or equal to it if
+    /// `inclusive` is set.
+    pub(crate) fn lookup_above<BK>(&self, key: &BK, inclusive: bool) -> Option<&A>
     where
         BK: Ord + ?Sized,
         A::Key: Borrow<BK>,
@@ -265,14 +485,15 @@ impl<A: BTreeValue> Node<A> {
             return None;
         }
         match A::search_key(&self.keys, key) {
-            Ok(index) => Some(&self.keys[index]),
-            Err(index) => match self.children[index] {
-                None => match self.keys.get(index) {
-                    Some(_) => Some(&self.keys[index]),
-                    None => None,
-                },
-                Some(ref node) => node.lookup_next(key),
+            Ok(index) if inclusive => Some(&self.keys[index]),
+            Ok(index) => match self.children[index + 1] {
+                Some(ref node) => node.min(),
+                None => self.keys.get(index + 1),
             },
+            Err(index) => self.children[index]
+                .as_ref()
+                .and_then(|node| node.lookup_above(key, inclusive))
+                .or_else(|| self.keys.get(index)),
         }
     }
 
@@ -400,7 +621,16 @@ impl<A: BTreeValue> Node<A> {
                         path.push((self, index));
                         path
                     }
-                    None => Vec::new(),
+                    // The next key is in an ancestor, if anywhere.
+                    None => {
+                        while let Some((node, index)) = path.pop() {
+                            if index < node.keys.len() {
+                                path.push((node, index));
+                                return path;
+                            }
+                        }
+                        path
+                    }
                 },
                 Some(ref node) => {
                     path.push((self, index));
@@ -429,14 +659,20 @@ impl<A: BTreeValue> Node<A> {
                 path
             }
             Err(index) => match self.children[index] {
-                None if index == 0 => Vec::new(),
-                None => match self.keys.get(index - 1) {
-                    Some(_) => {
-                        path.push((self, index));
-                        path
+                // The previous key is in an ancestor, if anywhere.
+                None if index == 0 => {
+                    while let Some((node, index)) = path.pop() {
+                        if index > 0 {
+                            path.push((node, index - 1));
+                            return path;
+                        }
                     }
-                    None => Vec::new(),
-                },
+                    path
+                }
+                None => {
+                    path.push((self, index - 1));
+                    path
+                }
                 Some(ref node) => {
                     path.push((self, index));
                     node.path_prev(key, path)
@@ -452,6 +688,8 @@ impl<A: BTreeValue> Node<A> {
         ins_left: Option<Node<A>>,
         ins_right: Option<Node<A>>,
     ) -> Insert<A> {
+        #[cfg(feature = "instrument")]
+        crate::instrument::record_rebalance();
         let left_child = ins_left.map(|node| PoolRef::new(pool, node));
         let right_child = ins_right.map(|node| PoolRef::new(pool, node));
         let index = A::search_value(&self.keys, &value).unwrap_err();
@@ -512,45 +750,54 @@ impl<A: BTreeValue> Node<A> {
         debug_assert!(right_children.len() == MEDIAN + 1);
 
         Split(
-            Node {
-                keys: left_keys,
-                children: left_children,
-            },
+            Node::from_parts(left_keys, left_children),
             median,
-            Node {
-                keys: right_keys,
-                children: right_children,
-            },
+            Node::from_parts(right_keys, right_children),
         )
     }
 
     fn merge(middle: A, left: Node<A>, mut right: Node<A>) -> Node<A> {
+        #[cfg(feature = "instrument")]
+        crate::instrument::record_rebalance();
+        let size = left.size + right.size + 1;
         let mut keys = left.keys;
         keys.push_back(middle);
         keys.append(&mut right.keys);
         let mut children = left.children;
         children.append(&mut right.children);
-        Node { keys, children }
+        Node {
+            keys,
+            children,
+            size,
+        }
+    }
+
+    fn child_size(child: &Option<PoolRef<Node<A>>>) -> usize {
+        child.as_ref().map_or(0, |child| child.size)
     }
 
     fn pop_min(&mut self) -> (A, Option<PoolRef<Node<A>>>) {
         let value = self.keys.pop_front();
         let child = self.children.pop_front();
+        self.size -= Node::child_size(&child) + 1;
         (value, child)
     }
 
     fn pop_max(&mut self) -> (A, Option<PoolRef<Node<A>>>) {
         let value = self.keys.pop_back();
         let child = self.children.pop_back();
+        self.size -= Node::child_size(&child) + 1;
         (value, child)
     }
 
     fn push_min(&mut self, child: Option<PoolRef<Node<A>>>, value: A) {
+        self.size += Node::child_size(&child) + 1;
         self.keys.push_front(value);
         self.children.push_front(child);
     }
 
     fn push_max(&mut self, child: Option<PoolRef<Node<A>>>, value: A) {
+        self.size += Node::child_size(&child) + 1;
         self.keys.push_back(value);
         self.children.push_back(child);
     }
@@ -562,6 +809,7 @@ impl<A: BTreeValue> Node<A> {
         if self.keys.is_empty() {
             self.keys.push_back(value);
             self.children.push_back(None);
+            self.size += 1;
             return Insert::Added;
         }
         let (median, left, right) = match A::search_value(&self.keys, &value) {
@@ -588,12 +836,14 @@ impl<A: BTreeValue> Node<A> {
                 match action {
                     ReplacedAction(value) => return Insert::Replaced(value),
                     AddedAction => {
+                        self.size += 1;
                         return Insert::Added;
                     }
                     InsertAt => {
                         if has_room {
                             self.keys.insert(index, value);
                             self.children.insert(index + 1, None);
+                            self.size += 1;
                             return Insert::Added;
                         } else {
                             (value, None, None)
@@ -605,6 +855,7 @@ impl<A: BTreeValue> Node<A> {
                             self.keys.insert(index, median);
                             self.children
                                 .insert(index + 1, Some(PoolRef::new(pool, right)));
+                            self.size += 1;
                             return Insert::Added;
                         } else {
                             (median, Some(left), Some(right))
@@ -632,6 +883,26 @@ impl<A: BTreeValue> Node<A> {
         index: Result<usize, usize>,
         key: &BK,
     ) -> Remove<A>
+    where
+        A: Clone,
+        BK: Ord + ?Sized,
+        A::Key: Borrow<BK>,
+    {
+        // Whatever happens below, removing a value leaves this node with
+        // one value less under it, unless it's been replaced entirely.
+        let result = self.remove_value(pool, index, key);
+        if let Remove::Removed(_) = result {
+            self.size -= 1;
+        }
+        result
+    }
+
+    fn remove_value<BK>(
+        &mut self,
+        pool: &Pool<Node<A>>,
+        index: Result<usize, usize>,
+        key: &BK,
+    ) -> Remove<A>
     where
         A: Clone,
         BK: Ord + ?Sized,
@@ -903,6 +1174,513 @@ impl<A: BTreeValue> Node<A> {
             }
         }
     }
+
+    // Range removal
+    //
+    // Removing a range splits the tree in two at each end of the range
+    // and joins the outer pieces back together, which only touches the
+    // nodes along the two paths to the ends of the range. The functions
+    // below work on whole trees, given as a root node and its height
+    // (0 for a leaf). The trees they produce only guarantee that every
+    // node but the root has at least one key, rather than the usual half
+    // full nodes, but that's all the other operations rely on.
+
+    /// Remove every value inside `range` from a tree, and return the new
+    /// root.
+    pub(crate) fn remove_range<R, BK>(self, pool: &Pool<Node<A>>, range: &R) -> Node<A>
+    where
+        A: Clone,
+        R: RangeBounds<BK>,
+        BK: Ord + ?Sized,
+        A::Key: Borrow<BK>,
+    {
+        let height = self.height();
+        let ((left, left_height), (rest, rest_height)) =
+            self.split_where(pool, height, &mut |value: &A| match range.start_bound() {
+                Bound::Included(start) => value.cmp_keys(start) == Ordering::Less,
+                Bound::Excluded(start) => value.cmp_keys(start) != Ordering::Greater,
+                Bound::Unbounded => false,
+            });
+        let (_, (right, right_height)) =
+            rest.split_where(
+                pool,
+                rest_height,
+                &mut |value: &A| match range.end_bound() {
+                    Bound::Included(end) => value.cmp_keys(end) != Ordering::Greater,
+                    Bound::Excluded(end) => value.cmp_keys(end) == Ordering::Less,
+                    Bound::Unbounded => true,
+                },
+            );
+        Node::concat(pool, left, left_height, right, right_height).0
+    }
+
+    fn height(&self) -> usize {
+        match self.children[0] {
+            None => 0,
+            Some(ref child) => child.height() + 1,
+        }
+    }
+
+    /// Split a tree in two, with the values for which `goes_left` returns
+    /// true in the left tree. `goes_left` must return true for a prefix
+    /// of the values, and false for the rest.
+    fn split_where<F>(
+        mut self,
+        pool: &Pool<Node<A>>,
+        height: usize,
+        goes_left: &mut F,
+    ) -> ((Node<A>, usize), (Node<A>, usize))
+    where
+        A: Clone,
+        F: FnMut(&A) -> bool,
+    {
+        let index = self
+            .keys
+            .iter()
+            .take_while(|value| goes_left(value))
+            .count();
+        if height == 0 {
+            let right =
+                Node::from_parts(self.keys.split_off(index), self.children.split_off(index));
+            self.children.push_back(None);
+            self.recount();
+            return ((self, 0), (right, 0));
+        }
+        let mut right_keys = self.keys.split_off(index);
+        let mut right_children = self.children.split_off(index + 1);
+        let child = Node::take_child(self.children.pop_back());
+        let ((child_left, child_left_height), (child_right, child_right_height)) =
+            child.split_where(pool, height - 1, goes_left);
+        let left = if self.keys.is_empty() {
+            (child_left, child_left_height)
+        } else {
+            let separator = self.keys.pop_back();
+            let (rest, rest_height) = if self.keys.is_empty() {
+                (Node::take_child(self.children.pop_back()), height - 1)
+            } else {
+                self.recount();
+                (self, height)
+            };
+            Node::join(
+                pool,
+                rest,
+                rest_height,
+                separator,
+                child_left,
+                child_left_height,
+            )
+        };
+        let right = if right_keys.is_empty() {
+            (child_right, child_right_height)
+        } else {
+            let separator = right_keys.pop_front();
+            let (rest, rest_height) = if right_keys.is_empty() {
+                (Node::take_child(right_children.pop_front()), height - 1)
+            } else {
+                (Node::from_parts(right_keys, right_children), height)
+            };
+            Node::join(
+                pool,
+                child_right,
+                child_right_height,
+                separator,
+                rest,
+                rest_height,
+            )
+        };
+        (left, right)
+    }
+
+    /// Join two trees, where every value in `left` is smaller than every
+    /// value in `right`.
+    fn concat(
+        pool: &Pool<Node<A>>,
+        left: Node<A>,
+        left_height: usize,
+        right: Node<A>,
+        right_height: usize,
+    ) -> (Node<A>, usize)
+    where
+        A: Clone,
+    {
+        if left.keys.is_empty() {
+            return (right, right_height);
+        }
+        if right.keys.is_empty() {
+            return (left, left_height);
+        }
+        let (middle, left, left_height) = left.remove_last(pool, left_height);
+        Node::join(pool, left, left_height, middle, right, right_height)
+    }
+
+    /// Join two trees and a value between them, where every value in
+    /// `left` is smaller than `middle`, and every value in `right` is
+    /// larger.
+    fn join(
+        pool: &Pool<Node<A>>,
+        mut left: Node<A>,
+        left_height: usize,
+        middle: A,
+        mut right: Node<A>,
+        right_height: usize,
+    ) -> (Node<A>, usize)
+    where
+        A: Clone,
+    {
+        if left.keys.is_empty() {
+            return Node::insert_into(pool, right, right_height, middle);
+        }
+        if right.keys.is_empty() {
+            return Node::insert_into(pool, left, left_height, middle);
+        }
+        let height = left_height.max(right_height);
+        let (node, overflow) = match left_height.cmp(&right_height) {
+            Ordering::Equal => Node::join_level(left, middle, right),
+            Ordering::Greater => {
+                let overflow = left.join_right(pool, left_height, middle, right, right_height);
+                (left, overflow)
+            }
+            Ordering::Less => {
+                match right.join_left(pool, right_height, left, left_height, middle) {
+                    None => (right, None),
+                    Some((left, median)) => (left, Some((median, right))),
+                }
+            }
+        };
+        match overflow {
+            None => (node, height),
+            Some((median, right)) => (Node::new_from_split(pool, node, median, right), height + 1),
+        }
+    }
+
+    fn insert_into(
+        pool: &Pool<Node<A>>,
+        mut node: Node<A>,
+        height: usize,
+        value: A,
+    ) -> (Node<A>, usize)
+    where
+        A: Clone,
+    {
+        match node.insert(pool, value) {
+            Insert::Split(left, median, right) => {
+                (Node::new_from_split(pool, left, median, right), height + 1)
+            }
+            _ => (node, height),
+        }
+    }
+
+    /// Join two non-empty trees of the same height, returning the
+    /// joined tree, or the left tree and the value and tree to its
+    /// right if they don't fit in a single node.
+    fn join_level(left: Node<A>, middle: A, right: Node<A>) -> (Node<A>, Option<(A, Node<A>)>) {
+        if left.keys.len() + right.keys.len() < NODE_SIZE {
+            (Node::merge(middle, left, right), None)
+        } else {
+            (left, Some((middle, right)))
+        }
+    }
+
+    /// Join a shorter tree onto the right hand side of this one,
+    /// returning a value and node to go to the right of this one if
+    /// it overflows.
+    fn join_right(
+        &mut self,
+        pool: &Pool<Node<A>>,
+        height: usize,
+        middle: A,
+        right: Node<A>,
+        right_height: usize,
+    ) -> Option<(A, Node<A>)>
+    where
+        A: Clone,
+    {
+        let mut child = Node::take_child(self.children.pop_back());
+        let (child, overflow) = if height - 1 == right_height {
+            Node::join_level(child, middle, right)
+        } else {
+            let overflow = child.join_right(pool, height - 1, middle, right, right_height);
+            (child, overflow)
+        };
+        self.children.push_back(Some(PoolRef::new(pool, child)));
+        self.recount();
+        let (median, right) = overflow?;
+        self.push_max_or_split(median, Some(PoolRef::new(pool, right)))
+    }
+
+    /// Join a shorter tree onto the left hand side of this one,
+    /// returning a node and value to go to the left of this one if it
+    /// overflows.
+    fn join_left(
+        &mut self,
+        pool: &Pool<Node<A>>,
+        height: usize,
+        left: Node<A>,
+        left_height: usize,
+        middle: A,
+    ) -> Option<(Node<A>, A)>
+    where
+        A: Clone,
+    {
+        let mut child = Node::take_child(self.children.pop_front());
+        let (child, overflow) = if height - 1 == left_height {
+            match Node::join_level(left, middle, child) {
+                (joined, None) => (joined, None),
+                (left, Some((median, right))) => (right, Some((left, median))),
+            }
+        } else {
+            let overflow = child.join_left(pool, height - 1, left, left_height, middle);
+            (child, overflow)
+        };
+        self.children.push_front(Some(PoolRef::new(pool, child)));
+        self.recount();
+        let (left, median) = overflow?;
+        self.push_min_or_split(median, Some(PoolRef::new(pool, left)))
+    }
+
+    fn push_max_or_split(
+        &mut self,
+        value: A,
+        child: Option<PoolRef<Node<A>>>,
+    ) -> Option<(A, Node<A>)> {
+        if self.has_room() {
+            self.push_max(child, value);
+            return None;
+        }
+        let mut right_keys = self.keys.split_off(MEDIAN);
+        let mut right_children = self.children.split_off(MEDIAN + 1);
+        let median = right_keys.pop_front();
+        right_keys.push_back(value);
+        right_children.push_back(child);
+        self.recount();
+        Some((median, Node::from_parts(right_keys, right_children)))
+    }
+
+    fn push_min_or_split(
+        &mut self,
+        value: A,
+        child: Option<PoolRef<Node<A>>>,
+    ) -> Option<(Node<A>, A)> {
+        if self.has_room() {
+            self.push_min(child, value);
+            return None;
+        }
+        let left_len = NODE_SIZE - MEDIAN;
+        let mut left_keys = Chunk::from_front(&mut self.keys, left_len - 1);
+        let mut left_children = Chunk::from_front(&mut self.children, left_len);
+        let median = self.keys.pop_front();
+        left_keys.push_front(value);
+        left_children.push_front(child);
+        self.recount();
+        Some((Node::from_parts(left_keys, left_children), median))
+    }
+
+    /// Remove the largest value from a non-empty tree, and return it
+    /// along with the new tree.
+    fn remove_last(mut self, pool: &Pool<Node<A>>, height: usize) -> (A, Node<A>, usize)
+    where
+        A: Clone,
+    {
+        let value = self.pop_last(pool);
+        if self.keys.is_empty() && height > 0 {
+            (
+                value,
+                Node::take_child(self.children.pop_back()),
+                height - 1,
+            )
+        } else {
+            (value, self, height)
+        }
+    }
+
+    // Remove the largest value below this node, which may leave this
+    // node without any keys.
+    fn pop_last(&mut self, pool: &Pool<Node<A>>) -> A
+    where
+        A: Clone,
+    {
+        let value;
+        match self.children.last_mut().unwrap() {
+            None => {
+                return self.pop_max().0;
+            }
+            Some(ref mut child_ref) => {
+                let child = PoolRef::make_mut(pool, child_ref);
+                value = child.pop_last(pool);
+                self.size -= 1;
+                if !child.keys.is_empty() {
+                    return value;
+                }
+            }
+        }
+        // The last child has run out of keys, so either steal a key for it
+        // from its left sibling, or merge it into the sibling.
+        let mut child = Node::take_child(self.children.pop_back());
+        let mut sibling = Node::take_child(self.children.pop_back());
+        let separator = self.keys.pop_back();
+        if sibling.keys.len() > 1 {
+            let (sibling_value, grandchild) = sibling.pop_max();
+            child.push_min(grandchild, separator);
+            self.keys.push_back(sibling_value);
+            self.children.push_back(Some(PoolRef::new(pool, sibling)));
+            self.children.push_back(Some(PoolRef::new(pool, child)));
+        } else {
+            let merged = Node::merge(separator, sibling, child);
+            self.children.push_back(Some(PoolRef::new(pool, merged)));
+        }
+        value
+    }
+
+    fn take_child(child: Option<PoolRef<Node<A>>>) -> Node<A>
+    where
+        A: Clone,
+    {
+        PoolRef::unwrap_or_clone(child.unwrap())
+    }
+
+    // Set operations
+    //
+    // Two trees are combined by taking the values in the root of one of
+    // them, splitting the other tree around each of those values,
+    // combining the pieces with the subtrees between the values
+    // recursively, and joining the results back together. Whenever a
+    // piece comes out empty, the subtree it would have been combined with
+    // is kept or dropped as a whole without looking inside it, so when
+    // one tree is much smaller than the other, the cost is O(m log(n/m))
+    // and most of the larger tree's nodes are shared with the result.
+
+    /// Combine two trees, holding `left_size` and `right_size` values, and
+    /// return the new root along with its size.
+    ///
+    /// Values only found in one of the trees are kept if `keep.0` is set
+    /// for `left` or `keep.1` for `right`. For values found in both, `f`
+    /// is called with the value from `left` and the value from `right`,
+    /// and returns the value to keep, if any.
+    pub(crate) fn combine<F>(
+        pool: &Pool<Node<A>>,
+        left: Node<A>,
+        left_size: usize,
+        right: Node<A>,
+        right_size: usize,
+        keep: (bool, bool),
+        f: &mut F,
+    ) -> (Node<A>, usize)
+    where
+        A: Clone,
+        F: FnMut(A, A) -> Option<A>,
+    {
+        let mut matched = 0;
+        let mut kept = 0;
+        let left_height = left.height();
+        let right_height = right.height();
+        let (root, _) = Node::combine_trees(
+            pool,
+            (left, left_height),
+            (right, right_height),
+            keep,
+            &mut |left_value, right_value| {
+                matched += 1;
+                let value = f(left_value, right_value);
+                kept += value.is_some() as usize;
+                value
+            },
+        );
+        let mut size = kept;
+        if keep.0 {
+            size += left_size - matched;
+        }
+        if keep.1 {
+            size += right_size - matched;
+        }
+        (root, size)
+    }
+
+    fn combine_trees<F>(
+        pool: &Pool<Node<A>>,
+        left: (Node<A>, usize),
+        right: (Node<A>, usize),
+        keep: (bool, bool),
+        f: &mut F,
+    ) -> (Node<A>, usize)
+    where
+        A: Clone,
+        F: FnMut(A, A) -> Option<A>,
+    {
+        if right.0.keys.is_empty() {
+            return if keep.0 { left } else { (Node::default(), 0) };
+        }
+        if left.0.keys.is_empty() {
+            return if keep.1 { right } else { (Node::default(), 0) };
+        }
+        let (right, right_height) = right;
+        let mut children = right.children.into_iter();
+        let mut rest = left;
+        let mut out = (Node::default(), 0);
+        let mut middle = None;
+        for right_value in right.keys {
+            let child = Node::subtree(children.next().unwrap(), right_height);
+            let (below, left_value, above) = rest.0.split_around(pool, rest.1, &right_value);
+            rest = above;
+            let node = Node::combine_trees(pool, below, child, keep, f);
+            out = Node::join_or_concat(pool, out, middle, node);
+            middle = match left_value {
+                Some(left_value) => f(left_value, right_value),
+                None if keep.1 => Some(right_value),
+                None => None,
+            };
+        }
+        let child = Node::subtree(children.next().unwrap(), right_height);
+        let node = Node::combine_trees(pool, rest, child, keep, f);
+        Node::join_or_concat(pool, out, middle, node)
+    }
+
+    fn join_or_concat(
+        pool: &Pool<Node<A>>,
+        (left, left_height): (Node<A>, usize),
+        middle: Option<A>,
+        (right, right_height): (Node<A>, usize),
+    ) -> (Node<A>, usize)
+    where
+        A: Clone,
+    {
+        match middle {
+            Some(middle) => Node::join(pool, left, left_height, middle, right, right_height),
+            None => Node::concat(pool, left, left_height, right, right_height),
+        }
+    }
+
+    /// Split a tree into the values smaller than `value`, the value equal
+    /// to it if there is one, and the values larger than it.
+    fn split_around(
+        self,
+        pool: &Pool<Node<A>>,
+        height: usize,
+        value: &A,
+    ) -> ((Node<A>, usize), Option<A>, (Node<A>, usize))
+    where
+        A: Clone,
+    {
+        let ((left, left_height), right) = self.split_where(pool, height, &mut |other: &A| {
+            other.cmp_values(value) != Ordering::Greater
+        });
+        match left.max() {
+            Some(max) if max.cmp_values(value) == Ordering::Equal => {
+                let (max, left, left_height) = left.remove_last(pool, left_height);
+                ((left, left_height), Some(max), right)
+            }
+            _ => ((left, left_height), None, right),
+        }
+    }
+
+    fn subtree(child: Option<PoolRef<Node<A>>>, parent_height: usize) -> (Node<A>, usize)
+    where
+        A: Clone,
+    {
+        match child {
+            None => (Node::default(), 0),
+            Some(child) => (PoolRef::unwrap_or_clone(child), parent_height - 1),
+        }
+    }
 }
 
 // Iterator
@@ -1009,14 +1787,13 @@ impl<'a, A: BTreeValue> Iter<'a, A> {
             Some((node, index)) => match node.children[index] {
                 Some(ref child) => {
                     path.push((node, index));
-                    let mut end = child.keys.len() - 1;
-                    path.push((child, end));
                     let mut node = child;
-                    while let Some(ref right_child) = node.children[end + 1] {
-                        end = right_child.keys.len() - 1;
-                        path.push((right_child, end));
+                    while let Some(ref right_child) = node.children[node.keys.len()] {
+                        path.push((node, node.keys.len()));
                         node = right_child;
                     }
+                    let end = node.keys.len() - 1;
+                    path.push((node, end));
                     Some(&node.keys[end])
                 }
                 None => {
@@ -14,7 +14,7 @@ use sized_chunks::sparse_chunk::{Iter as ChunkIter, IterMut as ChunkIterMut, Spa
 use typenum::{Pow, Unsigned, U2};
 
 use crate::config::HashLevelSize;
-use crate::util::{clone_ref, Pool, PoolClone, PoolDefault, PoolRef, Ref};
+use crate::util::{clone_ref, Merged, Pool, PoolClone, PoolDefault, PoolRef, Ref};
 
 pub(crate) type HashWidth = <U2 as Pow<HashLevelSize>>::Output;
 pub(crate) type HashBits = <HashWidth as Bits>::Store; // a uint of HASH_SIZE bits
@@ -167,6 +167,52 @@ impl<A> Node<A> {
     fn pop(&mut self) -> Entry<A> {
         self.data.pop().unwrap()
     }
+
+    /// Build a trie with the same shape as this one, applying a function
+    /// to each value. The function must leave the key of each value
+    /// alone, or the new trie won't be searchable.
+    pub(crate) fn map_values<B, F>(&self, pool: &Pool<Node<B>>, f: &mut F) -> Node<B>
+    where
+        F: FnMut(&A) -> B,
+    {
+        let mut out = Node::new();
+        for index in 0..HASH_WIDTH {
+            let entry = match self.data.get(index) {
+                None => continue,
+                Some(Entry::Value(value, hash)) => Entry::Value(f(value), *hash),
+                Some(Entry::Collision(coll)) => Entry::Collision(Ref::new(CollisionNode {
+                    hash: coll.hash,
+                    data: coll.data.iter().map(&mut *f).collect(),
+                })),
+                Some(Entry::Node(node)) => {
+                    Entry::Node(PoolRef::new(pool, node.map_values(pool, f)))
+                }
+            };
+            out.data.insert(index, entry);
+        }
+        out
+    }
+
+    /// Fold over the values in the trie, stopping as soon as `f`
+    /// returns `Err`.
+    pub(crate) fn fold_while<'a, B, F>(&'a self, init: B, f: &mut F) -> Result<B, B>
+    where
+        F: FnMut(B, &'a A) -> Result<B, B>,
+    {
+        let mut acc = init;
+        for entry in self.data.iter() {
+            match entry {
+                Entry::Value(value, _) => acc = f(acc, value)?,
+                Entry::Collision(coll) => {
+                    for value in &coll.data {
+                        acc = f(acc, value)?;
+                    }
+                }
+                Entry::Node(child) => acc = child.fold_while(acc, f)?,
+            }
+        }
+        Ok(acc)
+    }
 }
 
 impl<A: HashValue> Node<A> {
@@ -470,6 +516,483 @@ impl<A: HashValue> CollisionNode<A> {
     }
 }
 
+// Merging
+
+/// What to do with a pair of pointer equal subtrees when merging two tries.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Identical {
+    /// Keep a single copy of the subtree without merging it. Its values
+    /// are still counted to keep track of the result's size.
+    Keep,
+    /// Discard the subtree without merging it. Its values are still
+    /// counted to keep track of the result's size.
+    Drop,
+    /// Merge the subtree with itself value by value.
+    Visit,
+}
+
+impl<A> Entry<A> {
+    /// Turn a list of values with the same hash into an entry at the bottom
+    /// level of the trie.
+    fn from_values(hash: HashBits, mut values: Vec<A>) -> Option<Self> {
+        match values.len() {
+            0 => None,
+            1 => Some(Entry::Value(values.pop().unwrap(), hash)),
+            _ => Some(Entry::from(CollisionNode { hash, data: values })),
+        }
+    }
+
+    /// Turn a merged node into an entry, pulling single values up a level
+    /// like `Node::remove` does.
+    fn from_merged(pool: &Pool<Node<A>>, mut node: Node<A>) -> Option<Self> {
+        match node.len() {
+            0 => None,
+            1 if node.data[node.data.first_index().unwrap()].is_value() => Some(node.pop()),
+            _ => Some(Entry::from_node(pool, node)),
+        }
+    }
+}
+
+impl<A: Clone> Entry<A> {
+    /// Get the values of an entry at the bottom level of the trie.
+    fn bottom_values(&self) -> (HashBits, Vec<A>) {
+        match self {
+            Entry::Value(value, hash) => (*hash, vec![value.clone()]),
+            Entry::Collision(coll) => (coll.hash, coll.data.clone()),
+            Entry::Node(_) => unreachable!("nodes::hamt::Entry: node below the bottom level"),
+        }
+    }
+}
+
+impl<A: HashValue + Clone> Node<A> {
+    /// Count the values in a subtree.
+    fn count(&self) -> usize {
+        self.data
+            .iter()
+            .map(|entry| match entry {
+                Entry::Value(_, _) => 1,
+                Entry::Collision(coll) => coll.len(),
+                Entry::Node(child) => child.count(),
+            })
+            .sum()
+    }
+
+    /// Merge two tries, which must have been built using the same hasher.
+    ///
+    /// Slots which are only occupied on one side are shared with the result
+    /// without being visited, and `f` is only called for keys present on both
+    /// sides, with the left value as its first argument. `removed` is
+    /// incremented by the number of values from either side which didn't
+    /// make it into the result.
+    pub(crate) fn merge<F>(
+        pool: &Pool<Node<A>>,
+        left: &Self,
+        right: &Self,
+        shift: usize,
+        identical: Identical,
+        f: &mut F,
+        removed: &mut usize,
+    ) -> Self
+    where
+        F: FnMut(A, A) -> Option<A>,
+    {
+        let mut out = Node::new();
+        for index in 0..HASH_WIDTH {
+            let entry = match (left.data.get(index), right.data.get(index)) {
+                (None, None) => None,
+                (Some(entry), None) | (None, Some(entry)) => Some(entry.clone()),
+                (Some(left), Some(right)) => {
+                    Self::merge_entries(pool, left, right, shift, identical, f, removed)
+                }
+            };
+            if let Some(entry) = entry {
+                out.data.insert(index, entry);
+            }
+        }
+        out
+    }
+
+    fn merge_entries<F>(
+        pool: &Pool<Node<A>>,
+        left: &Entry<A>,
+        right: &Entry<A>,
+        shift: usize,
+        identical: Identical,
+        f: &mut F,
+        removed: &mut usize,
+    ) -> Option<Entry<A>>
+    where
+        F: FnMut(A, A) -> Option<A>,
+    {
+        let child_shift = shift + HASH_SHIFT;
+        if child_shift >= HASH_WIDTH {
+            // We're at the lowest level, where every entry is a value or a
+            // collision node, all with the same hash.
+            if let (Entry::Collision(left_coll), Entry::Collision(right_coll)) = (left, right) {
+                if Ref::ptr_eq(left_coll, right_coll) {
+                    match identical {
+                        Identical::Keep => {
+                            *removed += left_coll.len();
+                            return Some(left.clone());
+                        }
+                        Identical::Drop => {
+                            *removed += left_coll.len() * 2;
+                            return None;
+                        }
+                        Identical::Visit => {}
+                    }
+                }
+            }
+            let (hash, mut values) = left.bottom_values();
+            for right_value in right.bottom_values().1 {
+                let key = right_value.extract_key();
+                match values.iter().position(|value| value.extract_key() == key) {
+                    None => values.push(right_value),
+                    Some(index) => {
+                        let left_value = values.remove(index);
+                        if let Some(value) = f(left_value, right_value) {
+                            values.insert(index, value);
+                            *removed += 1;
+                        } else {
+                            *removed += 2;
+                        }
+                    }
+                }
+            }
+            return Entry::from_values(hash, values);
+        }
+        match (left, right) {
+            (Entry::Node(left_node), Entry::Node(right_node)) => {
+                if PoolRef::ptr_eq(left_node, right_node) {
+                    match identical {
+                        Identical::Keep => {
+                            *removed += left_node.count();
+                            return Some(left.clone());
+                        }
+                        Identical::Drop => {
+                            *removed += left_node.count() * 2;
+                            return None;
+                        }
+                        Identical::Visit => {}
+                    }
+                }
+                let node = Self::merge(
+                    pool,
+                    left_node,
+                    right_node,
+                    child_shift,
+                    identical,
+                    f,
+                    removed,
+                );
+                Entry::from_merged(pool, node)
+            }
+            (Entry::Node(left_node), Entry::Value(value, hash)) => {
+                let index = mask(*hash, child_shift) as usize;
+                let right_node = Node::unit(index, Entry::Value(value.clone(), *hash));
+                let node = Self::merge(
+                    pool,
+                    left_node,
+                    &right_node,
+                    child_shift,
+                    identical,
+                    f,
+                    removed,
+                );
+                Entry::from_merged(pool, node)
+            }
+            (Entry::Value(value, hash), Entry::Node(right_node)) => {
+                let index = mask(*hash, child_shift) as usize;
+                let left_node = Node::unit(index, Entry::Value(value.clone(), *hash));
+                let node = Self::merge(
+                    pool,
+                    &left_node,
+                    right_node,
+                    child_shift,
+                    identical,
+                    f,
+                    removed,
+                );
+                Entry::from_merged(pool, node)
+            }
+            (Entry::Value(left_value, left_hash), Entry::Value(right_value, right_hash)) => {
+                if left_value.extract_key() == right_value.extract_key() {
+                    if let Some(value) = f(left_value.clone(), right_value.clone()) {
+                        *removed += 1;
+                        Some(Entry::Value(value, *left_hash))
+                    } else {
+                        *removed += 2;
+                        None
+                    }
+                } else {
+                    let node = Node::merge_values(
+                        pool,
+                        left_value.clone(),
+                        *left_hash,
+                        right_value.clone(),
+                        *right_hash,
+                        child_shift,
+                    );
+                    Some(Entry::from_node(pool, node))
+                }
+            }
+            _ => unreachable!("nodes::hamt::Node::merge: collision above the bottom level"),
+        }
+    }
+
+    /// Intersect two tries, which must have been built using the same hasher.
+    ///
+    /// Slots which are only occupied on one side are skipped without being
+    /// visited, and `f` is called for every key present on both sides to
+    /// produce the values of the result. `size` is incremented by the number
+    /// of values in the result.
+    pub(crate) fn intersect<B, C, F>(
+        pool: &Pool<Node<C>>,
+        left: &Self,
+        right: &Node<B>,
+        shift: usize,
+        f: &mut F,
+        size: &mut usize,
+    ) -> Node<C>
+    where
+        B: HashValue<Key = A::Key> + Clone,
+        F: FnMut(A, B) -> C,
+    {
+        let mut out = Node::new();
+        for index in 0..HASH_WIDTH {
+            if let (Some(left), Some(right)) = (left.data.get(index), right.data.get(index)) {
+                if let Some(entry) = Self::intersect_entries(pool, left, right, shift, f, size) {
+                    out.data.insert(index, entry);
+                }
+            }
+        }
+        out
+    }
+
+    fn intersect_entries<B, C, F>(
+        pool: &Pool<Node<C>>,
+        left: &Entry<A>,
+        right: &Entry<B>,
+        shift: usize,
+        f: &mut F,
+        size: &mut usize,
+    ) -> Option<Entry<C>>
+    where
+        B: HashValue<Key = A::Key> + Clone,
+        F: FnMut(A, B) -> C,
+    {
+        let child_shift = shift + HASH_SHIFT;
+        if child_shift >= HASH_WIDTH {
+            let (hash, left_values) = left.bottom_values();
+            let mut right_values = right.bottom_values().1;
+            let mut values = Vec::new();
+            for left_value in left_values {
+                let key = left_value.extract_key();
+                if let Some(index) = right_values.iter().position(|v| v.extract_key() == key) {
+                    values.push(f(left_value, right_values.remove(index)));
+                }
+            }
+            *size += values.len();
+            return Entry::from_values(hash, values);
+        }
+        match (left, right) {
+            (Entry::Node(left_node), Entry::Node(right_node)) => {
+                let node = Self::intersect(pool, left_node, right_node, child_shift, f, size);
+                Entry::from_merged(pool, node)
+            }
+            (Entry::Node(left_node), Entry::Value(right_value, hash)) => {
+                let left_value = left_node.get(*hash, child_shift, right_value.extract_key())?;
+                *size += 1;
+                Some(Entry::Value(
+                    f(left_value.clone(), right_value.clone()),
+                    *hash,
+                ))
+            }
+            (Entry::Value(left_value, hash), Entry::Node(right_node)) => {
+                let right_value = right_node.get(*hash, child_shift, left_value.extract_key())?;
+                *size += 1;
+                Some(Entry::Value(
+                    f(left_value.clone(), right_value.clone()),
+                    *hash,
+                ))
+            }
+            (Entry::Value(left_value, hash), Entry::Value(right_value, _)) => {
+                if left_value.extract_key() == right_value.extract_key() {
+                    *size += 1;
+                    Some(Entry::Value(
+                        f(left_value.clone(), right_value.clone()),
+                        *hash,
+                    ))
+                } else {
+                    None
+                }
+            }
+            _ => unreachable!("nodes::hamt::Node::intersect: collision above the bottom level"),
+        }
+    }
+
+    /// Merge two tries into a trie of a third value type, all of which must
+    /// have been built using the same hasher.
+    ///
+    /// Unlike `merge`, slots which are only occupied on one side can't be
+    /// shared with the result, because their values need converting, but
+    /// both tries are still walked side by side in a single pass. `f` is
+    /// called for every key in either trie, and returns the value for it in
+    /// the result, or `None` to leave it out. `size` is incremented by the
+    /// number of values in the result.
+    pub(crate) fn merge_with<B, C, F>(
+        pool: &Pool<Node<C>>,
+        left: &Self,
+        right: &Node<B>,
+        shift: usize,
+        f: &mut F,
+        size: &mut usize,
+    ) -> Node<C>
+    where
+        B: HashValue<Key = A::Key> + Clone,
+        C: HashValue + Clone,
+        F: FnMut(Merged<A, B>) -> Option<C>,
+    {
+        let mut out = Node::new();
+        for index in 0..HASH_WIDTH {
+            let entry = match (left.data.get(index), right.data.get(index)) {
+                (None, None) => None,
+                (Some(left), None) => {
+                    Self::convert_entry(pool, left, &mut |value| f(Merged::Left(value)), size)
+                }
+                (None, Some(right)) => {
+                    Node::convert_entry(pool, right, &mut |value| f(Merged::Right(value)), size)
+                }
+                (Some(left), Some(right)) => {
+                    Self::merge_entries_with(pool, left, right, shift, f, size)
+                }
+            };
+            if let Some(entry) = entry {
+                out.data.insert(index, entry);
+            }
+        }
+        out
+    }
+
+    fn merge_entries_with<B, C, F>(
+        pool: &Pool<Node<C>>,
+        left: &Entry<A>,
+        right: &Entry<B>,
+        shift: usize,
+        f: &mut F,
+        size: &mut usize,
+    ) -> Option<Entry<C>>
+    where
+        B: HashValue<Key = A::Key> + Clone,
+        C: HashValue + Clone,
+        F: FnMut(Merged<A, B>) -> Option<C>,
+    {
+        let child_shift = shift + HASH_SHIFT;
+        if child_shift >= HASH_WIDTH {
+            let (hash, left_values) = left.bottom_values();
+            let mut right_values = right.bottom_values().1;
+            let mut values = Vec::new();
+            for left_value in left_values {
+                let key = left_value.extract_key();
+                let merged = match right_values.iter().position(|v| v.extract_key() == key) {
+                    Some(index) => Merged::Both(left_value, right_values.remove(index)),
+                    None => Merged::Left(left_value),
+                };
+                values.extend(f(merged));
+            }
+            for right_value in right_values {
+                values.extend(f(Merged::Right(right_value)));
+            }
+            *size += values.len();
+            return Entry::from_values(hash, values);
+        }
+        let node = match (left, right) {
+            (Entry::Node(left_node), Entry::Node(right_node)) => {
+                Self::merge_with(pool, left_node, right_node, child_shift, f, size)
+            }
+            (Entry::Node(left_node), Entry::Value(value, hash)) => {
+                let index = mask(*hash, child_shift) as usize;
+                let right_node = Node::unit(index, Entry::Value(value.clone(), *hash));
+                Self::merge_with(pool, left_node, &right_node, child_shift, f, size)
+            }
+            (Entry::Value(value, hash), Entry::Node(right_node)) => {
+                let index = mask(*hash, child_shift) as usize;
+                let left_node = Node::unit(index, Entry::Value(value.clone(), *hash));
+                Self::merge_with(pool, &left_node, right_node, child_shift, f, size)
+            }
+            (Entry::Value(left_value, left_hash), Entry::Value(right_value, right_hash)) => {
+                if left_value.extract_key() == right_value.extract_key() {
+                    let value = f(Merged::Both(left_value.clone(), right_value.clone()))?;
+                    *size += 1;
+                    return Some(Entry::Value(value, *left_hash));
+                }
+                let left_value = f(Merged::Left(left_value.clone()));
+                let right_value = f(Merged::Right(right_value.clone()));
+                match (left_value, right_value) {
+                    (Some(left_value), Some(right_value)) => {
+                        *size += 2;
+                        Node::merge_values(
+                            pool,
+                            left_value,
+                            *left_hash,
+                            right_value,
+                            *right_hash,
+                            child_shift,
+                        )
+                    }
+                    (Some(value), None) => {
+                        *size += 1;
+                        return Some(Entry::Value(value, *left_hash));
+                    }
+                    (None, Some(value)) => {
+                        *size += 1;
+                        return Some(Entry::Value(value, *right_hash));
+                    }
+                    (None, None) => return None,
+                }
+            }
+            _ => unreachable!("nodes::hamt::Node::merge_with: collision above the bottom level"),
+        };
+        Entry::from_merged(pool, node)
+    }
+
+    /// Convert the values under an entry which only one side of a merge has,
+    /// leaving out the ones `f` returns `None` for.
+    fn convert_entry<C, F>(
+        pool: &Pool<Node<C>>,
+        entry: &Entry<A>,
+        f: &mut F,
+        size: &mut usize,
+    ) -> Option<Entry<C>>
+    where
+        F: FnMut(A) -> Option<C>,
+    {
+        match entry {
+            Entry::Value(value, hash) => {
+                let value = f(value.clone())?;
+                *size += 1;
+                Some(Entry::Value(value, *hash))
+            }
+            Entry::Collision(coll) => {
+                let values: Vec<C> = coll.data.iter().cloned().filter_map(&mut *f).collect();
+                *size += values.len();
+                Entry::from_values(coll.hash, values)
+            }
+            Entry::Node(node) => {
+                let mut out = Node::new();
+                for index in 0..HASH_WIDTH {
+                    if let Some(entry) = node.data.get(index) {
+                        if let Some(entry) = Self::convert_entry(pool, entry, f, size) {
+                            out.data.insert(index, entry);
+                        }
+                    }
+                }
+                Entry::from_merged(pool, out)
+            }
+        }
+    }
+}
+
 // Ref iterator
 
 pub(crate) struct Iter<'a, A> {
@@ -713,6 +1236,203 @@ impl<A: HashValue> ExactSizeIterator for Drain<A> where A: Clone {}
 
 impl<A: HashValue> FusedIterator for Drain<A> where A: Clone {}
 
+// Diff iterator
+
+/// A description of a difference between two tries.
+pub(crate) enum DiffItem<'a, A> {
+    Add(&'a A),
+    Update { old: &'a A, new: &'a A },
+    Remove(&'a A),
+}
+
+enum DiffWork<'a, A> {
+    Compare(&'a Node<A>, &'a Node<A>, usize),
+    Add(Iter<'a, A>, Option<&'a A>),
+    Remove(Iter<'a, A>, Option<&'a A>),
+}
+
+/// An iterator over the differences between two tries built with the same
+/// hasher, which skips over any subtrees the two tries share.
+pub(crate) struct DiffIter<'a, A> {
+    stack: Vec<DiffWork<'a, A>>,
+    items: Vec<DiffItem<'a, A>>,
+}
+
+fn same_key<A: HashValue>(except: Option<&A>, value: &A) -> bool {
+    match except {
+        Some(except) => except.extract_key() == value.extract_key(),
+        None => false,
+    }
+}
+
+impl<'a, A> DiffIter<'a, A>
+where
+    A: HashValue + PartialEq + 'a,
+{
+    pub(crate) fn new(old: &'a Node<A>, new: &'a Node<A>) -> Self {
+        DiffIter {
+            stack: if ptr::eq(old, new) {
+                Vec::new()
+            } else {
+                vec![DiffWork::Compare(old, new, 0)]
+            },
+            items: Vec::new(),
+        }
+    }
+
+    // The subtree iterators are given a count they'll never reach, so they
+    // run until they've exhausted the subtree.
+
+    fn add(&mut self, entry: &'a Entry<A>, except: Option<&'a A>) {
+        match entry {
+            Entry::Value(value, _) => {
+                if !same_key(except, value) {
+                    self.items.push(DiffItem::Add(value));
+                }
+            }
+            Entry::Collision(coll) => {
+                for value in &coll.data {
+                    if !same_key(except, value) {
+                        self.items.push(DiffItem::Add(value));
+                    }
+                }
+            }
+            Entry::Node(node) => self
+                .stack
+                .push(DiffWork::Add(Iter::new(node, usize::MAX), except)),
+        }
+    }
+
+    fn remove(&mut self, entry: &'a Entry<A>, except: Option<&'a A>) {
+        match entry {
+            Entry::Value(value, _) => {
+                if !same_key(except, value) {
+                    self.items.push(DiffItem::Remove(value));
+                }
+            }
+            Entry::Collision(coll) => {
+                for value in &coll.data {
+                    if !same_key(except, value) {
+                        self.items.push(DiffItem::Remove(value));
+                    }
+                }
+            }
+            Entry::Node(node) => self
+                .stack
+                .push(DiffWork::Remove(Iter::new(node, usize::MAX), except)),
+        }
+    }
+
+    fn compare_values(&mut self, old: &'a A, new: &'a A) {
+        if old != new {
+            self.items.push(DiffItem::Update { old, new });
+        }
+    }
+
+    fn compare_nodes(&mut self, old: &'a Node<A>, new: &'a Node<A>, shift: usize) {
+        for index in 0..HASH_WIDTH {
+            match (old.data.get(index), new.data.get(index)) {
+                (None, None) => {}
+                (Some(old), None) => self.remove(old, None),
+                (None, Some(new)) => self.add(new, None),
+                (Some(old), Some(new)) => self.compare_entries(old, new, shift + HASH_SHIFT),
+            }
+        }
+    }
+
+    fn compare_entries(&mut self, old: &'a Entry<A>, new: &'a Entry<A>, child_shift: usize) {
+        match (old, new) {
+            (Entry::Node(old_node), Entry::Node(new_node)) => {
+                if !PoolRef::ptr_eq(old_node, new_node) {
+                    self.stack
+                        .push(DiffWork::Compare(old_node, new_node, child_shift));
+                }
+            }
+            (Entry::Collision(old_coll), Entry::Collision(new_coll))
+                if Ref::ptr_eq(old_coll, new_coll) => {}
+            (Entry::Node(old_node), Entry::Value(new_value, hash)) => {
+                match old_node.get(*hash, child_shift, new_value.extract_key()) {
+                    Some(old_value) => self.compare_values(old_value, new_value),
+                    None => self.items.push(DiffItem::Add(new_value)),
+                }
+                self.remove(old, Some(new_value));
+            }
+            (Entry::Value(old_value, hash), Entry::Node(new_node)) => {
+                match new_node.get(*hash, child_shift, old_value.extract_key()) {
+                    Some(new_value) => self.compare_values(old_value, new_value),
+                    None => self.items.push(DiffItem::Remove(old_value)),
+                }
+                self.add(new, Some(old_value));
+            }
+            _ => {
+                // Only values and collision nodes are left, which we can
+                // compare as lists of values.
+                let old_values = old.values();
+                let new_values = new.values();
+                for old_value in old_values {
+                    let key = old_value.extract_key();
+                    match new_values.iter().find(|value| value.extract_key() == key) {
+                        Some(new_value) => self.compare_values(old_value, new_value),
+                        None => self.items.push(DiffItem::Remove(old_value)),
+                    }
+                }
+                for new_value in new_values {
+                    let key = new_value.extract_key();
+                    if !old_values.iter().any(|value| value.extract_key() == key) {
+                        self.items.push(DiffItem::Add(new_value));
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<A> Entry<A> {
+    fn values(&self) -> &[A] {
+        match self {
+            Entry::Value(value, _) => std::slice::from_ref(value),
+            Entry::Collision(coll) => &coll.data,
+            Entry::Node(_) => unreachable!("nodes::hamt::Entry::values: not a value"),
+        }
+    }
+}
+
+impl<'a, A> Iterator for DiffIter<'a, A>
+where
+    A: HashValue + PartialEq + 'a,
+{
+    type Item = DiffItem<'a, A>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.items.pop() {
+                return Some(item);
+            }
+            match self.stack.pop()? {
+                DiffWork::Compare(old, new, shift) => self.compare_nodes(old, new, shift),
+                DiffWork::Add(mut it, except) => {
+                    if let Some((value, _)) = it.next() {
+                        self.stack.push(DiffWork::Add(it, except));
+                        if !same_key(except, value) {
+                            return Some(DiffItem::Add(value));
+                        }
+                    }
+                }
+                DiffWork::Remove(mut it, except) => {
+                    if let Some((value, _)) = it.next() {
+                        self.stack.push(DiffWork::Remove(it, except));
+                        if !same_key(except, value) {
+                            return Some(DiffItem::Remove(value));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<'a, A> FusedIterator for DiffIter<'a, A> where A: HashValue + PartialEq + 'a {}
+
 impl<A: HashValue + fmt::Debug> fmt::Debug for Node<A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         write!(f, "Node[ ")?;
@@ -922,6 +922,8 @@ impl<A: Clone> Node<A> {
         middle: Self,
         right: Self,
     ) -> Self {
+        #[cfg(feature = "instrument")]
+        crate::instrument::record_rebalance();
         let left_nodes = left.children.nodes().into_iter();
         let middle_nodes = middle.children.nodes().into_iter();
         let right_nodes = right.children.nodes().into_iter();
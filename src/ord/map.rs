@@ -13,7 +13,21 @@
 //! ordered, so that keys always come out from lowest to highest,
 //! where a [`HashMap`][hashmap::HashMap] has no guaranteed ordering.
 //!
+//! ## Performance Notes
+//!
+//! The B-tree's nodes are wide, holding up to 64 key/value pairs each
+//! in a contiguous array, so ordered scans over a [`range`][range] of
+//! keys spend most of their time walking through arrays rather than
+//! following pointers. A B+-tree with linked leaves would make scans a
+//! little cheaper still, but linking leaves together doesn't work for
+//! a persistent structure, because updating any leaf would mean copying
+//! every leaf before it in the chain. If you need to visit every pair
+//! in a map, [`fold`][fold] walks the tree directly, which is faster
+//! than going through an iterator.
+//!
 //! [1]: https://en.wikipedia.org/wiki/B-tree
+//! [range]: ./struct.OrdMap.html#method.range
+//! [fold]: ./struct.OrdMap.html#method.fold
 //! [hashmap::HashMap]: ../hashmap/struct.HashMap.html
 //! [std::cmp::Ord]: https://doc.rust-lang.org/std/cmp/trait.Ord.html
 
@@ -22,16 +36,20 @@ use std::cmp::Ordering;
 use std::collections;
 use std::fmt::{Debug, Error, Formatter};
 use std::hash::{BuildHasher, Hash, Hasher};
-use std::iter::{FromIterator, Iterator, Sum};
+use std::iter::{FromIterator, FusedIterator, Iterator, Rev, Sum};
 use std::mem;
 use std::ops::{Add, Index, IndexMut, RangeBounds};
 
 use crate::hashmap::HashMap;
+use crate::hashset::HashSet;
+use crate::merge::{Merge, MergeStrategy};
 use crate::nodes::btree::{BTreeValue, Insert, Node, Remove};
+use crate::ordset::OrdSet;
 #[cfg(has_specialisation)]
 use crate::util::linear_search_by;
-use crate::util::{Pool, PoolRef};
+use crate::util::{prefer_point_updates, MergeIter, Merged, Pool, PoolRef};
 
+pub use crate::error::map::OccupiedError;
 pub use crate::nodes::btree::{
     ConsumingIter, DiffItem as NodeDiffItem, DiffIter as NodeDiffIter, Iter as RangedIter,
 };
@@ -354,7 +372,76 @@ where
         self.root.min()
     }
 
+    /// Get the key/value pair at a given position in the map's
+    /// ordering, counting from the smallest key at index 0.
+    ///
+    /// If the index is out of bounds, returns `None`.
+    ///
+    /// Each node in the tree keeps count of the keys under it, so this
+    /// only has to follow a single path down the tree.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let map = ordmap!{10 => "a", 20 => "b", 30 => "c"};
+    /// assert_eq!(Some((&20, &"b")), map.get_index(map.len() / 2));
+    /// assert_eq!(None, map.get_index(3));
+    /// ```
+    #[must_use]
+    pub fn get_index(&self, index: usize) -> Option<(&K, &V)> {
+        self.root
+            .lookup_index(index)
+            .map(|(key, value)| (key, value))
+    }
+
+    /// Get the position of a key in the map's ordering, or `None` if the
+    /// key isn't in the map.
+    ///
+    /// This is the inverse of [`get_index()`][get_index]: it counts the
+    /// keys smaller than the one given, using the counts kept in the
+    /// nodes along the path to it.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let map = ordmap!{10 => "a", 20 => "b", 30 => "c"};
+    /// assert_eq!(Some(2), map.index_of(&30));
+    /// assert_eq!(None, map.index_of(&25));
+    /// ```
+    ///
+    /// [get_index]: #method.get_index
+    #[must_use]
+    pub fn index_of<BK>(&self, key: &BK) -> Option<usize>
+    where
+        BK: Ord + ?Sized,
+        K: Borrow<BK>,
+    {
+        self.root.index_of(key)
+    }
+
     /// Get an iterator over the key/value pairs of a map.
+    ///
+    /// The pairs come out in ascending key order. The iterator is double
+    /// ended, so calling `rev()` on it walks the map from the largest key
+    /// down, without any extra work.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let events = ordmap!{100 => "start", 200 => "tick", 300 => "stop"};
+    /// let latest: Vec<_> = events.iter().rev().take(2).map(|(_, v)| *v).collect();
+    /// assert_eq!(vec!["stop", "tick"], latest);
+    /// ```
     #[must_use]
     pub fn iter(&self) -> Iter<'_, K, V> {
         Iter {
@@ -362,6 +449,26 @@ where
         }
     }
 
+    /// Get an iterator over clones of the key/value pairs of a map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let map = ordmap!{1 => "one", 2 => "two"};
+    /// let pairs: Vec<(i32, &str)> = map.cloned_iter().collect();
+    /// assert_eq!(vec![(1, "one"), (2, "two")], pairs);
+    /// ```
+    #[must_use]
+    pub fn cloned_iter(&self) -> ClonedIter<'_, K, V>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        ClonedIter { it: self.iter() }
+    }
+
     /// Create an iterator over a range of key/value pairs.
     #[must_use]
     pub fn range<R, BK>(&self, range: R) -> Iter<'_, K, V>
@@ -387,6 +494,251 @@ where
         Values { it: self.iter() }
     }
 
+    /// Get a view of a map in descending key order.
+    ///
+    /// The view borrows the map rather than copying it, and iterates
+    /// over it backwards, with its notion of smallest and largest key
+    /// flipped.
+    ///
+    /// Time: O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let scores = ordmap!{10 => "alice", 30 => "bob", 20 => "carol"};
+    /// let leaderboard = scores.reversed();
+    /// assert_eq!(Some(&(30, "bob")), leaderboard.get_min());
+    /// assert_eq!(
+    ///     vec!["bob", "carol", "alice"],
+    ///     leaderboard.values().cloned().collect::<Vec<_>>()
+    /// );
+    /// ```
+    #[must_use]
+    pub fn reversed(&self) -> Reversed<'_, K, V> {
+        Reversed { map: self }
+    }
+
+    /// Fold the key/value pairs of a map into an accumulator, in key order.
+    ///
+    /// This walks the tree directly rather than going through an
+    /// iterator, which makes it faster than `iter().fold()`.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let map = ordmap!{1 => 11, 2 => 22, 3 => 33};
+    /// assert_eq!(66, map.fold(0, |sum, (_, value)| sum + value));
+    /// ```
+    pub fn fold<'a, B, F>(&'a self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, (&'a K, &'a V)) -> B,
+    {
+        match self
+            .root
+            .fold_while(init, &mut |acc, (key, value)| Ok(f(acc, (key, value))))
+        {
+            Ok(acc) | Err(acc) => acc,
+        }
+    }
+
+    /// Fold the key/value pairs of a map into an accumulator, in key order, passing
+    /// the key and the value to the function as separate arguments.
+    ///
+    /// Like [`fold`][fold], this walks the tree directly.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let map = ordmap!{1 => 11, 2 => 22, 3 => 33};
+    /// assert_eq!(72, map.fold_with_key(0, |sum, key, value| sum + key + value));
+    /// ```
+    ///
+    /// [fold]: #method.fold
+    pub fn fold_with_key<'a, B, F>(&'a self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, &'a K, &'a V) -> B,
+    {
+        self.fold(init, |acc, (key, value)| f(acc, key, value))
+    }
+
+    /// Call a function on every key/value pair in a map, in key order.
+    ///
+    /// Like [`fold`][fold], this walks the tree directly rather than
+    /// going through an iterator.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let map = ordmap!{1 => "a", 2 => "b", 3 => "c"};
+    /// let mut keys = Vec::new();
+    /// map.for_each(|key, _| keys.push(*key));
+    /// assert_eq!(vec![1, 2, 3], keys);
+    /// ```
+    ///
+    /// [fold]: #method.fold
+    pub fn for_each<'a, F>(&'a self, mut f: F)
+    where
+        F: FnMut(&'a K, &'a V),
+    {
+        self.fold((), |(), (key, value)| f(key, value))
+    }
+
+    /// Test whether any key/value pair in a map satisfies a predicate.
+    ///
+    /// Time: O(n)
+    pub fn any<F>(&self, mut f: F) -> bool
+    where
+        F: FnMut((&K, &V)) -> bool,
+    {
+        self.find(|(key, value)| f((key, value))).is_some()
+    }
+
+    /// Test whether every key/value pair in a map satisfies a predicate.
+    ///
+    /// Time: O(n)
+    pub fn all<F>(&self, mut f: F) -> bool
+    where
+        F: FnMut((&K, &V)) -> bool,
+    {
+        !self.any(|(key, value)| !f((key, value)))
+    }
+
+    /// Find the key/value pair with the smallest key in a map which
+    /// satisfies a predicate.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let map = ordmap!{1 => 11, 2 => 22, 3 => 33};
+    /// assert_eq!(Some((&2, &22)), map.find(|(key, _)| *key > 1));
+    /// assert_eq!(None, map.find(|(_, value)| *value > 33));
+    /// ```
+    #[must_use]
+    pub fn find<F>(&self, mut f: F) -> Option<(&K, &V)>
+    where
+        F: FnMut((&K, &V)) -> bool,
+    {
+        match self.root.fold_while(None, &mut |_, (key, value)| {
+            if f((key, value)) {
+                Err(Some((key, value)))
+            } else {
+                Ok(None)
+            }
+        }) {
+            Ok(_) => None,
+            Err(found) => found,
+        }
+    }
+
+    /// Collect the keys of a map into an ordered set.
+    ///
+    /// The keys are already in order, so the set is built directly from
+    /// them rather than by inserting them one at a time.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// # use im::ordset::OrdSet;
+    /// let map = ordmap!{1 => "up", 2 => "down", 3 => "up"};
+    /// assert_eq!(ordset![1, 2, 3], map.keys_set());
+    /// ```
+    #[must_use]
+    pub fn keys_set(&self) -> OrdSet<K>
+    where
+        K: Clone,
+    {
+        OrdSet::from_ordered(self.keys().cloned())
+    }
+
+    /// Collect the distinct values of a map into an ordered set.
+    ///
+    /// Time: O(n log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// # use im::ordset::OrdSet;
+    /// let map = ordmap!{1 => "up", 2 => "down", 3 => "up"};
+    /// assert_eq!(ordset!["down", "up"], map.values_set());
+    /// ```
+    #[must_use]
+    pub fn values_set(&self) -> OrdSet<V>
+    where
+        V: Ord + Clone,
+    {
+        self.values().cloned().collect()
+    }
+
+    /// Collect the distinct values of a map into a hash set.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// # use im::hashset::HashSet;
+    /// let map = ordmap!{1 => "up", 2 => "down", 3 => "up"};
+    /// assert_eq!(hashset!["down", "up"], map.values_hashset());
+    /// ```
+    #[must_use]
+    pub fn values_hashset(&self) -> HashSet<V>
+    where
+        V: Hash + Eq + Clone,
+    {
+        self.values().cloned().collect()
+    }
+
+    /// Construct a reverse index of a map, mapping each distinct value
+    /// to the set of keys which map to it.
+    ///
+    /// Time: O(n log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let map = ordmap!{1 => "up", 2 => "down", 3 => "up"};
+    /// assert_eq!(
+    ///     ordmap!{"down" => ordset![2], "up" => ordset![1, 3]},
+    ///     map.invert()
+    /// );
+    /// ```
+    #[must_use]
+    pub fn invert(&self) -> OrdMap<V, OrdSet<K>>
+    where
+        K: Clone,
+        V: Ord + Clone,
+    {
+        OrdMap::group_by(self.iter().map(|(key, value)| (value.clone(), key.clone())))
+    }
+
     /// Get an iterator over the differences between this map and
     /// another, i.e. the set of entries to add, update, or remove to
     /// this map in order to make it equal to the other map.
@@ -405,6 +757,47 @@ where
         }
     }
 
+    /// Get an iterator which walks this map and another side by side in
+    /// key order.
+    ///
+    /// Each key in either map comes out once, as a
+    /// [`MergeItem`][MergeItem] saying whether it's only in this map,
+    /// only in the other map, or in both, along with its values. This
+    /// is what [`merge_with_key`][merge_with_key] does to build a new
+    /// map, but lazily and without cloning anything.
+    ///
+    /// Time: O(n + m)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::{OrdMap, MergeItem};
+    /// let names = ordmap!{1 => "alice", 2 => "bob"};
+    /// let ages = ordmap!{2 => 42, 3 => 23};
+    /// let merged: Vec<_> = names.zip_merge(&ages).collect();
+    /// assert_eq!(
+    ///     vec![
+    ///         MergeItem::Left(&1, &"alice"),
+    ///         MergeItem::Both(&2, &"bob", &42),
+    ///         MergeItem::Right(&3, &23),
+    ///     ],
+    ///     merged
+    /// );
+    /// ```
+    ///
+    /// [MergeItem]: ./enum.MergeItem.html
+    /// [merge_with_key]: #method.merge_with_key
+    #[must_use]
+    pub fn zip_merge<'a, W>(&'a self, other: &'a OrdMap<K, W>) -> ZipMergeIter<'a, K, V, W>
+    where
+        (K, W): BTreeValue,
+    {
+        ZipMergeIter {
+            it: MergeIter::new(self.iter(), other.iter(), |left, right| left.0.cmp(right.0)),
+        }
+    }
+
     /// Get the value for a key from a map.
     ///
     /// Time: O(log n)
@@ -431,6 +824,10 @@ where
 
     /// Get the key/value pair for a key from a map.
     ///
+    /// The key returned is the one stored in the map, not the one
+    /// you looked up, which is useful for interning or for keys which
+    /// compare equal but can still be told apart.
+    ///
     /// Time: O(log n)
     ///
     /// # Examples
@@ -453,6 +850,54 @@ where
         self.root.lookup(key).map(|&(ref k, ref v)| (k, v))
     }
 
+    /// Get a copy of the value for a key, or a default value if the key
+    /// isn't in the map.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let map = ordmap!{"port" => 8080};
+    /// assert_eq!(8080, map.get_or("port", 80));
+    /// assert_eq!(30, map.get_or("timeout", 30));
+    /// ```
+    #[must_use]
+    pub fn get_or<BK>(&self, key: &BK, default: V) -> V
+    where
+        BK: Ord + ?Sized,
+        K: Borrow<BK>,
+        V: Clone,
+    {
+        self.get(key).cloned().unwrap_or(default)
+    }
+
+    /// Get a copy of the value for a key, or call a function to make a
+    /// default value if the key isn't in the map.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let map = ordmap!{"host" => "example.com".to_string()};
+    /// assert_eq!("localhost", map.get_or_else("proxy", || "localhost".to_string()));
+    /// ```
+    #[must_use]
+    pub fn get_or_else<BK, F>(&self, key: &BK, f: F) -> V
+    where
+        BK: Ord + ?Sized,
+        K: Borrow<BK>,
+        V: Clone,
+        F: FnOnce() -> V,
+    {
+        self.get(key).cloned().unwrap_or_else(f)
+    }
+
     /// Get the closest smaller entry in a map to a given key
     /// as a mutable reference.
     ///
@@ -503,13 +948,123 @@ where
         self.root.lookup_next(key).map(|(k, v)| (k, v))
     }
 
-    /// Test for the presence of a key in a map.
+    /// Get the entry with the largest key less than a given key.
+    ///
+    /// Unlike [`get_prev`][get_prev], an entry for the key itself is
+    /// never returned.
     ///
     /// Time: O(log n)
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```rust
+    /// # #[macro_use] extern crate im;
+    /// # use im::OrdMap;
+    /// let map = ordmap![1 => "a", 3 => "b", 5 => "c"];
+    /// assert_eq!(Some((&1, &"a")), map.lookup_lt(&3));
+    /// assert_eq!(None, map.lookup_lt(&1));
+    /// ```
+    ///
+    /// [get_prev]: #method.get_prev
+    #[must_use]
+    pub fn lookup_lt<BK>(&self, key: &BK) -> Option<(&K, &V)>
+    where
+        BK: Ord + ?Sized,
+        K: Borrow<BK>,
+    {
+        self.root.lookup_below(key, false).map(|(k, v)| (k, v))
+    }
+
+    /// Get the entry with the largest key less than or equal to a given
+    /// key.
+    ///
+    /// This is the same as [`get_prev`][get_prev].
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate im;
+    /// # use im::OrdMap;
+    /// let map = ordmap![1 => "a", 3 => "b", 5 => "c"];
+    /// assert_eq!(Some((&3, &"b")), map.lookup_le(&3));
+    /// assert_eq!(Some((&3, &"b")), map.lookup_le(&4));
+    /// assert_eq!(None, map.lookup_le(&0));
+    /// ```
+    ///
+    /// [get_prev]: #method.get_prev
+    #[must_use]
+    pub fn lookup_le<BK>(&self, key: &BK) -> Option<(&K, &V)>
+    where
+        BK: Ord + ?Sized,
+        K: Borrow<BK>,
+    {
+        self.get_prev(key)
+    }
+
+    /// Get the entry with the smallest key greater than a given key.
+    ///
+    /// Unlike [`get_next`][get_next], an entry for the key itself is
+    /// never returned.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate im;
+    /// # use im::OrdMap;
+    /// let map = ordmap![1 => "a", 3 => "b", 5 => "c"];
+    /// assert_eq!(Some((&5, &"c")), map.lookup_gt(&3));
+    /// assert_eq!(None, map.lookup_gt(&5));
+    /// ```
+    ///
+    /// [get_next]: #method.get_next
+    #[must_use]
+    pub fn lookup_gt<BK>(&self, key: &BK) -> Option<(&K, &V)>
+    where
+        BK: Ord + ?Sized,
+        K: Borrow<BK>,
+    {
+        self.root.lookup_above(key, false).map(|(k, v)| (k, v))
+    }
+
+    /// Get the entry with the smallest key greater than or equal to a
+    /// given key.
+    ///
+    /// This is the same as [`get_next`][get_next].
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate im;
+    /// # use im::OrdMap;
+    /// let map = ordmap![1 => "a", 3 => "b", 5 => "c"];
+    /// assert_eq!(Some((&3, &"b")), map.lookup_ge(&3));
+    /// assert_eq!(Some((&5, &"c")), map.lookup_ge(&4));
+    /// assert_eq!(None, map.lookup_ge(&6));
+    /// ```
+    ///
+    /// [get_next]: #method.get_next
+    #[must_use]
+    pub fn lookup_ge<BK>(&self, key: &BK) -> Option<(&K, &V)>
+    where
+        BK: Ord + ?Sized,
+        K: Borrow<BK>,
+    {
+        self.get_next(key)
+    }
+
+    /// Test for the presence of a key in a map.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
     /// # #[macro_use] extern crate im;
     /// # use im::ordmap::OrdMap;
     /// let map = ordmap!{123 => "lol"};
@@ -542,8 +1097,12 @@ where
         F: FnMut(&V, &B) -> bool,
         RM: Borrow<OrdMap<K, B>>,
     {
+        let other = other.borrow();
+        if other.len() < self.len() {
+            return false;
+        }
         self.iter()
-            .all(|(k, v)| other.borrow().get(k).map(|ov| cmp(v, ov)).unwrap_or(false))
+            .all(|(k, v)| other.get(k).map(|ov| cmp(v, ov)).unwrap_or(false))
     }
 
     /// Test whether a map is a proper submap of another map, meaning
@@ -615,6 +1174,45 @@ where
     {
         self.is_proper_submap_by(other.borrow(), PartialEq::eq)
     }
+
+    /// Test whether a map is a supermap of another map, meaning that
+    /// all keys in the other map must also be in our map, with the
+    /// same values.
+    ///
+    /// Time: O(m log n) where m is the size of the other map
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let map1 = ordmap!{1 => 1, 2 => 2, 3 => 3};
+    /// let map2 = ordmap!{1 => 1, 2 => 2};
+    /// assert!(map1.is_supermap(map2));
+    /// ```
+    #[must_use]
+    pub fn is_supermap<RM>(&self, other: RM) -> bool
+    where
+        V: PartialEq,
+        RM: Borrow<Self>,
+    {
+        other.borrow().is_submap(self)
+    }
+
+    /// Test whether a map is a proper supermap of another map, meaning
+    /// that all keys in the other map must also be in our map, with
+    /// the same values. To be a proper supermap, ours must also contain
+    /// more keys than the other map.
+    ///
+    /// Time: O(m log n) where m is the size of the other map
+    #[must_use]
+    pub fn is_proper_supermap<RM>(&self, other: RM) -> bool
+    where
+        V: PartialEq,
+        RM: Borrow<Self>,
+    {
+        other.borrow().is_proper_submap(self)
+    }
 }
 
 impl<K, V> OrdMap<K, V>
@@ -622,6 +1220,67 @@ where
     K: Ord + Clone,
     V: Clone,
 {
+    /// Construct a map from key/value pairs which are already in
+    /// ascending key order.
+    ///
+    /// The tree is built directly from the pairs instead of inserting
+    /// them one at a time. If a key is equal to the one before it, its
+    /// value replaces the previous one, as with [`insert`][insert]. If the
+    /// keys turn out not to be in order, this falls back to inserting the
+    /// rest one at a time, so the result is always correct.
+    ///
+    /// Time: O(n) for ordered input, O(n log n) otherwise
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let map = OrdMap::from_ordered((0..100_000).map(|i| (i, i * 2)));
+    /// assert_eq!(100_000, map.len());
+    /// assert_eq!(Some(&198), map.get(&99));
+    /// assert_eq!(
+    ///     ordmap!{1 => "b", 2 => "c"},
+    ///     OrdMap::from_ordered(vec![(1, "a"), (1, "b"), (2, "c")])
+    /// );
+    /// ```
+    ///
+    /// [insert]: #method.insert
+    #[must_use]
+    pub fn from_ordered<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let mut iter = iter.into_iter();
+        let mut entries: Vec<(K, V)> = Vec::with_capacity(iter.size_hint().0);
+        for (key, value) in iter.by_ref() {
+            if let Some(last) = entries.last_mut() {
+                match last.0.cmp(&key) {
+                    Ordering::Less => {}
+                    Ordering::Equal => {
+                        *last = (key, value);
+                        continue;
+                    }
+                    Ordering::Greater => {
+                        let mut map: Self = entries.into_iter().collect();
+                        map.insert(key, value);
+                        map.extend(iter);
+                        return map;
+                    }
+                }
+            }
+            entries.push((key, value));
+        }
+        let pool = OrdMapPool::default();
+        let size = entries.len();
+        let root = Node::from_sorted(&pool.0, &mut entries.into_iter(), size);
+        OrdMap {
+            size,
+            root: PoolRef::new(&pool.0, root),
+            pool,
+        }
+    }
+
     /// Get a mutable reference to the value for a key from a map.
     ///
     /// Time: O(log n)
@@ -759,54 +1418,602 @@ where
         None
     }
 
+    /// Insert a key/value mapping into a map, combining it with the
+    /// existing value if the map already has a mapping for the key.
+    ///
+    /// If the key is already in the map, the provided function is
+    /// called with the old value and the new value, and the result
+    /// replaces the old value. Otherwise, the new value is inserted as
+    /// is.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let mut counts = ordmap!{};
+    /// for word in "a b a c a b".split(' ') {
+    ///     counts.insert_with(word, 1, |old, new| old + new);
+    /// }
+    /// assert_eq!(ordmap!{"a" => 3, "b" => 2, "c" => 1}, counts);
+    /// ```
+    pub fn insert_with<F>(&mut self, key: K, value: V, f: F)
+    where
+        F: FnOnce(V, V) -> V,
+    {
+        self.insert_with_key(key, value, |_, old, new| f(old, new))
+    }
+
+    /// Insert a key/value mapping into a map, combining it with the
+    /// existing value if the map already has a mapping for the key.
+    ///
+    /// Like [`insert_with`][insert_with], but the function also gets
+    /// the key.
+    ///
+    /// Time: O(log n)
+    ///
+    /// [insert_with]: #method.insert_with
+    pub fn insert_with_key<F>(&mut self, key: K, value: V, f: F)
+    where
+        F: FnOnce(&K, V, V) -> V,
+    {
+        if let Some(existing) = self.get_mut(&key) {
+            let old = existing.clone();
+            *existing = f(&key, old, value);
+            return;
+        }
+        self.insert(key, value);
+    }
+
+    /// Construct a map from an iterator of key/value pairs, using a
+    /// function to combine the values when a key occurs more than once.
+    ///
+    /// The function is called with the value collected for the key so
+    /// far and the next value for it, in the order the iterator
+    /// produces them, and its result becomes the value for the key. Pass
+    /// `|first, _| first` to keep the first value for each key, or
+    /// `|_, last| last` to keep the last one, which is what
+    /// [`collect()`][collect] does.
+    ///
+    /// Time: O(n log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let pairs = vec![("a", 1), ("b", 2), ("a", 3)];
+    /// let first: OrdMap<_, _> = OrdMap::from_iter_with(pairs.clone(), |first, _| first);
+    /// assert_eq!(ordmap!{"a" => 1, "b" => 2}, first);
+    /// let sum: OrdMap<_, _> = OrdMap::from_iter_with(pairs, |sum, next| sum + next);
+    /// assert_eq!(ordmap!{"a" => 4, "b" => 2}, sum);
+    /// ```
+    ///
+    /// [collect]: https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.collect
+    #[must_use]
+    pub fn from_iter_with<I, F>(iter: I, mut f: F) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        F: FnMut(V, V) -> V,
+    {
+        let mut out = Self::default();
+        for (key, value) in iter {
+            out.insert_with(key, value, &mut f);
+        }
+        out
+    }
+
+    /// Construct a map of groups from an iterator of key/value pairs,
+    /// collecting the values for each key into a collection.
+    ///
+    /// The map's value type is the collection the values go into, which
+    /// can be anything with a [`Default`][Default] and an
+    /// [`Extend`][Extend] implementation, such as a
+    /// [`Vector`][Vector] or a `Vec`. Values are added to their groups in
+    /// the order the iterator produces them.
+    ///
+    /// Time: O(n log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// # use im::vector::Vector;
+    /// let words = vec!["apple", "avocado", "banana", "apricot"];
+    /// let groups: OrdMap<char, Vector<&str>> =
+    ///     OrdMap::group_by(words.into_iter().map(|word| (word.chars().next().unwrap(), word)));
+    /// assert_eq!(
+    ///     ordmap!{
+    ///         'a' => vector!["apple", "avocado", "apricot"],
+    ///         'b' => vector!["banana"]
+    ///     },
+    ///     groups
+    /// );
+    /// ```
+    ///
+    /// [Default]: https://doc.rust-lang.org/std/default/trait.Default.html
+    /// [Extend]: https://doc.rust-lang.org/std/iter/trait.Extend.html
+    /// [Vector]: ../vector/struct.Vector.html
+    #[must_use]
+    pub fn group_by<I, A>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K, A)>,
+        V: Default + Extend<A>,
+    {
+        let mut out = Self::default();
+        for (key, value) in iter {
+            out.entry(key).or_default().extend(Some(value));
+        }
+        out
+    }
+
     /// Remove a key/value mapping from a map if it exists.
     ///
-    /// Time: O(log n)
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let mut map = ordmap!{123 => "123", 456 => "456"};
+    /// map.remove(&123);
+    /// map.remove(&456);
+    /// assert!(map.is_empty());
+    /// ```
+    ///
+    /// [remove]: #method.remove
+    #[inline]
+    pub fn remove<BK>(&mut self, k: &BK) -> Option<V>
+    where
+        BK: Ord + ?Sized,
+        K: Borrow<BK>,
+    {
+        self.remove_with_key(k).map(|(_, v)| v)
+    }
+
+    /// Remove a key/value pair from a map, if it exists, and return
+    /// the removed key and value.
+    ///
+    /// Time: O(log n)
+    pub fn remove_with_key<BK>(&mut self, k: &BK) -> Option<(K, V)>
+    where
+        BK: Ord + ?Sized,
+        K: Borrow<BK>,
+    {
+        let (new_root, removed_value) = {
+            let root = PoolRef::make_mut(&self.pool.0, &mut self.root);
+            match root.remove(&self.pool.0, k) {
+                Remove::NoChange => return None,
+                Remove::Removed(pair) => {
+                    self.size -= 1;
+                    return Some(pair);
+                }
+                Remove::Update(pair, root) => (PoolRef::new(&self.pool.0, root), Some(pair)),
+            }
+        };
+        self.size -= 1;
+        self.root = new_root;
+        removed_value
+    }
+
+    /// Remove every key/value pair with a key inside a range from a
+    /// map.
+    ///
+    /// Rather than removing the keys one at a time, this splits the
+    /// tree at each end of the range and joins the pieces outside the
+    /// range back together, so only the nodes along the edges of the
+    /// range are touched.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let mut map = ordmap!{1 => 1, 2 => 2, 3 => 3, 4 => 4};
+    /// map.remove_range(..3);
+    /// assert_eq!(ordmap!{3 => 3, 4 => 4}, map);
+    /// ```
+    pub fn remove_range<R, BK>(&mut self, range: R)
+    where
+        R: RangeBounds<BK>,
+        BK: Ord + ?Sized,
+        K: Borrow<BK>,
+    {
+        if self
+            .range((range.start_bound(), range.end_bound()))
+            .next()
+            .is_none()
+        {
+            return;
+        }
+        let root = mem::replace(&mut self.root, PoolRef::default(&self.pool.0));
+        let root = PoolRef::unwrap_or_clone(root).remove_range(&self.pool.0, &range);
+        self.size = root.len();
+        self.root = PoolRef::new(&self.pool.0, root);
+    }
+
+    /// Insert a batch of key/value pairs into a map.
+    ///
+    /// The batch is sorted and built into a map of its own, which is
+    /// then combined with this one with [`union`][union], splitting and
+    /// joining their trees instead of copying a path through the tree
+    /// for every pair. If a key occurs more than once in the batch, the
+    /// last value for it wins.
+    ///
+    /// Time: O(k log k + m log (n / m)) where k is the size of the batch
+    /// and m is the size of the smaller of the batch and the map
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let mut map = ordmap!{1 => "a", 2 => "b"};
+    /// map.insert_many(vec![(3, "c"), (2, "x"), (2, "y")]);
+    /// assert_eq!(ordmap!{1 => "a", 2 => "y", 3 => "c"}, map);
+    /// ```
+    ///
+    /// [union]: #method.union
+    pub fn insert_many<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let mut batch: Vec<(K, V)> = iter.into_iter().collect();
+        batch.sort_by(|left, right| left.0.cmp(&right.0));
+        let batch = OrdMap::from_ordered(batch);
+        let map = mem::take(self);
+        *self = batch.union(map);
+    }
+
+    /// Remove a batch of keys from a map.
+    ///
+    /// The batch is sorted, and the keys which are in the map are built
+    /// into a map of their own, which is then taken away from this one
+    /// with [`relative_complement`][relative_complement], splitting and
+    /// joining their trees instead of copying a path through the tree
+    /// for every key.
+    ///
+    /// Time: O(k log k + k log n) where k is the size of the batch
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let mut map = ordmap!{1 => "a", 2 => "b", 3 => "c"};
+    /// map.remove_many(vec![3, 1, 4]);
+    /// assert_eq!(ordmap!{2 => "b"}, map);
+    /// ```
+    ///
+    /// [relative_complement]: #method.relative_complement
+    pub fn remove_many<I>(&mut self, keys: I)
+    where
+        I: IntoIterator<Item = K>,
+    {
+        let mut keys: Vec<K> = keys.into_iter().collect();
+        keys.sort();
+        let batch = OrdMap::from_ordered(keys.into_iter().filter_map(|key| {
+            let value = self.get(&key)?.clone();
+            Some((key, value))
+        }));
+        let map = mem::take(self);
+        *self = map.relative_complement(batch);
+    }
+
+    /// Construct a new map with the same keys, applying a function to
+    /// every value.
+    ///
+    /// The new map is built with the same shape as the current one,
+    /// without comparing or re-sorting any keys.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let map = ordmap!{1 => "one", 2 => "two"};
+    /// assert_eq!(ordmap!{1 => 3, 2 => 3}, map.map_values(|v| v.len()));
+    /// ```
+    #[must_use]
+    pub fn map_values<W, F>(&self, mut f: F) -> OrdMap<K, W>
+    where
+        F: FnMut(&V) -> W,
+    {
+        self.map_with_key(|_, value| f(value))
+    }
+
+    /// Construct a new map with the same keys, applying a function to
+    /// every key and value pair and using the result as the new value.
+    ///
+    /// The function is called on the pairs in ascending key order. The
+    /// new map is built with the same shape as the current one, without
+    /// comparing or re-sorting any keys.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let map = ordmap!{1 => 10, 2 => 20};
+    /// assert_eq!(ordmap!{1 => 11, 2 => 22}, map.map_with_key(|k, v| k + v));
+    /// ```
+    #[must_use]
+    pub fn map_with_key<W, F>(&self, mut f: F) -> OrdMap<K, W>
+    where
+        F: FnMut(&K, &V) -> W,
+    {
+        let pool = OrdMapPool::default();
+        let root = self
+            .root
+            .map_ordered(&pool.0, &mut |(key, value): &(K, V)| {
+                (key.clone(), f(key, value))
+            });
+        OrdMap {
+            size: self.size,
+            root: PoolRef::new(&pool.0, root),
+            pool,
+        }
+    }
+
+    /// Construct a new map with the same values, applying a function to
+    /// every key and using the result as the new key.
+    ///
+    /// If more than one key maps to the same new key, `resolve` is called
+    /// with the new key, the value collected for it so far and the next
+    /// value for it, and its result becomes the value for the new key.
+    /// Values are visited in ascending order of their original keys.
+    ///
+    /// Time: O(n log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let map = ordmap!{"A" => 1, "a" => 2, "b" => 3};
+    /// let lower = map.map_keys(|k| k.to_lowercase(), |_, v1, v2| v1 + v2);
+    /// assert_eq!(ordmap!{"a".to_string() => 3, "b".to_string() => 3}, lower);
+    /// ```
+    #[must_use]
+    pub fn map_keys<K2, F, R>(&self, mut f: F, mut resolve: R) -> OrdMap<K2, V>
+    where
+        K2: Ord + Clone,
+        F: FnMut(&K) -> K2,
+        R: FnMut(&K2, V, V) -> V,
+    {
+        let mut out = OrdMap::new();
+        for (key, value) in self.iter() {
+            let key = f(key);
+            let value = match out.remove(&key) {
+                None => value.clone(),
+                Some(previous) => resolve(&key, previous, value.clone()),
+            };
+            out.insert(key, value);
+        }
+        out
+    }
+
+    /// Filter out values from a map which don't satisfy a predicate.
+    ///
+    /// If the map isn't shared with any other map, the values are
+    /// removed in place, without reconstructing the tree.
+    ///
+    /// Time: O(n + m log n) where m is the number of removed values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let mut map = ordmap!{1 => 1, 2 => 2, 3 => 3};
+    /// map.retain(|k, v| *k > 1);
+    /// assert_eq!(ordmap!{2 => 2, 3 => 3}, map);
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        let removed: Vec<K> = self
+            .iter()
+            .filter(|(key, value)| !f(key, value))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in removed {
+            self.remove(&key);
+        }
+    }
+
+    /// Remove the key/value pairs which satisfy a predicate from a map,
+    /// and return them in a new map.
+    ///
+    /// Like [`retain`][retain], this removes the pairs in place if the
+    /// map isn't shared with any other map.
+    ///
+    /// Time: O(n + m log n) where m is the number of removed values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let mut map = ordmap!{1 => 1, 2 => 2, 3 => 3};
+    /// let removed = map.drain_filter(|k, v| *k > 1);
+    /// assert_eq!(ordmap!{1 => 1}, map);
+    /// assert_eq!(ordmap!{2 => 2, 3 => 3}, removed);
+    /// ```
+    ///
+    /// [retain]: #method.retain
+    pub fn drain_filter<F>(&mut self, mut f: F) -> Self
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        let mut removed = OrdMap::new();
+        self.retain(|key, value| {
+            if f(key, value) {
+                removed.insert(key.clone(), value.clone());
+                false
+            } else {
+                true
+            }
+        });
+        removed
+    }
+
+    /// Construct a new map containing only the key/value pairs of the
+    /// current map which satisfy a predicate.
+    ///
+    /// The new map shares the parts of its tree which no pairs were
+    /// removed from with the current map.
+    ///
+    /// Time: O(n + m log n) where m is the number of removed pairs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let map = ordmap!{1 => 1, 2 => 2, 3 => 3};
+    /// assert_eq!(ordmap!{2 => 2, 3 => 3}, map.filter(|k, v| *k > 1));
+    /// assert_eq!(3, map.len());
+    /// ```
+    #[must_use]
+    pub fn filter<F>(&self, f: F) -> Self
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        let mut out = self.clone();
+        out.retain(f);
+        out
+    }
+
+    /// Split a map into two maps, the first containing the key/value
+    /// pairs which satisfy a predicate and the second containing the
+    /// pairs which don't.
+    ///
+    /// This visits each pair once, and the second map shares the parts
+    /// of its tree which no pairs were removed from with the current
+    /// map.
+    ///
+    /// Time: O(n + m log n) where m is the number of pairs satisfying
+    /// the predicate
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let map = ordmap!{1 => 1, 2 => 2, 3 => 3};
+    /// let (big, small) = map.partition(|k, v| *k > 1);
+    /// assert_eq!(ordmap!{2 => 2, 3 => 3}, big);
+    /// assert_eq!(ordmap!{1 => 1}, small);
+    /// ```
+    #[must_use]
+    pub fn partition<F>(&self, f: F) -> (Self, Self)
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        let mut failed = self.clone();
+        let passed = failed.drain_filter(f);
+        (passed, failed)
+    }
+
+    /// Construct a new map containing only the key/value pairs whose
+    /// keys are in a set.
+    ///
+    /// If the set is much smaller than the map, its keys are looked up
+    /// in the map, and if the map is much smaller than the set, its keys
+    /// are looked up in the set. Otherwise, the map and the set are
+    /// walked side by side in key order. Either way, the new map is
+    /// built directly from the pairs it keeps.
+    ///
+    /// Time: O(min(m log n, n + m)) where m is the size of the smaller
+    /// collection
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let map = ordmap!{1 => "a", 2 => "b", 3 => "c"};
+    /// assert_eq!(ordmap!{1 => "a", 3 => "c"}, map.restrict_keys(&ordset!{1, 3, 5}));
+    /// ```
+    #[must_use]
+    pub fn restrict_keys(&self, keys: &OrdSet<K>) -> Self {
+        if prefer_point_updates(keys.len(), self.len()) {
+            OrdMap::from_ordered(
+                keys.iter()
+                    .filter_map(|key| self.get_key_value(key))
+                    .map(|(key, value)| (key.clone(), value.clone())),
+            )
+        } else if prefer_point_updates(self.len(), keys.len()) {
+            OrdMap::from_ordered(
+                self.iter()
+                    .filter(|(key, _)| keys.contains(key))
+                    .map(|(key, value)| (key.clone(), value.clone())),
+            )
+        } else {
+            self.filter_by_keys(keys, true)
+        }
+    }
+
+    /// Construct a new map without the key/value pairs whose keys are in
+    /// a set.
+    ///
+    /// If the set is much smaller than the map, its keys are removed from
+    /// a copy of the map, which keeps sharing the parts of its tree they
+    /// don't touch. If the map is much smaller than the set, its keys are
+    /// looked up in the set. Otherwise, the map and the set are walked
+    /// side by side in key order into a new tree.
+    ///
+    /// Time: O(min(m log n, n + m)) where m is the size of the smaller
+    /// collection
     ///
     /// # Examples
     ///
     /// ```
     /// # #[macro_use] extern crate im;
     /// # use im::ordmap::OrdMap;
-    /// let mut map = ordmap!{123 => "123", 456 => "456"};
-    /// map.remove(&123);
-    /// map.remove(&456);
-    /// assert!(map.is_empty());
+    /// let map = ordmap!{1 => "a", 2 => "b", 3 => "c"};
+    /// assert_eq!(ordmap!{2 => "b"}, map.without_keys(&ordset!{1, 3, 5}));
     /// ```
-    ///
-    /// [remove]: #method.remove
-    #[inline]
-    pub fn remove<BK>(&mut self, k: &BK) -> Option<V>
-    where
-        BK: Ord + ?Sized,
-        K: Borrow<BK>,
-    {
-        self.remove_with_key(k).map(|(_, v)| v)
+    #[must_use]
+    pub fn without_keys(&self, keys: &OrdSet<K>) -> Self {
+        if prefer_point_updates(keys.len(), self.len()) {
+            let mut out = self.clone();
+            for key in keys {
+                out.remove(key);
+            }
+            out
+        } else if prefer_point_updates(self.len(), keys.len()) {
+            self.filter(|key, _| !keys.contains(key))
+        } else {
+            self.filter_by_keys(keys, false)
+        }
     }
 
-    /// Remove a key/value pair from a map, if it exists, and return
-    /// the removed key and value.
-    ///
-    /// Time: O(log n)
-    pub fn remove_with_key<BK>(&mut self, k: &BK) -> Option<(K, V)>
-    where
-        BK: Ord + ?Sized,
-        K: Borrow<BK>,
-    {
-        let (new_root, removed_value) = {
-            let root = PoolRef::make_mut(&self.pool.0, &mut self.root);
-            match root.remove(&self.pool.0, k) {
-                Remove::NoChange => return None,
-                Remove::Removed(pair) => {
-                    self.size -= 1;
-                    return Some(pair);
-                }
-                Remove::Update(pair, root) => (PoolRef::new(&self.pool.0, root), Some(pair)),
-            }
-        };
-        self.size -= 1;
-        self.root = new_root;
-        removed_value
+    // Walk the map and the set in key order, keeping the pairs whose
+    // keys are in the set if `present` is true, or not in it otherwise.
+    fn filter_by_keys(&self, keys: &OrdSet<K>, present: bool) -> Self {
+        OrdMap::from_ordered(
+            MergeIter::new(self.iter(), keys.iter(), |(key, _), other| {
+                (*key).cmp(other)
+            })
+            .filter_map(|item| match item {
+                Merged::Left((key, value)) if !present => Some((key.clone(), value.clone())),
+                Merged::Both((key, value), _) if present => Some((key.clone(), value.clone())),
+                _ => None,
+            }),
+        )
     }
 
     /// Construct a new map by inserting a key/value mapping into a
@@ -835,6 +2042,49 @@ where
         out
     }
 
+    /// Construct a new map by inserting a key/value mapping into a
+    /// map, failing if the map already has a mapping for the key.
+    ///
+    /// Unlike [`update()`][update], this never overwrites an existing
+    /// value. If the key is already in the map, you get back an
+    /// [`OccupiedError`][OccupiedError] holding the key and value you
+    /// tried to insert along with the existing value.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::{OrdMap, OccupiedError};
+    /// let map = ordmap!{123 => "123"};
+    /// assert_eq!(
+    ///     Ok(ordmap!{123 => "123", 456 => "456"}),
+    ///     map.try_insert(456, "456")
+    /// );
+    /// assert_eq!(
+    ///     Err(OccupiedError {
+    ///         key: 123,
+    ///         value: "new",
+    ///         existing: "123"
+    ///     }),
+    ///     map.try_insert(123, "new")
+    /// );
+    /// ```
+    ///
+    /// [update]: #method.update
+    /// [OccupiedError]: ./struct.OccupiedError.html
+    pub fn try_insert(&self, key: K, value: V) -> Result<Self, OccupiedError<K, V>> {
+        match self.get(&key) {
+            Some(existing) => Err(OccupiedError {
+                key,
+                value,
+                existing: existing.clone(),
+            }),
+            None => Ok(self.update(key, value)),
+        }
+    }
+
     /// Construct a new map by inserting a key/value mapping into a
     /// map.
     ///
@@ -907,6 +2157,18 @@ where
     ///
     /// Time: O(log n)
     ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let count = |n: Option<i32>| Some(n.map_or(1, |n| n + 1));
+    /// let map = ordmap!{"a" => 1};
+    /// assert_eq!(ordmap!{"a" => 2}, map.alter(count, "a"));
+    /// assert_eq!(ordmap!{"a" => 1, "b" => 1}, map.alter(count, "b"));
+    /// assert_eq!(ordmap!{}, map.alter(|_| None, "a"));
+    /// ```
+    ///
     /// [std::option::Option]: https://doc.rust-lang.org/std/option/enum.Option.html
     #[must_use]
     pub fn alter<F>(&self, f: F, k: K) -> Self
@@ -922,6 +2184,67 @@ where
         }
     }
 
+    /// Construct a new map with the value for a given key replaced by
+    /// the result of calling a function on it, or a copy of the map if
+    /// the key isn't in it.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let map = ordmap!{"a" => 1};
+    /// assert_eq!(ordmap!{"a" => 11}, map.adjust("a", |v| v + 10));
+    /// assert_eq!(map, map.adjust("b", |v| v + 10));
+    /// ```
+    #[must_use]
+    pub fn adjust<BK, F>(&self, key: &BK, f: F) -> Self
+    where
+        BK: Ord + ?Sized,
+        K: Borrow<BK>,
+        F: FnOnce(&V) -> V,
+    {
+        self.adjust_with_key(key, |_, value| f(value))
+    }
+
+    /// Construct a new map with the value for a given key replaced by
+    /// the result of calling a function on the key and the value, or a
+    /// copy of the map sharing all of its nodes if the key isn't in it.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let map = ordmap!{"a" => 1};
+    /// assert_eq!(
+    ///     ordmap!{"a" => 2},
+    ///     map.adjust_with_key("a", |k, v| v + k.len())
+    /// );
+    /// ```
+    #[must_use]
+    pub fn adjust_with_key<BK, F>(&self, key: &BK, f: F) -> Self
+    where
+        BK: Ord + ?Sized,
+        K: Borrow<BK>,
+        F: FnOnce(&K, &V) -> V,
+    {
+        let mut out = self.clone();
+        // Don't copy the path to a key that isn't there.
+        if !self.contains_key(key) {
+            return out;
+        }
+        let root = PoolRef::make_mut(&out.pool.0, &mut out.root);
+        if let Some((key, value)) = root.lookup_mut(&out.pool.0, key) {
+            *value = f(key, value);
+        }
+        out
+    }
+
     /// Remove a key/value pair from a map, if it exists.
     ///
     /// Time: O(log n)
@@ -937,9 +2260,22 @@ where
     }
 
     /// Remove a key/value pair from a map, if it exists, and return
-    /// the removed value as well as the updated list.
+    /// the removed value as well as the updated map.
+    ///
+    /// The key is looked up and removed in a single walk down the
+    /// tree.
     ///
     /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let map = ordmap!{1 => "one", 2 => "two"};
+    /// assert_eq!(Some(("one", ordmap!{2 => "two"})), map.extract(&1));
+    /// assert_eq!(None, map.extract(&3));
+    /// ```
     #[must_use]
     pub fn extract<BK>(&self, k: &BK) -> Option<(V, Self)>
     where
@@ -950,9 +2286,21 @@ where
     }
 
     /// Remove a key/value pair from a map, if it exists, and return
-    /// the removed key and value as well as the updated list.
+    /// the removed key and value as well as the updated map.
     ///
     /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let map = ordmap!{"one".to_string() => 1, "two".to_string() => 2};
+    /// assert_eq!(
+    ///     Some(("one".to_string(), 1, ordmap!{"two".to_string() => 2})),
+    ///     map.extract_with_key("one")
+    /// );
+    /// ```
     #[must_use]
     pub fn extract_with_key<BK>(&self, k: &BK) -> Option<(K, V, Self)>
     where
@@ -967,7 +2315,11 @@ where
     /// Construct the union of two maps, keeping the values in the
     /// current map when keys exist in both maps.
     ///
-    /// Time: O(n log n)
+    /// The trees are combined by splitting and joining them, which
+    /// leaves the parts of the larger tree the smaller one doesn't reach
+    /// into shared with the result.
+    ///
+    /// Time: O(m log (n / m)) where m is the size of the smaller map
     ///
     /// # Examples
     ///
@@ -981,11 +2333,46 @@ where
     /// ```
     #[inline]
     #[must_use]
-    pub fn union(mut self, other: Self) -> Self {
-        for (k, v) in other {
-            self.entry(k).or_insert(v);
-        }
-        self
+    pub fn union(self, other: Self) -> Self {
+        self.union_with_key(other, |_, left, _| left)
+    }
+
+    /// Merge two maps deeply, recursing into values present in both
+    /// maps rather than replacing one with the other.
+    ///
+    /// Values present in only one of the maps are kept as is. When a
+    /// key is present in both, the two values are combined using
+    /// their [`Merge`][Merge] implementation, which for nested maps
+    /// means merging them in turn, and for leaf values means picking
+    /// one according to `strategy`.
+    ///
+    /// Time: O(n log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// # use im::merge::MergeStrategy;
+    /// let defaults = ordmap!{"a" => ordmap!{"x" => 1, "y" => 2}};
+    /// let file = ordmap!{"a" => ordmap!{"y" => 3}, "b" => ordmap!{"z" => 4}};
+    /// assert_eq!(
+    ///     ordmap!{"a" => ordmap!{"x" => 1, "y" => 3}, "b" => ordmap!{"z" => 4}},
+    ///     defaults.merge_deep(&file, MergeStrategy::PreferRight)
+    /// );
+    /// assert_eq!(
+    ///     ordmap!{"a" => ordmap!{"x" => 1, "y" => 2}, "b" => ordmap!{"z" => 4}},
+    ///     defaults.merge_deep(&file, MergeStrategy::PreferLeft)
+    /// );
+    /// ```
+    ///
+    /// [Merge]: ../merge/trait.Merge.html
+    #[must_use]
+    pub fn merge_deep(&self, other: &Self, strategy: MergeStrategy) -> Self
+    where
+        V: Merge,
+    {
+        self.clone().merge(other.clone(), strategy)
     }
 
     /// Construct the union of two maps, using a function to decide
@@ -1015,7 +2402,9 @@ where
     /// value from the other map as the third argument. It should
     /// return the value to be inserted in the resulting map.
     ///
-    /// Time: O(n log n)
+    /// See [`union`][union] for how the maps are combined.
+    ///
+    /// Time: O(m log (n / m)) where m is the size of the smaller map
     ///
     /// # Examples
     ///
@@ -1030,23 +2419,21 @@ where
     ///     |key, left, right| left + right
     /// ));
     /// ```
+    ///
+    /// [union]: #method.union
     #[must_use]
-    pub fn union_with_key<F>(mut self, other: Self, mut f: F) -> Self
+    pub fn union_with_key<F>(self, other: Self, mut f: F) -> Self
     where
         F: FnMut(&K, V, V) -> V,
     {
-        for (key, right_value) in other {
-            match self.remove(&key) {
-                None => {
-                    self.insert(key, right_value);
-                }
-                Some(left_value) => {
-                    let final_value = f(&key, left_value, right_value);
-                    self.insert(key, final_value);
-                }
-            }
-        }
-        self
+        self.combine(
+            other,
+            (true, true),
+            |(key, left_value), (_, right_value)| {
+                let value = f(&key, left_value, right_value);
+                Some((key, value))
+            },
+        )
     }
 
     /// Construct the union of a sequence of maps, selecting the value
@@ -1227,7 +2614,10 @@ where
     /// to decide what to do if a key occurs in both. The function
     /// receives the key as well as both values.
     ///
-    /// Time: O(n log n)
+    /// The trees are combined by splitting and joining them, like
+    /// [`union`][union] does.
+    ///
+    /// Time: O(m log (n / m)) where m is the size of the smaller map
     ///
     /// # Examples
     ///
@@ -1242,31 +2632,29 @@ where
     ///     |key, left, right| Some(left + right)
     /// ));
     /// ```
+    ///
+    /// [union]: #method.union
     #[must_use]
-    pub fn symmetric_difference_with_key<F>(mut self, other: Self, mut f: F) -> Self
+    pub fn symmetric_difference_with_key<F>(self, other: Self, mut f: F) -> Self
     where
         F: FnMut(&K, V, V) -> Option<V>,
     {
-        let mut out = Self::default();
-        for (key, right_value) in other {
-            match self.remove(&key) {
-                None => {
-                    out.insert(key, right_value);
-                }
-                Some(left_value) => {
-                    if let Some(final_value) = f(&key, left_value, right_value) {
-                        out.insert(key, final_value);
-                    }
-                }
-            }
-        }
-        out.union(self)
+        self.combine(
+            other,
+            (true, true),
+            |(key, left_value), (_, right_value)| {
+                f(&key, left_value, right_value).map(|value| (key, value))
+            },
+        )
     }
 
     /// Construct the relative complement between two maps by discarding keys
     /// which occur in `other`.
     ///
-    /// Time: O(m log n) where m is the size of the other map
+    /// The trees are combined by splitting and joining them, like
+    /// [`union`][union] does.
+    ///
+    /// Time: O(m log (n / m)) where m is the size of the smaller map
     ///
     /// # Examples
     ///
@@ -1278,19 +2666,21 @@ where
     /// let expected = ordmap!{1 => 1};
     /// assert_eq!(expected, map1.relative_complement(map2));
     /// ```
+    ///
+    /// [union]: #method.union
     #[inline]
     #[must_use]
-    pub fn relative_complement(mut self, other: Self) -> Self {
-        for (key, _) in other {
-            let _ = self.remove(&key);
-        }
-        self
+    pub fn relative_complement(self, other: Self) -> Self {
+        self.combine(other, (true, false), |_, _| None)
     }
 
     /// Construct the intersection of two maps, keeping the values
     /// from the current map.
     ///
-    /// Time: O(n log n)
+    /// The trees are combined by splitting and joining them, like
+    /// [`union`][union] does.
+    ///
+    /// Time: O(m log (n / m)) where m is the size of the smaller map
     ///
     /// # Examples
     ///
@@ -1302,17 +2692,19 @@ where
     /// let expected = ordmap!{2 => 2};
     /// assert_eq!(expected, map1.intersection(map2));
     /// ```
+    ///
+    /// [union]: #method.union
     #[inline]
     #[must_use]
     pub fn intersection(self, other: Self) -> Self {
-        self.intersection_with_key(other, |_, v, _| v)
+        self.combine(other, (false, false), |left, _| Some(left))
     }
 
     /// Construct the intersection of two maps, calling a function
     /// with both values for each key and using the result as the
     /// value for the key.
     ///
-    /// Time: O(n log n)
+    /// Time: O(min(m log n, n + m)) where m is the size of the smaller map
     #[inline]
     #[must_use]
     pub fn intersection_with<B, C, F>(self, other: OrdMap<K, B>, mut f: F) -> OrdMap<K, C>
@@ -1328,7 +2720,12 @@ where
     /// with the key and both values for each key and using the result
     /// as the value for the key.
     ///
-    /// Time: O(n log n)
+    /// The result can hold a different type of value, so it's built in a
+    /// new tree: if one map is much smaller than the other, its keys are
+    /// looked up in the larger one, otherwise both maps are merged in
+    /// order.
+    ///
+    /// Time: O(min(m log n, n + m)) where m is the size of the smaller map
     ///
     /// # Examples
     ///
@@ -1344,23 +2741,133 @@ where
     /// ));
     /// ```
     #[must_use]
-    pub fn intersection_with_key<B, C, F>(mut self, other: OrdMap<K, B>, mut f: F) -> OrdMap<K, C>
+    pub fn intersection_with_key<B, C, F>(
+        mut self,
+        mut other: OrdMap<K, B>,
+        mut f: F,
+    ) -> OrdMap<K, C>
     where
         B: Clone,
         C: Clone,
         F: FnMut(&K, V, B) -> C,
     {
-        let mut out = OrdMap::<K, C>::default();
-        for (key, right_value) in other {
-            match self.remove(&key) {
-                None => (),
-                Some(left_value) => {
+        if self.len() >= other.len() && prefer_point_updates(other.len(), self.len()) {
+            let mut out = OrdMap::<K, C>::default();
+            for (key, right_value) in other {
+                if let Some(left_value) = self.remove(&key) {
+                    let result = f(&key, left_value, right_value);
+                    out.insert(key, result);
+                }
+            }
+            out
+        } else if prefer_point_updates(self.len(), other.len()) {
+            let mut out = OrdMap::<K, C>::default();
+            for (key, left_value) in self {
+                if let Some(right_value) = other.remove(&key) {
                     let result = f(&key, left_value, right_value);
                     out.insert(key, result);
                 }
             }
+            out
+        } else {
+            self.merge_with_key(
+                other,
+                |key, left_value, right_value| Some(f(key, left_value, right_value)),
+                |_, _| None,
+                |_, _| None,
+            )
+        }
+    }
+
+    /// Merge two maps, deciding what to do with each key with one of
+    /// three functions, depending on whether the key is in both maps,
+    /// only in the current map, or only in the other map.
+    ///
+    /// Each function receives the key and the value or values for it,
+    /// and returns the value for the key in the resulting map, or `None`
+    /// to leave the key out. The more specific operations, like
+    /// [`union_with_key`][union_with_key],
+    /// [`intersection_with_key`][intersection_with_key] and
+    /// [`relative_complement`][relative_complement], can all be
+    /// expressed in terms of this one.
+    ///
+    /// Both maps are walked side by side in key order with
+    /// [`zip_merge`][zip_merge], and the result is built directly from
+    /// the merged entries.
+    ///
+    /// Time: O(n + m)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let ours = ordmap!{"a" => 1, "b" => 2, "c" => 3};
+    /// let theirs = ordmap!{"b" => 20, "c" => 30, "d" => 40};
+    /// let merged = ours.merge_with_key(
+    ///     theirs,
+    ///     |_, ours, theirs| if ours == theirs / 10 { Some(theirs) } else { None },
+    ///     |_, ours| Some(ours),
+    ///     |_, theirs| Some(theirs),
+    /// );
+    /// assert_eq!(ordmap!{"a" => 1, "b" => 20, "c" => 30, "d" => 40}, merged);
+    /// ```
+    ///
+    /// [union_with_key]: #method.union_with_key
+    /// [intersection_with_key]: #method.intersection_with_key
+    /// [relative_complement]: #method.relative_complement
+    /// [zip_merge]: #method.zip_merge
+    #[must_use]
+    pub fn merge_with_key<B, C, FB, FL, FR>(
+        self,
+        other: OrdMap<K, B>,
+        mut both: FB,
+        mut left_only: FL,
+        mut right_only: FR,
+    ) -> OrdMap<K, C>
+    where
+        B: Clone,
+        C: Clone,
+        FB: FnMut(&K, V, B) -> Option<C>,
+        FL: FnMut(&K, V) -> Option<C>,
+        FR: FnMut(&K, B) -> Option<C>,
+    {
+        let merged = self.zip_merge(&other).filter_map(|item| match item {
+            MergeItem::Left(key, value) => {
+                left_only(key, value.clone()).map(|value| (key.clone(), value))
+            }
+            MergeItem::Right(key, value) => {
+                right_only(key, value.clone()).map(|value| (key.clone(), value))
+            }
+            MergeItem::Both(key, left, right) => {
+                both(key, left.clone(), right.clone()).map(|value| (key.clone(), value))
+            }
+        });
+        OrdMap::from_ordered(merged)
+    }
+
+    // Combine the trees of two maps, keeping the entries only found in
+    // `self` or `other` according to `keep`, and calling `f` for keys
+    // found in both.
+    fn combine<F>(self, other: Self, keep: (bool, bool), mut f: F) -> Self
+    where
+        F: FnMut((K, V), (K, V)) -> Option<(K, V)>,
+    {
+        let pool = self.pool.clone();
+        let (root, size) = Node::combine(
+            &pool.0,
+            PoolRef::unwrap_or_clone(self.root),
+            self.size,
+            PoolRef::unwrap_or_clone(other.root),
+            other.size,
+            keep,
+            &mut f,
+        );
+        OrdMap {
+            size,
+            root: PoolRef::new(&pool.0, root),
+            pool,
         }
-        out
     }
 
     /// Split a map into two, with the left hand map containing keys
@@ -1400,24 +2907,68 @@ where
             })
     }
 
+    /// Split a map into two, with the left hand map containing the `n`
+    /// smallest keys and the right hand map containing the rest.
+    ///
+    /// This is the same as calling [`take`][take] and [`skip`][skip]:
+    /// the key at position `n` is found through the counts kept in the
+    /// nodes of the tree, and the tree is split at that key, so both
+    /// maps share their nodes with the original map, apart from those
+    /// along the point where it was split.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordmap::OrdMap;
+    /// let map = ordmap!{1 => "a", 2 => "b", 3 => "c"};
+    /// let (left, right) = map.split_at(1);
+    /// assert_eq!(ordmap!{1 => "a"}, left);
+    /// assert_eq!(ordmap!{2 => "b", 3 => "c"}, right);
+    /// ```
+    ///
+    /// [take]: #method.take
+    /// [skip]: #method.skip
+    #[must_use]
+    pub fn split_at(&self, n: usize) -> (Self, Self) {
+        (self.take(n), self.skip(n))
+    }
+
     /// Construct a map with only the `n` smallest keys from a given
     /// map.
+    ///
+    /// The new map shares its nodes with the original map, apart from
+    /// those along the point where it was cut off.
+    ///
+    /// Time: O(log n)
     #[must_use]
     pub fn take(&self, n: usize) -> Self {
-        self.iter()
-            .take(n)
-            .map(|(k, v)| (k.clone(), v.clone()))
-            .collect()
+        let mut out = self.clone();
+        if let Some((boundary, _)) = self.get_index(n) {
+            out.remove_range(boundary..);
+        }
+        out
     }
 
     /// Construct a map with the `n` smallest keys removed from a
     /// given map.
+    ///
+    /// The new map shares its nodes with the original map, apart from
+    /// those along the point where it was cut off.
+    ///
+    /// Time: O(log n)
     #[must_use]
     pub fn skip(&self, n: usize) -> Self {
-        self.iter()
-            .skip(n)
-            .map(|(k, v)| (k.clone(), v.clone()))
-            .collect()
+        match self.get_index(n) {
+            None => Self::new(),
+            Some((boundary, _)) => {
+                let mut out = self.clone();
+                out.remove_range(..boundary);
+                out
+            }
+        }
     }
 
     /// Remove the smallest key from a map, and return its value as
@@ -1689,8 +3240,29 @@ where
     }
 }
 
-impl<K: Ord + Eq, V: Eq> Eq for OrdMap<K, V> {}
-
+impl<K: Ord + Eq, V: Eq> Eq for OrdMap<K, V> {}
+
+impl<K, V> PartialEq<collections::BTreeMap<K, V>> for OrdMap<K, V>
+where
+    K: Ord,
+    V: PartialEq,
+{
+    fn eq(&self, other: &collections::BTreeMap<K, V>) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<K, V, S> PartialEq<collections::HashMap<K, V, S>> for OrdMap<K, V>
+where
+    K: Ord + Hash + Eq,
+    V: PartialEq,
+    S: BuildHasher,
+{
+    fn eq(&self, other: &collections::HashMap<K, V, S>) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
 impl<K, V> PartialOrd for OrdMap<K, V>
 where
     K: Ord,
@@ -1756,6 +3328,17 @@ where
     }
 }
 
+impl<K, V> Merge for OrdMap<K, V>
+where
+    K: Ord + Clone,
+    V: Merge + Clone,
+{
+    /// Merge two maps, merging the values of keys present in both.
+    fn merge(self, other: Self, strategy: MergeStrategy) -> Self {
+        self.union_with(other, |left, right| left.merge(right, strategy))
+    }
+}
+
 impl<K, V> Sum for OrdMap<K, V>
 where
     K: Ord + Clone,
@@ -1828,6 +3411,120 @@ where
     }
 }
 
+// Reversed view
+
+/// A view of an [`OrdMap`][OrdMap] in descending key order.
+///
+/// This is created by [`OrdMap::reversed()`][reversed].
+///
+/// [OrdMap]: ./struct.OrdMap.html
+/// [reversed]: ./struct.OrdMap.html#method.reversed
+pub struct Reversed<'a, K, V> {
+    map: &'a OrdMap<K, V>,
+}
+
+impl<'a, K, V> Reversed<'a, K, V>
+where
+    K: Ord,
+{
+    /// Get the map this is a view of.
+    #[must_use]
+    pub fn as_map(&self) -> &'a OrdMap<K, V> {
+        self.map
+    }
+
+    /// Test whether the map is empty.
+    ///
+    /// Time: O(1)
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Get the size of the map.
+    ///
+    /// Time: O(1)
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Get the first key in descending order, which is the largest key
+    /// in the map, along with its value.
+    ///
+    /// Time: O(log n)
+    #[must_use]
+    pub fn get_min(&self) -> Option<&'a (K, V)> {
+        self.map.get_max()
+    }
+
+    /// Get the last key in descending order, which is the smallest key
+    /// in the map, along with its value.
+    ///
+    /// Time: O(log n)
+    #[must_use]
+    pub fn get_max(&self) -> Option<&'a (K, V)> {
+        self.map.get_min()
+    }
+
+    /// Get an iterator over the key/value pairs of the map in
+    /// descending key order.
+    pub fn iter(&self) -> Rev<Iter<'a, K, V>> {
+        self.map.iter().rev()
+    }
+
+    /// Get an iterator over a range of key/value pairs in descending
+    /// key order.
+    pub fn range<R, BK>(&self, range: R) -> Rev<Iter<'a, K, V>>
+    where
+        R: RangeBounds<BK>,
+        K: Borrow<BK>,
+        BK: Ord + ?Sized,
+    {
+        self.map.range(range).rev()
+    }
+
+    /// Get an iterator over the map's keys in descending order.
+    pub fn keys(&self) -> Rev<Keys<'a, K, V>> {
+        self.map.keys().rev()
+    }
+
+    /// Get an iterator over the map's values in descending key order.
+    pub fn values(&self) -> Rev<Values<'a, K, V>> {
+        self.map.values().rev()
+    }
+}
+
+impl<'a, K, V> Clone for Reversed<'a, K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, K, V> Copy for Reversed<'a, K, V> {}
+
+impl<'a, K, V> Debug for Reversed<'a, K, V>
+where
+    K: Ord + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, K, V> IntoIterator for Reversed<'a, K, V>
+where
+    K: Ord,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Rev<Iter<'a, K, V>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 // Iterators
 
 /// An iterator over the key/value pairs of a map.
@@ -1861,6 +3558,47 @@ where
 
 impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> where (K, V): 'a + BTreeValue {}
 
+/// An iterator over clones of the key/value pairs of a map.
+pub struct ClonedIter<'a, K, V> {
+    it: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for ClonedIter<'a, K, V>
+where
+    (K, V): 'a + BTreeValue,
+    K: Clone,
+    V: Clone,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.it.next().map(|(k, v)| (k.clone(), v.clone()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for ClonedIter<'a, K, V>
+where
+    (K, V): 'a + BTreeValue,
+    K: Clone,
+    V: Clone,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.it.next_back().map(|(k, v)| (k.clone(), v.clone()))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for ClonedIter<'a, K, V>
+where
+    (K, V): 'a + BTreeValue,
+    K: Clone,
+    V: Clone,
+{
+}
+
 /// An iterator over the differences between two maps.
 pub struct DiffIter<'a, K, V> {
     it: NodeDiffIter<'a, (K, V)>,
@@ -1903,6 +3641,62 @@ where
     }
 }
 
+/// An iterator over two maps side by side in key order, created by
+/// [`OrdMap::zip_merge()`][zip_merge].
+///
+/// [zip_merge]: ./struct.OrdMap.html#method.zip_merge
+pub struct ZipMergeIter<'a, K, V, W> {
+    it: MergeIter<
+        Iter<'a, K, V>,
+        Iter<'a, K, W>,
+        (&'a K, &'a V),
+        (&'a K, &'a W),
+        fn(&(&'a K, &'a V), &(&'a K, &'a W)) -> Ordering,
+    >,
+}
+
+/// A key from one or both of two maps walked side by side, along with its
+/// values.
+#[derive(PartialEq, Eq, Debug)]
+pub enum MergeItem<'a, K, V, W> {
+    /// The key is only in the left hand map.
+    Left(&'a K, &'a V),
+    /// The key is only in the right hand map.
+    Right(&'a K, &'a W),
+    /// The key is in both maps.
+    Both(&'a K, &'a V, &'a W),
+}
+
+impl<'a, K, V, W> Iterator for ZipMergeIter<'a, K, V, W>
+where
+    (K, V): 'a + BTreeValue,
+    (K, W): 'a + BTreeValue,
+    K: Ord,
+{
+    type Item = MergeItem<'a, K, V, W>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(match self.it.next()? {
+            Merged::Left((key, value)) => MergeItem::Left(key, value),
+            Merged::Right((key, value)) => MergeItem::Right(key, value),
+            Merged::Both((key, left), (_, right)) => MergeItem::Both(key, left, right),
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (left, right) = self.it.lens();
+        (left.max(right), Some(left + right))
+    }
+}
+
+impl<'a, K, V, W> FusedIterator for ZipMergeIter<'a, K, V, W>
+where
+    (K, V): 'a + BTreeValue,
+    (K, W): 'a + BTreeValue,
+    K: Ord,
+{
+}
+
 /// An iterator ove the keys of a map.
 pub struct Keys<'a, K, V> {
     it: Iter<'a, K, V>,
@@ -1990,6 +3784,13 @@ where
     K: Ord + Clone + From<RK>,
     V: Clone + From<RV>,
 {
+    /// Collect key/value pairs into a map.
+    ///
+    /// If a key occurs more than once, the last value for it wins. Use
+    /// [`OrdMap::from_iter_with()`][from_iter_with] to choose what
+    /// happens instead.
+    ///
+    /// [from_iter_with]: ./struct.OrdMap.html#method.from_iter_with
     fn from_iter<T>(i: T) -> Self
     where
         T: IntoIterator<Item = (RK, RV)>,
@@ -2071,7 +3872,7 @@ where
     V: Clone + From<RV>,
 {
     fn from(m: Vec<(RK, RV)>) -> OrdMap<K, V> {
-        m.into_iter().collect()
+        OrdMap::from_ordered(m.into_iter().map(|(k, v)| (K::from(k), V::from(v))))
     }
 }
 
@@ -2085,9 +3886,10 @@ where
     RV: ToOwned<Owned = OV>,
 {
     fn from(m: &'a Vec<(RK, RV)>) -> OrdMap<K, V> {
-        m.iter()
-            .map(|&(ref k, ref v)| (k.to_owned(), v.to_owned()))
-            .collect()
+        OrdMap::from_ordered(
+            m.iter()
+                .map(|&(ref k, ref v)| (K::from(k.to_owned()), V::from(v.to_owned()))),
+        )
     }
 }
 
@@ -2123,7 +3925,7 @@ where
     V: Clone + From<RV>,
 {
     fn from(m: collections::BTreeMap<RK, RV>) -> OrdMap<K, V> {
-        m.into_iter().collect()
+        OrdMap::from_ordered(m.into_iter().map(|(k, v)| (K::from(k), V::from(v))))
     }
 }
 
@@ -2137,9 +3939,10 @@ where
     RV: ToOwned<Owned = OV>,
 {
     fn from(m: &'a collections::BTreeMap<RK, RV>) -> OrdMap<K, V> {
-        m.iter()
-            .map(|(k, v)| (k.to_owned(), v.to_owned()))
-            .collect()
+        OrdMap::from_ordered(
+            m.iter()
+                .map(|(k, v)| (K::from(k.to_owned()), V::from(v.to_owned()))),
+        )
     }
 }
 
@@ -2157,6 +3960,58 @@ impl<'a, K: Ord + Hash + Eq + Clone, V: Clone, S: BuildHasher> From<&'a HashMap<
     }
 }
 
+impl<K: Ord + Clone, V: Clone> From<OrdMap<K, V>> for Vec<(K, V)> {
+    /// Create a [`std::vec::Vec`][vec] from a map, with the key/value
+    /// pairs in ascending key order.
+    ///
+    /// Time: O(n)
+    ///
+    /// [vec]: https://doc.rust-lang.org/std/vec/struct.Vec.html
+    fn from(map: OrdMap<K, V>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl<K: Ord + Clone, V: Clone> From<&OrdMap<K, V>> for Vec<(K, V)> {
+    fn from(map: &OrdMap<K, V>) -> Self {
+        map.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+    }
+}
+
+impl<K: Ord + Clone, V: Clone> From<OrdMap<K, V>> for collections::BTreeMap<K, V> {
+    fn from(map: OrdMap<K, V>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl<K: Ord + Clone, V: Clone> From<&OrdMap<K, V>> for collections::BTreeMap<K, V> {
+    fn from(map: &OrdMap<K, V>) -> Self {
+        map.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+    }
+}
+
+impl<K, V, S> From<OrdMap<K, V>> for collections::HashMap<K, V, S>
+where
+    K: Ord + Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Default,
+{
+    fn from(map: OrdMap<K, V>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl<K, V, S> From<&OrdMap<K, V>> for collections::HashMap<K, V, S>
+where
+    K: Ord + Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Default,
+{
+    fn from(map: &OrdMap<K, V>) -> Self {
+        map.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+    }
+}
+
 // Proptest
 #[cfg(any(test, feature = "proptest"))]
 #[doc(hidden)]
@@ -2177,6 +4032,7 @@ mod test {
     use crate::test::is_sorted;
     use ::proptest::num::{i16, usize};
     use ::proptest::{bool, collection, proptest};
+    use std::ops::Bound;
 
     #[test]
     fn iterates_in_order() {
@@ -2322,6 +4178,47 @@ mod test {
         assert_eq!(ordmap! {1 => 2, 3 => 8, 5 => 6}, map);
     }
 
+    #[test]
+    fn get_key_value_returns_stored_key() {
+        let stored = "stored".to_string();
+        let stored_ptr = stored.as_ptr();
+        let map = ordmap! {stored => 1};
+        let (key, value) = map.get_key_value("stored").unwrap();
+        assert_eq!(stored_ptr, key.as_ptr());
+        assert_eq!(&1, value);
+        assert_eq!(None, map.get_key_value("missing"));
+    }
+
+    #[test]
+    fn merge_deep_layers() {
+        let defaults = ordmap! {
+            "server" => ordmap!{"host" => Some("localhost"), "port" => Some("80")},
+            "log" => ordmap!{"level" => Some("info")}
+        };
+        let file = ordmap! {"server" => ordmap!{"port" => Some("8080"), "host" => None}};
+        let env = ordmap! {"log" => ordmap!{"level" => Some("debug")}};
+        let config = [file, env].iter().fold(defaults, |acc, layer| {
+            acc.merge_deep(layer, MergeStrategy::PreferRight)
+        });
+        assert_eq!(
+            ordmap! {
+                "server" => ordmap!{"host" => Some("localhost"), "port" => Some("8080")},
+                "log" => ordmap!{"level" => Some("debug")}
+            },
+            config
+        );
+    }
+
+    #[test]
+    fn into_std() {
+        let map = ordmap! {3 => "c", 1 => "a", 2 => "b"};
+        assert_eq!(vec![(1, "a"), (2, "b"), (3, "c")], Vec::from(&map));
+        let btree_map: collections::BTreeMap<i32, &str> = map.clone().into();
+        assert_eq!(map, OrdMap::from(btree_map));
+        let hash_map: collections::HashMap<i32, &str> = map.clone().into();
+        assert_eq!(map, OrdMap::from(hash_map));
+    }
+
     #[test]
     fn entry_api() {
         let mut map = ordmap! {"bar" => 5};
@@ -2377,7 +4274,80 @@ mod test {
         assert_eq!(vec![(3, 4), (2, 3), (1, 2)], range);
     }
 
+    #[test]
+    fn range_between_keys() {
+        let map: OrdMap<u32, u32> = (0..5000u32).map(|i| (i * 3, i)).collect();
+        let std: collections::BTreeMap<u32, u32> = map.clone().into_iter().collect();
+        assert!(map.iter().rev().eq(std.iter().rev()));
+        for start in 0..100 {
+            let end = start * 150;
+            assert!(map.range(start..end).eq(std.range(start..end)));
+            assert!(map
+                .range(start..=end)
+                .rev()
+                .eq(std.range(start..=end).rev()));
+            assert!(map.range(end..).eq(std.range(end..)));
+        }
+    }
+
+    #[test]
+    fn adjust_missing_key_shares_tree() {
+        let map = ordmap! {1 => 1, 2 => 2};
+        assert!(map.ptr_eq(&map.adjust(&3, |value| value + 1)));
+        assert_eq!(ordmap! {1 => 1, 2 => 3}, map.adjust(&2, |value| value + 1));
+    }
+
     proptest! {
+        #[test]
+        fn index_access(ref input in collection::btree_map(0..10000i16, i16::ANY, 0..2000), ref ops in collection::vec((bool::ANY, 0..10000i16), 0..200)) {
+            let mut map = OrdMap::<i16, i16>::from(input.clone());
+            for (insert, key) in ops {
+                if *insert {
+                    map.insert(*key, 0);
+                } else {
+                    map.remove(key);
+                }
+            }
+            assert_eq!(map.len(), map.root.check_sizes());
+            for (index, (key, value)) in map.iter().enumerate() {
+                assert_eq!(Some((key, value)), map.get_index(index));
+                assert_eq!(Some(index), map.index_of(key));
+            }
+            assert_eq!(None, map.get_index(map.len()));
+            for (_, key) in ops {
+                assert_eq!(map.keys().position(|other| other == key), map.index_of(key));
+            }
+        }
+
+        #[test]
+        fn map_operations(ref large in collection::btree_map(0..5000i16, i16::ANY, 0..2000), ref small in collection::btree_map(0..5000i16, i16::ANY, 0..40)) {
+            let sizes = [(large, small), (small, large), (large, large)];
+            for &(left, right) in &sizes {
+                let map_left = OrdMap::<i16, i16>::from(left.clone());
+                let map_right = OrdMap::<i16, i16>::from(right.clone());
+                let mut union = right.clone();
+                union.extend(left.clone());
+                let mut map_union = map_left.clone().union(map_right.clone());
+                assert_eq!(union.len(), map_union.len());
+                assert_eq!(OrdMap::from(union.clone()), map_union);
+                for key in right.keys().chain(left.keys().step_by(7)) {
+                    assert_eq!(union.remove(key), map_union.remove(key));
+                    assert_eq!(union.insert(key / 2, *key), map_union.insert(key / 2, *key));
+                }
+                assert_eq!(OrdMap::from(union), map_union);
+                let intersection: collections::BTreeMap<i16, i16> = left.iter().filter(|(k, _)| right.contains_key(k)).map(|(k, v)| (*k, *v)).collect();
+                assert_eq!(OrdMap::from(intersection), map_left.clone().intersection(map_right.clone()));
+                let intersection: collections::BTreeMap<i16, i16> = left.iter().filter(|(k, _)| right.contains_key(k)).map(|(k, v)| (*k, v.wrapping_sub(right[k]))).collect();
+                assert_eq!(OrdMap::from(intersection), map_left.clone().intersection_with(map_right.clone(), i16::wrapping_sub));
+                let complement: collections::BTreeMap<i16, i16> = left.iter().filter(|(k, _)| !right.contains_key(k)).map(|(k, v)| (*k, *v)).collect();
+                assert_eq!(OrdMap::from(complement.clone()), map_left.clone().relative_complement(map_right.clone()));
+                let mut symmetric = complement.clone();
+                symmetric.extend(right.iter().filter(|(k, _)| !left.contains_key(k)).map(|(k, v)| (*k, *v)));
+                symmetric.extend(left.iter().filter(|(k, v)| right.get(k).is_some_and(|r| *v < r)).map(|(k, v)| (*k, *v)));
+                assert_eq!(OrdMap::from(symmetric), map_left.clone().symmetric_difference_with(map_right.clone(), |l, r| if l < r { Some(l) } else { None }));
+            }
+        }
+
         #[test]
         fn length(ref input in collection::btree_map(i16::ANY, i16::ANY, 0..1000)) {
             let map: OrdMap<i32, i32> = OrdMap::from(input.clone());
@@ -2454,6 +4424,108 @@ mod test {
             assert_eq!(m.len(), map.len());
         }
 
+        #[test]
+        fn merge_with_key(ref left in collection::btree_map(0..500i16, i16::ANY, 0..300),
+                          ref right in collection::btree_map(0..500i16, i16::ANY, 0..300)) {
+            let merged = OrdMap::<i16, i16>::from(left).merge_with_key(
+                OrdMap::<i16, i16>::from(right),
+                |_, l, r| if l < r { Some(i32::from(l) + i32::from(r)) } else { None },
+                |key, l| if key % 2 == 0 { Some(i32::from(l)) } else { None },
+                |_, r| Some(i32::from(r)),
+            );
+            let mut expected = collections::BTreeMap::new();
+            for (key, l) in left {
+                match right.get(key) {
+                    Some(r) if l < r => { expected.insert(*key, i32::from(*l) + i32::from(*r)); }
+                    Some(_) => {}
+                    None if key % 2 == 0 => { expected.insert(*key, i32::from(*l)); }
+                    None => {}
+                }
+            }
+            for (key, r) in right {
+                if !left.contains_key(key) {
+                    expected.insert(*key, i32::from(*r));
+                }
+            }
+            assert_eq!(OrdMap::from(expected), merged);
+        }
+
+        #[test]
+        fn nearest_lookups(ref m in collection::btree_map(0..10_000u16, i16::ANY, 0..2000), probes in collection::vec(0..10_001u16, 0..100)) {
+            let map = OrdMap::<u16, i16>::from(m.clone());
+            for probe in probes {
+                assert_eq!(m.range(..probe).next_back(), map.lookup_lt(&probe));
+                assert_eq!(m.range(..=probe).next_back(), map.lookup_le(&probe));
+                assert_eq!(m.range(probe + 1..).next(), map.lookup_gt(&probe));
+                assert_eq!(m.range(probe..).next(), map.lookup_ge(&probe));
+            }
+        }
+
+        #[test]
+        fn batch_updates(ref m in collection::btree_map(0..5000i16, i16::ANY, 0..2000), ref batch in collection::vec((0..5000i16, i16::ANY), 0..2000)) {
+            let mut std = m.clone();
+            let mut map = OrdMap::<i16, i16>::from(m.clone());
+            std.extend(batch.iter().cloned());
+            map.insert_many(batch.iter().cloned());
+            assert_eq!(OrdMap::from(std.clone()), map);
+            for (index, (key, _)) in batch.iter().enumerate() {
+                if index % 2 == 0 {
+                    std.remove(key);
+                }
+            }
+            map.remove_many(batch.iter().step_by(2).map(|(key, _)| *key));
+            assert_eq!(OrdMap::from(std), map);
+        }
+
+        #[test]
+        fn restrict_keys(ref m in collection::btree_map(0..5000i16, i16::ANY, 0..2000), ref keys in collection::btree_set(0..5000i16, 0..2000)) {
+            let map = OrdMap::<i16, i16>::from(m.clone());
+            let set: OrdSet<i16> = keys.iter().cloned().collect();
+            let (inside, outside): (Vec<_>, Vec<_>) = m.iter().map(|(k, v)| (*k, *v)).partition(|(k, _)| keys.contains(k));
+            assert_eq!(OrdMap::from(inside), map.restrict_keys(&set));
+            assert_eq!(OrdMap::from(outside), map.without_keys(&set));
+        }
+
+        #[test]
+        fn zip_merge(ref left in collection::btree_map(0..1000i16, i16::ANY, 0..500), ref right in collection::btree_map(0..1000i16, i16::ANY, 0..500)) {
+            let map_left = OrdMap::<i16, i16>::from(left.clone());
+            let map_right = OrdMap::<i16, i16>::from(right.clone());
+            let merged = map_left.clone().merge_with_key(map_right.clone(), |_, l, r| Some((Some(l), Some(r))), |_, l| Some((Some(l), None)), |_, r| Some((None, Some(r))));
+            let zipped: Vec<_> = map_left.zip_merge(&map_right).map(|item| match item {
+                MergeItem::Left(k, l) => (*k, (Some(*l), None)),
+                MergeItem::Right(k, r) => (*k, (None, Some(*r))),
+                MergeItem::Both(k, l, r) => (*k, (Some(*l), Some(*r))),
+            }).collect();
+            assert_eq!(merged, OrdMap::from(zipped));
+        }
+
+        #[test]
+        fn split_at(ref m in collection::btree_map(i16::ANY, i16::ANY, 0..1000), n in 0..1100usize) {
+            let map = OrdMap::<i16, i16>::from(m.clone());
+            let (left, right) = map.split_at(n);
+            assert_eq!(m.len().min(n), left.len());
+            assert_eq!(left.len(), left.root.check_sizes());
+            assert_eq!(right.len(), right.root.check_sizes());
+            assert!(left.iter().eq(m.iter().take(n)));
+            assert!(right.iter().eq(m.iter().skip(n)));
+        }
+
+        #[test]
+        fn from_ordered(ref m in collection::btree_map(i16::ANY, i16::ANY, 0..5000)) {
+            let map = OrdMap::from_ordered(m.clone());
+            assert_eq!(m.len(), map.len());
+            assert!(map.iter().eq(m.iter()));
+            let mut map = map;
+            for (index, key) in m.keys().enumerate() {
+                if index % 3 == 0 {
+                    assert_eq!(m.get(key).cloned(), map.remove(key));
+                }
+            }
+            assert!(map.keys().eq(m.keys().enumerate().filter(|(i, _)| i % 3 != 0).map(|(_, k)| k)));
+            let reversed: Vec<(i16, i16)> = m.iter().rev().map(|(k, v)| (*k, *v)).collect();
+            assert_eq!(OrdMap::<i16, i16>::from(m.clone()), OrdMap::from(reversed));
+        }
+
         #[test]
         fn from_iterator(ref m in collection::hash_map(i16::ANY, i16::ANY, 0..1000)) {
             let map: OrdMap<i16, i16> =
@@ -2477,6 +4549,18 @@ mod test {
             assert_eq!(map1, map2);
         }
 
+        #[test]
+        fn equality_with_std(ref m in collection::hash_map(i16::ANY, i16::ANY, 0..1000)) {
+            let map: OrdMap<i16, i16> =
+                FromIterator::from_iter(m.iter().map(|(k, v)| (*k, *v)));
+            let tree: collections::BTreeMap<i16, i16> = m.clone().into_iter().collect();
+            assert_eq!(map, tree);
+            assert_eq!(&map, m);
+            if let Some(key) = m.keys().next() {
+                assert_ne!(map.without(key), tree);
+            }
+        }
+
         #[test]
         fn lookup(ref m in ord_map(i16::ANY, i16::ANY, 0..1000)) {
             let map: OrdMap<i16, i16> =
@@ -2592,5 +4676,56 @@ mod test {
             }).collect();
             assert_eq!(expected, diff);
         }
+
+        #[test]
+        fn drain_filter(ref input in collection::btree_map(i16::ANY, i16::ANY, 0..1000)) {
+            let mut map: OrdMap<i16, i16> = OrdMap::from(input.clone());
+            let shared = map.clone();
+            let removed = map.drain_filter(|k, v| (k ^ v) & 1 == 0);
+            let (expected_removed, expected_kept): (collections::BTreeMap<i16, i16>, collections::BTreeMap<i16, i16>) =
+                input.iter().map(|(k, v)| (*k, *v)).partition(|(k, v)| (k ^ v) & 1 == 0);
+            assert_eq!(map, expected_kept);
+            assert_eq!(removed, expected_removed);
+            assert_eq!(&shared, input);
+        }
+
+        #[test]
+        fn remove_range(
+            ref input in collection::btree_map(0u16..20_000, i16::ANY, 0..5000),
+            ref ranges in collection::vec((0u16..20_000, 0u16..2000, 0u8..4), 1..5),
+            ref ops in collection::vec((bool::ANY, 0u16..20_000), 0..200)
+        ) {
+            let mut map: OrdMap<u16, i16> = OrdMap::from(input.clone());
+            let shared = map.clone();
+            let mut expected = input.clone();
+            for (start, len, kind) in ranges {
+                let end = start.saturating_add(*len);
+                let bounds = match kind {
+                    0 => (Bound::Included(*start), Bound::Excluded(end)),
+                    1 => (Bound::Excluded(*start), Bound::Included(end)),
+                    2 => (Bound::Unbounded, Bound::Excluded(end)),
+                    _ => (Bound::Included(*start), Bound::Unbounded),
+                };
+                map.remove_range(bounds);
+                let removed: Vec<u16> = expected.range(bounds).map(|(k, _)| *k).collect();
+                for key in removed {
+                    expected.remove(&key);
+                }
+                assert_eq!(expected.len(), map.len());
+                assert_eq!(map.len(), map.root.check_sizes());
+                assert_eq!(map, expected);
+            }
+            for (insert, key) in ops {
+                if *insert {
+                    assert_eq!(expected.insert(*key, 0), map.insert(*key, 0));
+                } else {
+                    assert_eq!(expected.remove(key), map.remove(key));
+                }
+            }
+            assert_eq!(expected.len(), map.len());
+            assert_eq!(map.len(), map.root.check_sizes());
+            assert_eq!(map, expected);
+            assert_eq!(&shared, input);
+        }
     }
 }
@@ -22,8 +22,9 @@ use std::cmp::Ordering;
 use std::collections;
 use std::fmt::{Debug, Error, Formatter};
 use std::hash::{BuildHasher, Hash, Hasher};
-use std::iter::{FromIterator, IntoIterator, Sum};
-use std::ops::{Add, Deref, Mul, RangeBounds};
+use std::iter::{Cloned, FromIterator, FusedIterator, IntoIterator, Rev, Sum};
+use std::mem;
+use std::ops::{Add, BitAnd, BitOr, BitXor, Deref, Mul, RangeBounds, Sub};
 
 use crate::hashset::HashSet;
 use crate::nodes::btree::{
@@ -32,8 +33,10 @@ use crate::nodes::btree::{
 };
 #[cfg(has_specialisation)]
 use crate::util::linear_search_by;
+use crate::util::{MergeIter, Merged};
 use crate::util::{Pool, PoolRef};
 
+pub use crate::error::set::OccupiedError;
 pub use crate::nodes::btree::DiffItem;
 
 /// Construct a set from a sequence of values.
@@ -334,6 +337,101 @@ where
         self.root.max().map(Deref::deref)
     }
 
+    /// Get the value at a given position in the set's ordering, counting
+    /// from the smallest value at index 0.
+    ///
+    /// If the index is out of bounds, returns `None`.
+    ///
+    /// Each node in the tree keeps count of the values under it, so this
+    /// only has to follow a single path down the tree.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// let set = ordset![10, 20, 30, 40];
+    /// assert_eq!(Some(&30), set.get_index(2));
+    /// assert_eq!(None, set.get_index(4));
+    /// ```
+    #[must_use]
+    pub fn get_index(&self, index: usize) -> Option<&A> {
+        self.root.lookup_index(index).map(|value| &value.0)
+    }
+
+    /// Pick a value from the set at random.
+    ///
+    /// `sample` is called with the size of the set and should return a
+    /// random index below it, which is how any random number generator
+    /// can be plugged in. The value at that position in the set's
+    /// iteration order is returned, so the values are as equally likely
+    /// to be picked as the indices `sample` returns.
+    ///
+    /// If the set is empty, or `sample` returns an index out of bounds,
+    /// returns `None`.
+    ///
+    /// This picks a position in the set and looks it up with
+    /// [`get_index()`][get_index].
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// use rand::{rngs::SmallRng, Rng, SeedableRng};
+    ///
+    /// let mut rng = SmallRng::seed_from_u64(1);
+    /// let set = ordset![1, 2, 3];
+    /// let value = set.choose(|len| rng.gen_range(0, len)).unwrap();
+    /// assert!(set.contains(value));
+    /// assert_eq!(None, OrdSet::<i32>::new().choose(|len| rng.gen_range(0, len)));
+    /// ```
+    ///
+    /// [get_index]: #method.get_index
+    #[must_use]
+    pub fn choose<F>(&self, mut sample: F) -> Option<&A>
+    where
+        F: FnMut(usize) -> usize,
+    {
+        if self.is_empty() {
+            return None;
+        }
+        self.get_index(sample(self.len()))
+    }
+
+    /// Get the position of a value in the set's ordering, or `None` if
+    /// the value isn't in the set.
+    ///
+    /// This is the inverse of [`get_index()`][get_index]: it counts the
+    /// values smaller than the one given, using the counts kept in the
+    /// nodes along the path to it.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// let set = ordset![10, 20, 30, 40];
+    /// assert_eq!(Some(2), set.index_of(&30));
+    /// assert_eq!(None, set.index_of(&35));
+    /// ```
+    ///
+    /// [get_index]: #method.get_index
+    #[must_use]
+    pub fn index_of<BA>(&self, value: &BA) -> Option<usize>
+    where
+        BA: Ord + ?Sized,
+        A: Borrow<BA>,
+    {
+        self.root.index_of(value)
+    }
+
     /// Create an iterator over the contents of the set.
     #[must_use]
     pub fn iter(&self) -> Iter<'_, A> {
@@ -342,7 +440,152 @@ where
         }
     }
 
+    /// Get an iterator over clones of the values in a set.
+    pub fn cloned_iter(&self) -> Cloned<Iter<'_, A>>
+    where
+        A: Clone,
+    {
+        self.iter().cloned()
+    }
+
+    /// Get a view of a set in descending order.
+    ///
+    /// The view borrows the set rather than copying it, and iterates
+    /// over it backwards, with its notion of smallest and largest value
+    /// flipped.
+    ///
+    /// Time: O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// let set = ordset![2, 3, 1];
+    /// let reversed = set.reversed();
+    /// assert_eq!(Some(&3), reversed.get_min());
+    /// assert_eq!(vec![3, 2, 1], reversed.iter().cloned().collect::<Vec<_>>());
+    /// ```
+    #[must_use]
+    pub fn reversed(&self) -> Reversed<'_, A> {
+        Reversed { set: self }
+    }
+
+    /// Fold the values of a set into an accumulator, in order.
+    ///
+    /// This walks the tree directly rather than going through an
+    /// iterator, which makes it faster than `iter().fold()`.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// let set = ordset!{1, 2, 3};
+    /// assert_eq!(6, set.fold(0, |sum, value| sum + value));
+    /// ```
+    pub fn fold<'a, B, F>(&'a self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, &'a A) -> B,
+    {
+        match self
+            .root
+            .fold_while(init, &mut |acc, value| Ok(f(acc, value)))
+        {
+            Ok(acc) | Err(acc) => acc,
+        }
+    }
+
+    /// Combine the values of a set in order using `f`, or return `None`
+    /// if the set is empty.
+    ///
+    /// Time: O(n)
+    pub fn reduce<F>(&self, mut f: F) -> Option<A>
+    where
+        A: Clone,
+        F: FnMut(A, &A) -> A,
+    {
+        self.fold(None, |acc, value| match acc {
+            None => Some(value.clone()),
+            Some(acc) => Some(f(acc, value)),
+        })
+    }
+
+    /// Test whether any value in a set satisfies a predicate.
+    ///
+    /// Time: O(n)
+    pub fn any<F>(&self, mut f: F) -> bool
+    where
+        F: FnMut(&A) -> bool,
+    {
+        self.find(|value| f(value)).is_some()
+    }
+
+    /// Test whether every value in a set satisfies a predicate.
+    ///
+    /// Time: O(n)
+    pub fn all<F>(&self, mut f: F) -> bool
+    where
+        F: FnMut(&A) -> bool,
+    {
+        !self.any(|value| !f(value))
+    }
+
+    /// Find the smallest value in a set which satisfies a predicate.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// let set = ordset!{1, 2, 3};
+    /// assert_eq!(Some(&2), set.find(|value| value % 2 == 0));
+    /// assert_eq!(None, set.find(|value| *value > 3));
+    /// ```
+    #[must_use]
+    pub fn find<F>(&self, mut f: F) -> Option<&A>
+    where
+        F: FnMut(&A) -> bool,
+    {
+        match self.root.fold_while(None, &mut |_, value| {
+            if f(value) {
+                Err(Some(value))
+            } else {
+                Ok(None)
+            }
+        }) {
+            Ok(_) => None,
+            Err(found) => found.map(Deref::deref),
+        }
+    }
+
     /// Create an iterator over a range inside the set.
+    ///
+    /// The iterator only visits the parts of the tree which overlap
+    /// the range. The range can be any of Rust's range types, or a pair
+    /// of [`Bound`][Bound]s.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// use std::ops::Bound;
+    ///
+    /// let set: OrdSet<i32> = (0..100).collect();
+    /// assert_eq!(vec![&3, &4], set.range(3..5).collect::<Vec<_>>());
+    /// assert_eq!(
+    ///     vec![&4, &5],
+    ///     set.range((Bound::Excluded(&3), Bound::Included(&5)))
+    ///         .collect::<Vec<_>>()
+    /// );
+    /// ```
+    ///
+    /// [Bound]: https://doc.rust-lang.org/std/ops/enum.Bound.html
     #[must_use]
     pub fn range<R, BA>(&self, range: R) -> RangedIter<'_, A>
     where
@@ -373,6 +616,107 @@ where
         }
     }
 
+    /// Get an iterator over the union of this set and another, in
+    /// order, without building a new set.
+    ///
+    /// Time: O(n + m)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// let left = ordset![1, 2, 3];
+    /// let right = ordset![3, 4];
+    /// assert_eq!(
+    ///     vec![&1, &2, &3, &4],
+    ///     left.union_iter(&right).collect::<Vec<_>>()
+    /// );
+    /// ```
+    #[must_use]
+    pub fn union_iter<'a>(&'a self, other: &'a Self) -> UnionIter<'a, A> {
+        UnionIter {
+            it: merge_sets(self, other),
+        }
+    }
+
+    /// Get an iterator over the values which are in both this set and
+    /// another, in order, without building a new set.
+    ///
+    /// Time: O(n + m)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// let left = ordset![1, 2, 3];
+    /// let right = ordset![2, 3, 4];
+    /// assert_eq!(2, left.intersection_iter(&right).count());
+    /// ```
+    #[must_use]
+    pub fn intersection_iter<'a>(&'a self, other: &'a Self) -> IntersectionIter<'a, A> {
+        IntersectionIter {
+            it: merge_sets(self, other),
+        }
+    }
+
+    /// Get an iterator over the values in this set which aren't in
+    /// another, in order, without building a new set.
+    ///
+    /// This yields the values of the
+    /// [`relative_complement`][relative_complement] of the two sets, not
+    /// their [`difference`][difference], which is a symmetric
+    /// difference.
+    ///
+    /// Time: O(n + m)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// let left = ordset![1, 2, 3];
+    /// let right = ordset![2, 3, 4];
+    /// assert_eq!(vec![&1], left.difference_iter(&right).collect::<Vec<_>>());
+    /// ```
+    ///
+    /// [relative_complement]: #method.relative_complement
+    /// [difference]: #method.difference
+    #[must_use]
+    pub fn difference_iter<'a>(&'a self, other: &'a Self) -> DifferenceIter<'a, A> {
+        DifferenceIter {
+            it: merge_sets(self, other),
+        }
+    }
+
+    /// Get an iterator over the values which are in either this set or
+    /// another but not both, in order, without building a new set.
+    ///
+    /// Time: O(n + m)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// let left = ordset![1, 2, 3];
+    /// let right = ordset![2, 3, 4];
+    /// assert_eq!(
+    ///     vec![&1, &4],
+    ///     left.symmetric_difference_iter(&right).collect::<Vec<_>>()
+    /// );
+    /// ```
+    #[must_use]
+    pub fn symmetric_difference_iter<'a>(
+        &'a self,
+        other: &'a Self,
+    ) -> SymmetricDifferenceIter<'a, A> {
+        SymmetricDifferenceIter {
+            it: merge_sets(self, other),
+        }
+    }
+
     /// Test if a value is part of a set.
     ///
     /// Time: O(log n)
@@ -412,7 +756,11 @@ where
     /// assert_eq!(Some(&5), set.get_prev(&6));
     /// ```
     #[must_use]
-    pub fn get_prev(&self, key: &A) -> Option<&A> {
+    pub fn get_prev<BA>(&self, key: &BA) -> Option<&A>
+    where
+        BA: Ord + ?Sized,
+        A: Borrow<BA>,
+    {
         self.root.lookup_prev(key).map(|v| &v.0)
     }
 
@@ -432,7 +780,11 @@ where
     /// assert_eq!(Some(&5), set.get_next(&4));
     /// ```
     #[must_use]
-    pub fn get_next(&self, key: &A) -> Option<&A> {
+    pub fn get_next<BA>(&self, key: &BA) -> Option<&A>
+    where
+        BA: Ord + ?Sized,
+        A: Borrow<BA>,
+    {
         self.root.lookup_next(key).map(|v| &v.0)
     }
 
@@ -449,6 +801,9 @@ where
         if other.len() < self.len() {
             return false;
         }
+        if self.ptr_eq(other) {
+            return true;
+        }
         self.iter().all(|a| other.contains(&a))
     }
 
@@ -464,12 +819,146 @@ where
     {
         self.len() != other.borrow().len() && self.is_subset(other)
     }
+
+    /// Test whether a set is a superset of another set, meaning that
+    /// all values in the other set must also be in our set.
+    ///
+    /// Time: O(m log n) where m is the size of the other set
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// let set = ordset![1, 2, 3];
+    /// assert!(set.is_superset(ordset![1, 3]));
+    /// assert!(!set.is_superset(ordset![1, 4]));
+    /// ```
+    #[must_use]
+    pub fn is_superset<RS>(&self, other: RS) -> bool
+    where
+        RS: Borrow<Self>,
+    {
+        other.borrow().is_subset(self)
+    }
+
+    /// Test whether a set is a proper superset of another set, meaning
+    /// that all values in the other set must also be in our set. A
+    /// proper superset must also be larger than the other set.
+    ///
+    /// Time: O(m log n) where m is the size of the other set
+    #[must_use]
+    pub fn is_proper_superset<RS>(&self, other: RS) -> bool
+    where
+        RS: Borrow<Self>,
+    {
+        other.borrow().is_proper_subset(self)
+    }
+
+    /// Test whether two sets have no values in common.
+    ///
+    /// Only the values of the smaller set which fall between the
+    /// smallest and largest values of the larger set are looked up, so
+    /// sets whose ranges barely overlap are compared quickly.
+    ///
+    /// Time: O(m log n) where m is the size of the smaller set
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// let set = ordset![1, 2, 3];
+    /// assert!(set.is_disjoint(ordset![4, 5]));
+    /// assert!(!set.is_disjoint(ordset![3, 4]));
+    /// ```
+    #[must_use]
+    pub fn is_disjoint<RS>(&self, other: RS) -> bool
+    where
+        RS: Borrow<Self>,
+    {
+        let other = other.borrow();
+        let (small, large) = if self.len() <= other.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let (min, max) = match (large.get_min(), large.get_max()) {
+            (Some(min), Some(max)) => (min, max),
+            _ => return true,
+        };
+        if small.is_empty() {
+            return true;
+        }
+        if self.ptr_eq(other) {
+            return false;
+        }
+        small.range(min..=max).all(|value| !large.contains(value))
+    }
 }
 
 impl<A> OrdSet<A>
 where
     A: Ord + Clone,
 {
+    /// Construct a set from values which are already in ascending order.
+    ///
+    /// The tree is built directly from the values instead of inserting
+    /// them one at a time. If a value is equal to the one before it, it
+    /// replaces it, as with [`insert`][insert]. If the values turn out
+    /// not to be in order, this falls back to inserting the rest one at
+    /// a time, so the result is always correct.
+    ///
+    /// Time: O(n) for ordered input, O(n log n) otherwise
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// let set = OrdSet::from_ordered(0..100_000);
+    /// assert_eq!(100_000, set.len());
+    /// assert_eq!(Some(&99_999), set.get_max());
+    /// assert_eq!(ordset![1, 2, 3], OrdSet::from_ordered(vec![1, 2, 2, 3]));
+    /// assert_eq!(ordset![1, 2, 3], OrdSet::from_ordered(vec![1, 3, 2]));
+    /// ```
+    ///
+    /// [insert]: #method.insert
+    #[must_use]
+    pub fn from_ordered<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = A>,
+    {
+        let mut iter = iter.into_iter();
+        let mut values: Vec<Value<A>> = Vec::with_capacity(iter.size_hint().0);
+        for value in iter.by_ref() {
+            if let Some(last) = values.last_mut() {
+                match last.0.cmp(&value) {
+                    Ordering::Less => {}
+                    Ordering::Equal => {
+                        *last = Value(value);
+                        continue;
+                    }
+                    Ordering::Greater => {
+                        let mut set: Self = values.into_iter().map(|value| value.0).collect();
+                        set.insert(value);
+                        set.extend(iter);
+                        return set;
+                    }
+                }
+            }
+            values.push(Value(value));
+        }
+        let pool = OrdSetPool::default();
+        let size = values.len();
+        let root = Node::from_sorted(&pool.0, &mut values.into_iter(), size);
+        OrdSet {
+            size,
+            root: PoolRef::new(&pool.0, root),
+            pool,
+        }
+    }
+
     /// Insert a value into a set.
     ///
     /// Time: O(log n)
@@ -559,6 +1048,199 @@ where
         self.remove(&key)
     }
 
+    /// Remove every value inside a range from a set.
+    ///
+    /// Rather than removing the values one at a time, this splits the
+    /// tree at each end of the range and joins the pieces outside the
+    /// range back together, so only the nodes along the edges of the
+    /// range are touched.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// let mut set = ordset![1, 2, 3, 4, 5];
+    /// set.remove_range(2..=4);
+    /// assert_eq!(ordset![1, 5], set);
+    /// ```
+    pub fn remove_range<R, BA>(&mut self, range: R)
+    where
+        R: RangeBounds<BA>,
+        A: Borrow<BA>,
+        BA: Ord + ?Sized,
+    {
+        if self
+            .range((range.start_bound(), range.end_bound()))
+            .next()
+            .is_none()
+        {
+            return;
+        }
+        let root = mem::replace(&mut self.root, PoolRef::default(&self.pool.0));
+        let root = PoolRef::unwrap_or_clone(root).remove_range(&self.pool.0, &range);
+        self.size = root.len();
+        self.root = PoolRef::new(&self.pool.0, root);
+    }
+
+    /// Filter out values from a set which don't satisfy a predicate.
+    ///
+    /// If the set isn't shared with any other set, the values are
+    /// removed in place, without reconstructing the tree.
+    ///
+    /// Time: O(n + m log n) where m is the number of removed values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// let mut set = ordset![1, 2, 3];
+    /// set.retain(|v| *v > 1);
+    /// assert_eq!(ordset![2, 3], set);
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&A) -> bool,
+    {
+        let removed: Vec<A> = self.iter().filter(|value| !f(value)).cloned().collect();
+        for value in removed {
+            self.remove(&value);
+        }
+    }
+
+    /// Remove the values which satisfy a predicate from a set, and
+    /// return them in a new set.
+    ///
+    /// Like [`retain`][retain], this removes the values in place if the
+    /// set isn't shared with any other set.
+    ///
+    /// Time: O(n + m log n) where m is the number of removed values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// let mut set = ordset![1, 2, 3];
+    /// let removed = set.drain_filter(|v| *v > 1);
+    /// assert_eq!(ordset![1], set);
+    /// assert_eq!(ordset![2, 3], removed);
+    /// ```
+    ///
+    /// [retain]: #method.retain
+    pub fn drain_filter<F>(&mut self, mut f: F) -> Self
+    where
+        F: FnMut(&A) -> bool,
+    {
+        let mut removed = OrdSet::new();
+        self.retain(|value| {
+            if f(value) {
+                removed.insert(value.clone());
+                false
+            } else {
+                true
+            }
+        });
+        removed
+    }
+
+    /// Construct a new set containing only the values of the current
+    /// set which satisfy a predicate.
+    ///
+    /// The new set shares the parts of its tree which no values were
+    /// removed from with the current set.
+    ///
+    /// Time: O(n + m log n) where m is the number of removed values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// let set = ordset![1, 2, 3, 4];
+    /// assert_eq!(ordset![2, 4], set.filter(|v| *v % 2 == 0));
+    /// assert_eq!(ordset![1, 2, 3, 4], set);
+    /// ```
+    #[must_use]
+    pub fn filter<F>(&self, f: F) -> Self
+    where
+        F: FnMut(&A) -> bool,
+    {
+        let mut out = self.clone();
+        out.retain(f);
+        out
+    }
+
+    /// Construct a new set by applying a function to every value of the
+    /// current set.
+    ///
+    /// If the function maps several values to the same value, the new
+    /// set keeps only one of them, so it can be smaller than the
+    /// current set.
+    ///
+    /// Time: O(n log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// let set = ordset![-2, 1, 2];
+    /// assert_eq!(ordset![1, 4], set.map(|v| v * v));
+    /// ```
+    #[must_use]
+    pub fn map<B, F>(&self, f: F) -> OrdSet<B>
+    where
+        B: Ord + Clone,
+        F: FnMut(&A) -> B,
+    {
+        self.iter().map(f).collect()
+    }
+
+    /// Construct a new set by applying a strictly increasing function to
+    /// every value of the current set.
+    ///
+    /// Because the function keeps the values in the same order, the new
+    /// set is built with the same shape as the current one, without
+    /// comparing any values. The function is called on the values in
+    /// ascending order.
+    ///
+    /// The function must map smaller values to smaller values. This isn't
+    /// checked, and if it doesn't hold, the new set will give wrong
+    /// answers to lookups. Use [`map`][map] if you're not sure.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// let set = ordset![1, 2, 3];
+    /// assert_eq!(ordset![10, 20, 30], set.map_monotonic(|v| v * 10));
+    /// ```
+    ///
+    /// [map]: #method.map
+    #[must_use]
+    pub fn map_monotonic<B, F>(&self, mut f: F) -> OrdSet<B>
+    where
+        B: Ord,
+        F: FnMut(&A) -> B,
+    {
+        let pool = OrdSetPool::default();
+        let root = self
+            .root
+            .map_ordered(&pool.0, &mut |value: &Value<A>| Value(f(value)));
+        OrdSet {
+            size: self.size,
+            root: PoolRef::new(&pool.0, root),
+            pool,
+        }
+    }
+
     /// Construct a new set from the current set with the given value
     /// added.
     ///
@@ -582,6 +1264,35 @@ where
         out
     }
 
+    /// Construct a new set from the current set with the given value
+    /// added, failing if the value is already in the set.
+    ///
+    /// Unlike [`update()`][update], this never replaces a value that's
+    /// already there. If the value is in the set, you get it back in an
+    /// [`OccupiedError`][OccupiedError].
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::{OrdSet, OccupiedError};
+    /// let set = ordset![456];
+    /// assert_eq!(Ok(ordset![123, 456]), set.try_insert(123));
+    /// assert_eq!(Err(OccupiedError { value: 456 }), set.try_insert(456));
+    /// ```
+    ///
+    /// [update]: #method.update
+    /// [OccupiedError]: ./struct.OccupiedError.html
+    pub fn try_insert(&self, a: A) -> Result<Self, OccupiedError<A>> {
+        if self.contains(&a) {
+            Err(OccupiedError { value: a })
+        } else {
+            Ok(self.update(a))
+        }
+    }
+
     /// Construct a new set with the given value removed if it's in
     /// the set.
     ///
@@ -597,6 +1308,38 @@ where
         out
     }
 
+    /// Remove a value from a set, if it's there, and return the
+    /// removed value as well as the updated set.
+    ///
+    /// The value is looked up and removed in a single walk down the
+    /// tree, and the value handed back is the one stored in the set,
+    /// not the one you searched for.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// let set = ordset!["foo".to_string(), "bar".to_string()];
+    /// assert_eq!(
+    ///   Some(("foo".to_string(), ordset!["bar".to_string()])),
+    ///   set.extract("foo")
+    /// );
+    /// assert_eq!(None, set.extract("baz"));
+    /// ```
+    #[must_use]
+    pub fn extract<BA>(&self, a: &BA) -> Option<(A, Self)>
+    where
+        BA: Ord + ?Sized,
+        A: Borrow<BA>,
+    {
+        let mut out = self.clone();
+        let result = out.remove(a);
+        result.map(|value| (value, out))
+    }
+
     /// Remove the smallest value from a set, and return that value as
     /// well as the updated set.
     ///
@@ -621,9 +1364,37 @@ where
         }
     }
 
-    /// Construct the union of two sets.
+    /// Remove the smallest value from a set, and return that value along
+    /// with the updated set, or `None` if the set is empty.
     ///
-    /// Time: O(n log n)
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// let mut work = ordset![3, 1, 2];
+    /// let mut done = Vec::new();
+    /// while let Some((next, rest)) = work.pop() {
+    ///     done.push(next);
+    ///     work = rest;
+    /// }
+    /// assert_eq!(vec![1, 2, 3], done);
+    /// ```
+    #[must_use]
+    pub fn pop(&self) -> Option<(A, Self)> {
+        self.get_min().and_then(|value| self.extract(value))
+    }
+
+    /// Construct the union of two sets, keeping the values from
+    /// `other` when both sets hold equal values.
+    ///
+    /// The trees are combined by splitting and joining them, which
+    /// leaves the parts of the larger tree the smaller one doesn't reach
+    /// into shared with the result.
+    ///
+    /// Time: O(m log (n / m)) where m is the size of the smaller set
     ///
     /// # Examples
     ///
@@ -636,22 +1407,54 @@ where
     /// assert_eq!(expected, set1.union(set2));
     /// ```
     #[must_use]
-    pub fn union(mut self, other: Self) -> Self {
-        for value in other {
-            self.insert(value);
-        }
-        self
+    pub fn union(self, other: Self) -> Self {
+        self.combine(other, (true, true), |_, value| Some(value))
     }
 
     /// Construct the union of multiple sets.
     ///
     /// Time: O(n log n)
     #[must_use]
-    pub fn unions<I>(i: I) -> Self
+    pub fn unions<I>(i: I) -> Self
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        i.into_iter().fold(Self::default(), Self::union)
+    }
+
+    /// Construct the intersection of multiple sets.
+    ///
+    /// The sets are intersected starting from the smallest, and it stops
+    /// as soon as the intersection is empty. Returns `None` if there are
+    /// no sets to intersect, since there's no set to start from.
+    ///
+    /// Time: O(n log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// let sets = vec![ordset!{1, 2, 3}, ordset!{2, 3, 4}, ordset!{3, 4, 5}];
+    /// assert_eq!(Some(ordset!{3}), OrdSet::intersections(sets));
+    /// assert_eq!(None, OrdSet::<i32>::intersections(vec![]));
+    /// ```
+    #[must_use]
+    pub fn intersections<I>(i: I) -> Option<Self>
     where
         I: IntoIterator<Item = Self>,
     {
-        i.into_iter().fold(Self::default(), Self::union)
+        let mut sets: Vec<Self> = i.into_iter().collect();
+        sets.sort_by_key(Self::len);
+        let mut sets = sets.into_iter();
+        let mut out = sets.next()?;
+        for set in sets {
+            if out.is_empty() {
+                break;
+            }
+            out.retain(|value| set.contains(value));
+        }
+        Some(out)
     }
 
     /// Construct the symmetric difference between two sets.
@@ -680,7 +1483,11 @@ where
 
     /// Construct the symmetric difference between two sets.
     ///
-    /// Time: O(n log n)
+    /// The trees are combined by splitting and joining them, like
+    /// [`union`][union] does. If both sets share the same tree the result
+    /// is empty without looking at any values.
+    ///
+    /// Time: O(m log (n / m)) where m is the size of the smaller set
     ///
     /// # Examples
     ///
@@ -692,20 +1499,23 @@ where
     /// let expected = ordset!{1, 3};
     /// assert_eq!(expected, set1.symmetric_difference(set2));
     /// ```
+    ///
+    /// [union]: #method.union
     #[must_use]
-    pub fn symmetric_difference(mut self, other: Self) -> Self {
-        for value in other {
-            if self.remove(&value).is_none() {
-                self.insert(value);
-            }
+    pub fn symmetric_difference(self, other: Self) -> Self {
+        if self.ptr_eq(&other) {
+            return Self::default();
         }
-        self
+        self.combine(other, (true, true), |_, _| None)
     }
 
     /// Construct the relative complement between two sets, that is the set
     /// of values in `self` that do not occur in `other`.
     ///
-    /// Time: O(m log n) where m is the size of the other set
+    /// The trees are combined by splitting and joining them, like
+    /// [`union`][union] does.
+    ///
+    /// Time: O(m log (n / m)) where m is the size of the smaller set
     ///
     /// # Examples
     ///
@@ -717,17 +1527,20 @@ where
     /// let expected = ordset!{1};
     /// assert_eq!(expected, set1.relative_complement(set2));
     /// ```
+    ///
+    /// [union]: #method.union
     #[must_use]
-    pub fn relative_complement(mut self, other: Self) -> Self {
-        for value in other {
-            let _ = self.remove(&value);
-        }
-        self
+    pub fn relative_complement(self, other: Self) -> Self {
+        self.combine(other, (true, false), |_, _| None)
     }
 
-    /// Construct the intersection of two sets.
+    /// Construct the intersection of two sets, keeping the values from
+    /// `other`.
     ///
-    /// Time: O(n log n)
+    /// The trees are combined by splitting and joining them, like
+    /// [`union`][union] does.
+    ///
+    /// Time: O(m log (n / m)) where m is the size of the smaller set
     ///
     /// # Examples
     ///
@@ -739,15 +1552,141 @@ where
     /// let expected = ordset!{2};
     /// assert_eq!(expected, set1.intersection(set2));
     /// ```
+    ///
+    /// [union]: #method.union
     #[must_use]
     pub fn intersection(self, other: Self) -> Self {
-        let mut out = Self::default();
-        for value in other {
-            if self.contains(&value) {
-                out.insert(value);
-            }
+        self.combine(other, (false, false), |_, value| Some(value))
+    }
+
+    // Combine the trees of two sets, keeping the values only found in
+    // `self` or `other` according to `keep`, and calling `f` for values
+    // found in both.
+    fn combine<F>(self, other: Self, keep: (bool, bool), mut f: F) -> Self
+    where
+        F: FnMut(A, A) -> Option<A>,
+    {
+        let pool = self.pool.clone();
+        let (root, size) = Node::combine(
+            &pool.0,
+            PoolRef::unwrap_or_clone(self.root),
+            self.size,
+            PoolRef::unwrap_or_clone(other.root),
+            other.size,
+            keep,
+            &mut |Value(left), Value(right)| f(left, right).map(Value),
+        );
+        OrdSet {
+            size,
+            root: PoolRef::new(&pool.0, root),
+            pool,
+        }
+    }
+
+    /// Construct the cartesian product of two sets, that is the set of
+    /// every pair `(a, b)` where `a` is in `self` and `b` is in
+    /// `other`.
+    ///
+    /// Both sets are walked in order, so the pairs are generated in
+    /// the order the product set stores them, and the tree is built
+    /// directly from them without any comparisons.
+    ///
+    /// Time: O(n m)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size of the product would overflow a `usize`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// let set1 = ordset!{1, 2};
+    /// let set2 = ordset!{"a", "b"};
+    /// let expected = ordset!{(1, "a"), (1, "b"), (2, "a"), (2, "b")};
+    /// assert_eq!(expected, set1.product(&set2));
+    /// ```
+    #[must_use]
+    pub fn product<B>(&self, other: &OrdSet<B>) -> OrdSet<(A, B)>
+    where
+        B: Ord + Clone,
+    {
+        let size = self
+            .len()
+            .checked_mul(other.len())
+            .expect("OrdSet::product: size of the product overflows usize");
+        let mut pairs = self.iter().flat_map(|left| {
+            other
+                .iter()
+                .map(move |right| Value((left.clone(), right.clone())))
+        });
+        let pool = OrdSetPool::default();
+        let root = Node::from_sorted(&pool.0, &mut pairs, size);
+        OrdSet {
+            size,
+            root: PoolRef::new(&pool.0, root),
+            pool,
+        }
+    }
+
+    /// Get a lazy iterator over every subset of a set, including the
+    /// empty set and the set itself.
+    ///
+    /// Subsets are generated by counting through the set in binary,
+    /// with the smallest value as the least significant bit, so the
+    /// empty set comes first and the full set comes last.
+    ///
+    /// Note that a set of `n` values has `2^n` subsets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// let set = ordset!{1, 2};
+    /// assert_eq!(
+    ///     vec![ordset!{}, ordset!{1}, ordset!{2}, ordset!{1, 2}],
+    ///     set.subsets().collect::<Vec<_>>()
+    /// );
+    /// ```
+    #[must_use]
+    pub fn subsets(&self) -> Subsets<A> {
+        let values: Vec<A> = self.iter().cloned().collect();
+        Subsets {
+            mask: Some(vec![false; values.len()]),
+            values,
+        }
+    }
+
+    /// Get a lazy iterator over every subset of a set containing
+    /// exactly `size` values.
+    ///
+    /// The subsets are generated in lexicographic order. If `size` is
+    /// larger than the set, the iterator is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// let set = ordset!{1, 2, 3};
+    /// assert_eq!(
+    ///     vec![ordset!{1, 2}, ordset!{1, 3}, ordset!{2, 3}],
+    ///     set.subsets_of_size(2).collect::<Vec<_>>()
+    /// );
+    /// ```
+    #[must_use]
+    pub fn subsets_of_size(&self, size: usize) -> SubsetsOfSize<A> {
+        let values: Vec<A> = self.iter().cloned().collect();
+        SubsetsOfSize {
+            indices: if size <= values.len() {
+                Some((0..size).collect())
+            } else {
+                None
+            },
+            values,
         }
-        out
     }
 
     /// Split a set into two, with the left hand set containing values
@@ -818,6 +1757,107 @@ where
     pub fn skip(&self, n: usize) -> Self {
         self.iter().skip(n).cloned().collect()
     }
+
+    /// Construct a set with the smallest values from a given set for
+    /// which a predicate returns true, up to the first value for which
+    /// it returns false.
+    ///
+    /// The new set shares its nodes with the original set, apart from
+    /// those along the point where it was cut off.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// let set = ordset![1, 2, 3, 4, 10];
+    /// assert_eq!(ordset![1, 2, 3, 4], set.take_while(|value| *value < 5));
+    /// ```
+    #[must_use]
+    pub fn take_while<F>(&self, mut f: F) -> Self
+    where
+        F: FnMut(&A) -> bool,
+    {
+        let mut out = self.clone();
+        if let Some(boundary) = self.iter().find(|value| !f(value)) {
+            out.remove_range(boundary..);
+        }
+        out
+    }
+
+    /// Construct a set with the smallest values from a given set for
+    /// which a predicate returns true removed, up to the first value for
+    /// which it returns false.
+    ///
+    /// The new set shares its nodes with the original set, apart from
+    /// those along the point where it was cut off.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// let set = ordset![1, 2, 3, 4, 10];
+    /// assert_eq!(ordset![10], set.drop_while(|value| *value < 5));
+    /// ```
+    #[must_use]
+    pub fn drop_while<F>(&self, mut f: F) -> Self
+    where
+        F: FnMut(&A) -> bool,
+    {
+        match self.iter().find(|value| !f(value)) {
+            None => Self::new(),
+            Some(boundary) => {
+                let mut out = self.clone();
+                out.remove_range(..boundary);
+                out
+            }
+        }
+    }
+
+    /// Split a set in two at the first value for which a predicate
+    /// returns false, with the values before it in the left hand set and
+    /// the rest in the right hand set.
+    ///
+    /// This is the same as calling [`take_while`][take_while] and
+    /// [`drop_while`][drop_while] with the same predicate, but only calls
+    /// the predicate once per value.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::ordset::OrdSet;
+    /// let set = ordset![1, 2, 3, 4, 5];
+    /// let (small, large) = set.span(|value| value * value < 10);
+    /// assert_eq!(ordset![1, 2, 3], small);
+    /// assert_eq!(ordset![4, 5], large);
+    /// ```
+    ///
+    /// [take_while]: #method.take_while
+    /// [drop_while]: #method.drop_while
+    #[must_use]
+    pub fn span<F>(&self, mut f: F) -> (Self, Self)
+    where
+        F: FnMut(&A) -> bool,
+    {
+        match self.iter().find(|value| !f(value)) {
+            None => (self.clone(), Self::new()),
+            Some(boundary) => {
+                let mut left = self.clone();
+                left.remove_range(boundary..);
+                let mut right = self.clone();
+                right.remove_range(..boundary);
+                (left, right)
+            }
+        }
+    }
 }
 
 // Core traits
@@ -845,6 +1885,22 @@ impl<A: Ord> PartialEq for OrdSet<A> {
 
 impl<A: Ord + Eq> Eq for OrdSet<A> {}
 
+impl<A: Ord> PartialEq<collections::BTreeSet<A>> for OrdSet<A> {
+    fn eq(&self, other: &collections::BTreeSet<A>) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<A, S> PartialEq<collections::HashSet<A, S>> for OrdSet<A>
+where
+    A: Ord + Hash + Eq,
+    S: BuildHasher,
+{
+    fn eq(&self, other: &collections::HashSet<A, S>) -> bool {
+        self.len() == other.len() && self.iter().all(|value| other.contains(value))
+    }
+}
+
 impl<A: Ord> PartialOrd for OrdSet<A> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         self.iter().partial_cmp(other.iter())
@@ -888,53 +1944,218 @@ impl<'a, A: Ord + Clone> Add for &'a OrdSet<A> {
     fn add(self, other: Self) -> Self::Output {
         self.clone().union(other.clone())
     }
-}
-
-impl<A: Ord + Clone> Mul for OrdSet<A> {
-    type Output = OrdSet<A>;
+}
+
+impl<A: Ord + Clone> Mul for OrdSet<A> {
+    type Output = OrdSet<A>;
+
+    fn mul(self, other: Self) -> Self::Output {
+        self.intersection(other)
+    }
+}
+
+impl<'a, A: Ord + Clone> Mul for &'a OrdSet<A> {
+    type Output = OrdSet<A>;
+
+    fn mul(self, other: Self) -> Self::Output {
+        self.clone().intersection(other.clone())
+    }
+}
+
+impl<A: Ord + Clone> BitOr for OrdSet<A> {
+    type Output = OrdSet<A>;
+
+    fn bitor(self, other: Self) -> Self::Output {
+        self.union(other)
+    }
+}
+
+impl<A: Ord + Clone> BitOr for &OrdSet<A> {
+    type Output = OrdSet<A>;
+
+    fn bitor(self, other: Self) -> Self::Output {
+        self.clone().union(other.clone())
+    }
+}
+
+impl<A: Ord + Clone> BitAnd for OrdSet<A> {
+    type Output = OrdSet<A>;
+
+    fn bitand(self, other: Self) -> Self::Output {
+        self.intersection(other)
+    }
+}
+
+impl<A: Ord + Clone> BitAnd for &OrdSet<A> {
+    type Output = OrdSet<A>;
+
+    fn bitand(self, other: Self) -> Self::Output {
+        self.clone().intersection(other.clone())
+    }
+}
+
+impl<A: Ord + Clone> BitXor for OrdSet<A> {
+    type Output = OrdSet<A>;
+
+    fn bitxor(self, other: Self) -> Self::Output {
+        self.symmetric_difference(other)
+    }
+}
+
+impl<A: Ord + Clone> BitXor for &OrdSet<A> {
+    type Output = OrdSet<A>;
+
+    fn bitxor(self, other: Self) -> Self::Output {
+        self.clone().symmetric_difference(other.clone())
+    }
+}
+
+impl<A: Ord + Clone> Sub for OrdSet<A> {
+    type Output = OrdSet<A>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self.relative_complement(other)
+    }
+}
+
+impl<A: Ord + Clone> Sub for &OrdSet<A> {
+    type Output = OrdSet<A>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self.clone().relative_complement(other.clone())
+    }
+}
+
+impl<A: Ord + Clone> Sum for OrdSet<A> {
+    fn sum<I>(it: I) -> Self
+    where
+        I: Iterator<Item = Self>,
+    {
+        it.fold(Self::new(), |a, b| a + b)
+    }
+}
+
+impl<A, R> Extend<R> for OrdSet<A>
+where
+    A: Ord + Clone + From<R>,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = R>,
+    {
+        for value in iter {
+            self.insert(From::from(value));
+        }
+    }
+}
+
+impl<A: Ord + Debug> Debug for OrdSet<A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+// Reversed view
+
+/// A view of an [`OrdSet`][OrdSet] in descending order.
+///
+/// This is created by [`OrdSet::reversed()`][reversed].
+///
+/// [OrdSet]: ./struct.OrdSet.html
+/// [reversed]: ./struct.OrdSet.html#method.reversed
+pub struct Reversed<'a, A> {
+    set: &'a OrdSet<A>,
+}
+
+impl<'a, A> Reversed<'a, A>
+where
+    A: Ord,
+{
+    /// Get the set this is a view of.
+    #[must_use]
+    pub fn as_set(&self) -> &'a OrdSet<A> {
+        self.set
+    }
+
+    /// Test whether the set is empty.
+    ///
+    /// Time: O(1)
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+
+    /// Get the size of the set.
+    ///
+    /// Time: O(1)
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
 
-    fn mul(self, other: Self) -> Self::Output {
-        self.intersection(other)
+    /// Get the first value in descending order, which is the largest
+    /// value in the set.
+    ///
+    /// Time: O(log n)
+    #[must_use]
+    pub fn get_min(&self) -> Option<&'a A> {
+        self.set.get_max()
     }
-}
 
-impl<'a, A: Ord + Clone> Mul for &'a OrdSet<A> {
-    type Output = OrdSet<A>;
+    /// Get the last value in descending order, which is the smallest
+    /// value in the set.
+    ///
+    /// Time: O(log n)
+    #[must_use]
+    pub fn get_max(&self) -> Option<&'a A> {
+        self.set.get_min()
+    }
 
-    fn mul(self, other: Self) -> Self::Output {
-        self.clone().intersection(other.clone())
+    /// Get an iterator over the values of the set in descending order.
+    pub fn iter(&self) -> Rev<Iter<'a, A>> {
+        self.set.iter().rev()
     }
-}
 
-impl<A: Ord + Clone> Sum for OrdSet<A> {
-    fn sum<I>(it: I) -> Self
+    /// Get an iterator over a range of values in descending order.
+    pub fn range<R, BA>(&self, range: R) -> Rev<RangedIter<'a, A>>
     where
-        I: Iterator<Item = Self>,
+        R: RangeBounds<BA>,
+        A: Borrow<BA>,
+        BA: Ord + ?Sized,
     {
-        it.fold(Self::new(), |a, b| a + b)
+        self.set.range(range).rev()
     }
 }
 
-impl<A, R> Extend<R> for OrdSet<A>
-where
-    A: Ord + Clone + From<R>,
-{
-    fn extend<I>(&mut self, iter: I)
-    where
-        I: IntoIterator<Item = R>,
-    {
-        for value in iter {
-            self.insert(From::from(value));
-        }
+impl<'a, A> Clone for Reversed<'a, A> {
+    fn clone(&self) -> Self {
+        *self
     }
 }
 
-impl<A: Ord + Debug> Debug for OrdSet<A> {
+impl<'a, A> Copy for Reversed<'a, A> {}
+
+impl<'a, A> Debug for Reversed<'a, A>
+where
+    A: Ord + Debug,
+{
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
         f.debug_set().entries(self.iter()).finish()
     }
 }
 
+impl<'a, A> IntoIterator for Reversed<'a, A>
+where
+    A: Ord,
+{
+    type Item = &'a A;
+    type IntoIter = Rev<Iter<'a, A>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 // Iterators
 
 /// An iterator over the elements of a set.
@@ -1052,6 +2273,221 @@ where
     }
 }
 
+// Walks two sets side by side, pairing up the values they share.
+type SetMergeIter<'a, A> =
+    MergeIter<Iter<'a, A>, Iter<'a, A>, &'a A, &'a A, fn(&&'a A, &&'a A) -> Ordering>;
+
+fn merge_sets<'a, A: Ord>(left: &'a OrdSet<A>, right: &'a OrdSet<A>) -> SetMergeIter<'a, A> {
+    MergeIter::new(left.iter(), right.iter(), Ord::cmp)
+}
+
+/// A lazy iterator over the union of two sets.
+///
+/// To obtain one, use [`OrdSet::union_iter()`][union_iter].
+///
+/// [union_iter]: struct.OrdSet.html#method.union_iter
+pub struct UnionIter<'a, A> {
+    it: SetMergeIter<'a, A>,
+}
+
+impl<'a, A> Iterator for UnionIter<'a, A>
+where
+    A: 'a + Ord,
+{
+    type Item = &'a A;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.it.next().map(|merged| match merged {
+            Merged::Left(value) | Merged::Right(value) | Merged::Both(value, _) => value,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (left, right) = self.it.lens();
+        (left.max(right), left.checked_add(right))
+    }
+}
+
+impl<'a, A> FusedIterator for UnionIter<'a, A> where A: 'a + Ord {}
+
+/// A lazy iterator over the intersection of two sets.
+///
+/// To obtain one, use [`OrdSet::intersection_iter()`][intersection_iter].
+///
+/// [intersection_iter]: struct.OrdSet.html#method.intersection_iter
+pub struct IntersectionIter<'a, A> {
+    it: SetMergeIter<'a, A>,
+}
+
+impl<'a, A> Iterator for IntersectionIter<'a, A>
+where
+    A: 'a + Ord,
+{
+    type Item = &'a A;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Merged::Both(value, _) = self.it.next()? {
+                return Some(value);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (left, right) = self.it.lens();
+        (0, Some(left.min(right)))
+    }
+}
+
+impl<'a, A> FusedIterator for IntersectionIter<'a, A> where A: 'a + Ord {}
+
+/// A lazy iterator over the values in one set which aren't in another.
+///
+/// To obtain one, use [`OrdSet::difference_iter()`][difference_iter].
+///
+/// [difference_iter]: struct.OrdSet.html#method.difference_iter
+pub struct DifferenceIter<'a, A> {
+    it: SetMergeIter<'a, A>,
+}
+
+impl<'a, A> Iterator for DifferenceIter<'a, A>
+where
+    A: 'a + Ord,
+{
+    type Item = &'a A;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Merged::Left(value) = self.it.next()? {
+                return Some(value);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (left, _) = self.it.lens();
+        (0, Some(left))
+    }
+}
+
+impl<'a, A> FusedIterator for DifferenceIter<'a, A> where A: 'a + Ord {}
+
+/// A lazy iterator over the symmetric difference of two sets.
+///
+/// To obtain one, use
+/// [`OrdSet::symmetric_difference_iter()`][symmetric_difference_iter].
+///
+/// [symmetric_difference_iter]: struct.OrdSet.html#method.symmetric_difference_iter
+pub struct SymmetricDifferenceIter<'a, A> {
+    it: SetMergeIter<'a, A>,
+}
+
+impl<'a, A> Iterator for SymmetricDifferenceIter<'a, A>
+where
+    A: 'a + Ord,
+{
+    type Item = &'a A;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.it.next()? {
+                Merged::Left(value) | Merged::Right(value) => return Some(value),
+                Merged::Both(_, _) => {}
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (left, right) = self.it.lens();
+        (0, left.checked_add(right))
+    }
+}
+
+impl<'a, A> FusedIterator for SymmetricDifferenceIter<'a, A> where A: 'a + Ord {}
+
+/// A lazy iterator over the subsets of a set.
+///
+/// To obtain one, use [`OrdSet::subsets()`][subsets].
+///
+/// [subsets]: struct.OrdSet.html#method.subsets
+pub struct Subsets<A> {
+    values: Vec<A>,
+    mask: Option<Vec<bool>>,
+}
+
+impl<A> Iterator for Subsets<A>
+where
+    A: Ord + Clone,
+{
+    type Item = OrdSet<A>;
+
+    /// Advance the iterator and return the next subset.
+    ///
+    /// Time: O(n log n)
+    fn next(&mut self) -> Option<Self::Item> {
+        let mask = self.mask.as_mut()?;
+        let subset = self
+            .values
+            .iter()
+            .zip(mask.iter())
+            .filter(|(_, selected)| **selected)
+            .map(|(value, _)| value.clone())
+            .collect();
+        match mask.iter().position(|selected| !*selected) {
+            None => self.mask = None,
+            Some(index) => {
+                for selected in &mut mask[..index] {
+                    *selected = false;
+                }
+                mask[index] = true;
+            }
+        }
+        Some(subset)
+    }
+}
+
+impl<A: Ord + Clone> FusedIterator for Subsets<A> {}
+
+/// A lazy iterator over the subsets of a set with a given size.
+///
+/// To obtain one, use [`OrdSet::subsets_of_size()`][subsets_of_size].
+///
+/// [subsets_of_size]: struct.OrdSet.html#method.subsets_of_size
+pub struct SubsetsOfSize<A> {
+    values: Vec<A>,
+    indices: Option<Vec<usize>>,
+}
+
+impl<A> Iterator for SubsetsOfSize<A>
+where
+    A: Ord + Clone,
+{
+    type Item = OrdSet<A>;
+
+    /// Advance the iterator and return the next subset.
+    ///
+    /// Time: O(k log k)
+    fn next(&mut self) -> Option<Self::Item> {
+        let values = &self.values;
+        let indices = self.indices.as_mut()?;
+        let subset = indices.iter().map(|index| values[*index].clone()).collect();
+        let size = indices.len();
+        let limit = values.len() - size;
+        match (0..size).rev().find(|i| indices[*i] < limit + *i) {
+            None => self.indices = None,
+            Some(i) => {
+                indices[i] += 1;
+                for j in i + 1..size {
+                    indices[j] = indices[j - 1] + 1;
+                }
+            }
+        }
+        Some(subset)
+    }
+}
+
+impl<A: Ord + Clone> FusedIterator for SubsetsOfSize<A> {}
+
 impl<A, R> FromIterator<R> for OrdSet<A>
 where
     A: Ord + Clone + From<R>,
@@ -1163,6 +2599,56 @@ impl<'a, A: Hash + Eq + Ord + Clone, S: BuildHasher> From<&'a HashSet<A, S>> for
     }
 }
 
+impl<A: Ord + Clone> From<OrdSet<A>> for Vec<A> {
+    /// Create a [`std::vec::Vec`][vec] from a set, with the values in
+    /// ascending order.
+    ///
+    /// Time: O(n)
+    ///
+    /// [vec]: https://doc.rust-lang.org/std/vec/struct.Vec.html
+    fn from(set: OrdSet<A>) -> Self {
+        set.into_iter().collect()
+    }
+}
+
+impl<A: Ord + Clone> From<&OrdSet<A>> for Vec<A> {
+    fn from(set: &OrdSet<A>) -> Self {
+        set.iter().cloned().collect()
+    }
+}
+
+impl<A: Ord + Clone> From<OrdSet<A>> for collections::BTreeSet<A> {
+    fn from(set: OrdSet<A>) -> Self {
+        set.into_iter().collect()
+    }
+}
+
+impl<A: Ord + Clone> From<&OrdSet<A>> for collections::BTreeSet<A> {
+    fn from(set: &OrdSet<A>) -> Self {
+        set.iter().cloned().collect()
+    }
+}
+
+impl<A, S> From<OrdSet<A>> for collections::HashSet<A, S>
+where
+    A: Ord + Hash + Eq + Clone,
+    S: BuildHasher + Default,
+{
+    fn from(set: OrdSet<A>) -> Self {
+        set.into_iter().collect()
+    }
+}
+
+impl<A, S> From<&OrdSet<A>> for collections::HashSet<A, S>
+where
+    A: Ord + Hash + Eq + Clone,
+    S: BuildHasher + Default,
+{
+    fn from(set: &OrdSet<A>) -> Self {
+        set.iter().cloned().collect()
+    }
+}
+
 // Proptest
 #[cfg(any(test, feature = "proptest"))]
 #[doc(hidden)]
@@ -1178,7 +2664,123 @@ pub mod proptest {
 mod test {
     use super::*;
     use crate::proptest::*;
-    use ::proptest::proptest;
+    use ::proptest::{bool, collection, proptest};
+
+    #[test]
+    fn operators() {
+        let left = ordset![1, 2, 3];
+        let right = ordset![3, 4];
+        assert_eq!(ordset![1, 2, 3, 4], &left | &right);
+        assert_eq!(ordset![3], &left & &right);
+        assert_eq!(ordset![1, 2, 4], &left ^ &right);
+        assert_eq!(ordset![1, 2], &left - &right);
+        assert_eq!(ordset![4], right - left);
+    }
+
+    #[test]
+    fn from_ordered() {
+        for &size in &[0, 1, 63, 64, 65, 129, 4095, 4096, 5000, 100_000] {
+            let set = OrdSet::from_ordered(0..size);
+            assert_eq!(size, set.len());
+            assert!(set.iter().cloned().eq(0..size));
+            assert_eq!(set, (0..size).collect::<OrdSet<_>>());
+            let mut set = set;
+            for value in (0..size).step_by(3) {
+                assert_eq!(Some(value), set.remove(&value));
+            }
+            assert!(set
+                .iter()
+                .cloned()
+                .eq((0..size).filter(|value| value % 3 != 0)));
+            set.insert(size);
+            assert_eq!(Some(&size), set.get_max());
+        }
+    }
+
+    #[test]
+    fn borrowed_lookups() {
+        let mut set: OrdSet<String> = ["apple", "banana", "cherry"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert!(set.contains("banana"));
+        assert_eq!(Some(1), set.index_of("banana"));
+        assert_eq!(Some(&"banana".to_string()), set.get_prev("blueberry"));
+        assert_eq!(Some(&"cherry".to_string()), set.get_next("blueberry"));
+        let (left, right) = set.clone().split("banana");
+        assert_eq!(1, left.len());
+        assert_eq!(1, right.len());
+        assert_eq!(Some("apple".to_string()), set.remove("apple"));
+        assert!(!set.contains("apple"));
+    }
+
+    #[test]
+    fn set_operations_keep_values_from_other() {
+        #[derive(Clone, Debug)]
+        struct Tagged(i32, &'static str);
+        impl PartialEq for Tagged {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+        impl Eq for Tagged {}
+        impl PartialOrd for Tagged {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for Tagged {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+        let small: OrdSet<_> = (0..3).map(|i| Tagged(i * 100, "small")).collect();
+        let large: OrdSet<_> = (0..1000).map(|i| Tagged(i, "large")).collect();
+        let union = large.clone().union(small.clone());
+        assert!(small
+            .iter()
+            .all(|value: &Tagged| union.get_next(value).unwrap().1 == "small"));
+        let union = small.clone().union(large.clone());
+        assert!(union.iter().all(|value| value.1 == "large"));
+        let intersection = large.clone().intersection(small.clone());
+        assert!(intersection.iter().all(|value| value.1 == "small"));
+        let intersection = small.intersection(large);
+        assert!(intersection.iter().all(|value| value.1 == "large"));
+    }
+
+    #[test]
+    fn choose_is_uniform() {
+        use rand::{rngs::SmallRng, Rng, SeedableRng};
+        let mut rng = SmallRng::seed_from_u64(1);
+        let set: OrdSet<usize> = (0..10usize).collect();
+        let mut counts = [0; 10];
+        for _ in 0..10_000 {
+            counts[*set.choose(|len| rng.gen_range(0, len)).unwrap()] += 1;
+        }
+        assert!(counts.iter().all(|count| *count > 800 && *count < 1200));
+    }
+
+    #[test]
+    fn into_std() {
+        let set = ordset![3, 1, 2];
+        assert_eq!(vec![1, 2, 3], Vec::from(&set));
+        let btree_set: collections::BTreeSet<i32> = set.clone().into();
+        assert_eq!(set, btree_set);
+        let hash_set: collections::HashSet<i32> = set.clone().into();
+        assert_eq!(set, hash_set);
+    }
+
+    #[test]
+    fn rev_iter() {
+        // Big enough for the tree to be more than two levels deep.
+        let set: OrdSet<u32> = (0..100_000u32).collect();
+        assert!(set.iter().rev().cloned().eq((0..100_000).rev()));
+        assert!(set
+            .cloned_iter()
+            .rev()
+            .take(3)
+            .eq(vec![99_999, 99_998, 99_997]));
+    }
 
     #[test]
     fn match_strings_with_string_slices() {
@@ -1189,6 +2791,64 @@ mod test {
         assert!(!set.contains("foo"));
     }
 
+    #[test]
+    fn equality_with_std() {
+        let set = ordset![1, 2, 3];
+        assert_eq!(set, collections::BTreeSet::from_iter(vec![1, 2, 3]));
+        assert_eq!(set, collections::HashSet::<_>::from_iter(vec![3, 2, 1]));
+        assert_ne!(set, collections::BTreeSet::from_iter(vec![1, 2]));
+        assert_ne!(set, collections::HashSet::<_>::from_iter(vec![1, 2, 4]));
+    }
+
+    #[test]
+    fn product() {
+        let set1: OrdSet<i32> = (0..50).collect();
+        let set2: OrdSet<char> = "abcdefg".chars().collect();
+        let product = set1.product(&set2);
+        assert_eq!(350, product.len());
+        assert_eq!(350, product.root.check_sizes());
+        assert!(product.contains(&(25, 'd')));
+        assert!(!product.contains(&(25, 'h')));
+        let expected: Vec<(i32, char)> = (0..50)
+            .flat_map(|i| "abcdefg".chars().map(move |c| (i, c)))
+            .collect();
+        assert_eq!(expected, product.into_iter().collect::<Vec<_>>());
+        assert!(set1.product(&OrdSet::<char>::new()).is_empty());
+    }
+
+    #[test]
+    fn subsets() {
+        let set: OrdSet<i32> = (0..10).collect();
+        let subsets: Vec<OrdSet<i32>> = set.subsets().collect();
+        assert_eq!(1024, subsets.len());
+        assert_eq!(
+            1024,
+            subsets
+                .iter()
+                .cloned()
+                .collect::<OrdSet<OrdSet<i32>>>()
+                .len()
+        );
+        assert!(subsets.iter().all(|subset| subset.is_subset(&set)));
+        assert_eq!(
+            vec![OrdSet::new()],
+            OrdSet::<i32>::new().subsets().collect::<Vec<_>>()
+        );
+        for size in 0..12 {
+            let subsets: Vec<OrdSet<i32>> = set.subsets_of_size(size).collect();
+            let expected: Vec<OrdSet<i32>> = set
+                .subsets()
+                .filter(|subset| subset.len() == size)
+                .collect();
+            assert_eq!(expected.len(), subsets.len());
+            assert!(subsets.windows(2).all(|pair| pair[0] < pair[1]));
+            assert_eq!(
+                expected.into_iter().collect::<OrdSet<OrdSet<i32>>>(),
+                subsets.into_iter().collect::<OrdSet<OrdSet<i32>>>()
+            );
+        }
+    }
+
     #[test]
     fn ranged_iter() {
         let set: OrdSet<i32> = ordset![1, 2, 3, 4, 5];
@@ -1234,5 +2894,103 @@ mod test {
             let result: Vec<i32> = set.range(..).rev().cloned().collect();
             assert_eq!(expected, result);
         }
+
+        #[test]
+        fn is_disjoint(ref left in ord_set(0..1000i32, 0..100), ref right in ord_set(0..1000i32, 0..100)) {
+            let expected = left.iter().all(|value| !right.contains(value));
+            assert_eq!(expected, left.is_disjoint(right));
+            assert_eq!(expected, right.is_disjoint(left));
+        }
+
+        #[test]
+        fn map_monotonic(ref set in ord_set(0..10000i32, 0..1000)) {
+            let mapped = set.map_monotonic(|value| i64::from(*value) * 3 - 5);
+            assert_eq!(set.map(|value| i64::from(*value) * 3 - 5), mapped);
+            for value in set {
+                assert!(mapped.contains(&(i64::from(*value) * 3 - 5)));
+            }
+        }
+
+        #[test]
+        fn span(ref set in ord_set(0..1000i32, 0..1000), threshold in 0..1000i32) {
+            let below = |value: &i32| *value < threshold;
+            let (left, right) = set.span(below);
+            assert_eq!(set.iter().cloned().filter(below).collect::<OrdSet<_>>(), left);
+            assert_eq!(set.iter().cloned().filter(|value| !below(value)).collect::<OrdSet<_>>(), right);
+            assert_eq!(left, set.take_while(below));
+            assert_eq!(right, set.drop_while(below));
+        }
+
+        #[test]
+        fn merge_iters(ref left in ord_set(0..1000i32, 0..100), ref right in ord_set(0..1000i32, 0..100)) {
+            let collect = |it: &mut dyn Iterator<Item = &i32>| it.cloned().collect::<Vec<_>>();
+            let mut union: Vec<i32> = left.iter().chain(right.iter()).cloned().collect();
+            union.sort();
+            union.dedup();
+            let in_both = |value: &i32| left.contains(value) && right.contains(value);
+            let intersection: Vec<i32> = union.iter().cloned().filter(|value| in_both(value)).collect();
+            let difference: Vec<i32> = union.iter().cloned().filter(|value| !right.contains(value)).collect();
+            let symmetric: Vec<i32> = union.iter().cloned().filter(|value| !in_both(value)).collect();
+            assert_eq!(union, collect(&mut left.union_iter(right)));
+            assert_eq!(intersection, collect(&mut left.intersection_iter(right)));
+            assert_eq!(difference, collect(&mut left.difference_iter(right)));
+            assert_eq!(symmetric, collect(&mut left.symmetric_difference_iter(right)));
+        }
+
+        #[test]
+        fn index_access(ref input in ord_set(0..10000i32, 0..2000), ref ops in collection::vec((bool::ANY, 0..10000i32), 0..200)) {
+            let mut set = input.clone();
+            for (insert, value) in ops {
+                if *insert {
+                    set.insert(*value);
+                } else {
+                    set.remove(value);
+                }
+            }
+            assert_eq!(input.len(), input.root.check_sizes());
+            assert_eq!(set.len(), set.root.check_sizes());
+            for (index, value) in set.iter().enumerate() {
+                assert_eq!(Some(value), set.get_index(index));
+                assert_eq!(Some(index), set.index_of(value));
+            }
+            assert_eq!(None, set.get_index(set.len()));
+            for (_, value) in ops {
+                assert_eq!(set.iter().position(|other| other == value), set.index_of(value));
+            }
+        }
+
+        #[test]
+        fn set_operations(
+            ref large in ord_set(0..5000i32, 0..2000),
+            ref small in ord_set(0..5000i32, 0..40),
+            ref ops in collection::vec((bool::ANY, 0..5000i32), 0..50)
+        ) {
+            let std_large: collections::BTreeSet<i32> = large.iter().cloned().collect();
+            let std_small: collections::BTreeSet<i32> = small.iter().cloned().collect();
+            // Check the result, and that the tree it came out with still
+            // takes updates.
+            let check = |mut set: OrdSet<i32>, values: Vec<&i32>| {
+                let mut expected: collections::BTreeSet<i32> = values.into_iter().cloned().collect();
+                assert_eq!(expected.len(), set.root.check_sizes());
+                assert_eq!(expected.len(), set.len());
+                assert_eq!(expected.iter().collect::<Vec<_>>(), set.iter().collect::<Vec<_>>());
+                for (insert, value) in ops {
+                    if *insert {
+                        assert_eq!(expected.replace(*value), set.insert(*value));
+                    } else {
+                        assert_eq!(expected.take(value), set.remove(value));
+                    }
+                }
+                assert_eq!(expected.len(), set.root.check_sizes());
+                assert_eq!(expected.len(), set.len());
+                assert_eq!(expected.iter().collect::<Vec<_>>(), set.iter().collect::<Vec<_>>());
+            };
+            for &(left, right, std_left, std_right) in &[(large, small, &std_large, &std_small), (small, large, &std_small, &std_large)] {
+                check(left.clone().union(right.clone()), std_left.union(std_right).collect());
+                check(left.clone().intersection(right.clone()), std_left.intersection(std_right).collect());
+                check(left.clone().relative_complement(right.clone()), std_left.difference(std_right).collect());
+                check(left.clone().symmetric_difference(right.clone()), std_left.symmetric_difference(std_right).collect());
+            }
+        }
     }
 }
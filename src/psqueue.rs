@@ -0,0 +1,395 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! A persistent priority search queue.
+//!
+//! A [`PSQueue`][PSQueue] is a map from keys to priorities which is
+//! also a priority queue: you can look up and update the priority of
+//! any key, and find or remove the key with the lowest priority, all in
+//! O(log n) time. This is the combination of operations needed by
+//! algorithms like Dijkstra's and A*, without having to pair a map with
+//! a heap and skip over stale heap entries.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[macro_use] extern crate im;
+//! # use im::psqueue::PSQueue;
+//! let mut queue = PSQueue::new();
+//! queue.insert("a", 5);
+//! queue.insert("b", 3);
+//! queue.insert("c", 4);
+//! assert!(queue.decrease_priority(&"a", 1));
+//! assert_eq!(Some(("a", 1)), queue.pop_min());
+//! assert_eq!(Some(("b", 3)), queue.pop_min());
+//! assert_eq!(Some(&4), queue.get(&"c"));
+//! ```
+//!
+//! [PSQueue]: struct.PSQueue.html
+
+use std::borrow::Borrow;
+use std::fmt::{Debug, Error, Formatter};
+use std::iter::FromIterator;
+
+use crate::ordmap::OrdMap;
+use crate::ordset::{Iter as SetIter, OrdSet};
+
+/// A persistent priority search queue.
+///
+/// Keys of type `K` are mapped to priorities of type `P`. When several
+/// keys have the same priority, the smallest key comes first.
+///
+/// Please see the [module documentation][module] for more details.
+///
+/// [module]: index.html
+pub struct PSQueue<K, P> {
+    priorities: OrdMap<K, P>,
+    queue: OrdSet<(P, K)>,
+}
+
+impl<K, P> PSQueue<K, P>
+where
+    K: Ord + Clone,
+    P: Ord + Clone,
+{
+    /// Construct an empty queue.
+    #[must_use]
+    pub fn new() -> Self {
+        PSQueue {
+            priorities: OrdMap::new(),
+            queue: OrdSet::new(),
+        }
+    }
+
+    /// Test whether a queue is empty.
+    ///
+    /// Time: O(1)
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.priorities.is_empty()
+    }
+
+    /// Get the number of keys in a queue.
+    ///
+    /// Time: O(1)
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.priorities.len()
+    }
+
+    /// Get the priority of a key.
+    ///
+    /// Time: O(log n)
+    #[must_use]
+    pub fn get<BK>(&self, key: &BK) -> Option<&P>
+    where
+        BK: Ord + ?Sized,
+        K: Borrow<BK>,
+    {
+        self.priorities.get(key)
+    }
+
+    /// Test whether a queue contains a key.
+    ///
+    /// Time: O(log n)
+    #[must_use]
+    pub fn contains_key<BK>(&self, key: &BK) -> bool
+    where
+        BK: Ord + ?Sized,
+        K: Borrow<BK>,
+    {
+        self.priorities.contains_key(key)
+    }
+
+    /// Get the key with the lowest priority, along with its priority,
+    /// without removing it.
+    ///
+    /// Time: O(log n)
+    #[must_use]
+    pub fn peek_min(&self) -> Option<(&K, &P)> {
+        self.queue.get_min().map(|(priority, key)| (key, priority))
+    }
+
+    /// Remove the key with the lowest priority from a queue, and return
+    /// it along with its priority.
+    ///
+    /// Time: O(log n)
+    pub fn pop_min(&mut self) -> Option<(K, P)> {
+        let (priority, key) = self.queue.remove_min()?;
+        self.priorities.remove(&key);
+        Some((key, priority))
+    }
+
+    /// Insert a key into a queue with a given priority, replacing its
+    /// priority if it's already in the queue, and return its old
+    /// priority.
+    ///
+    /// Time: O(log n)
+    pub fn insert(&mut self, key: K, priority: P) -> Option<P> {
+        let old = self.priorities.insert(key.clone(), priority.clone());
+        if let Some(ref old_priority) = old {
+            self.queue.remove(&(old_priority.clone(), key.clone()));
+        }
+        self.queue.insert((priority, key));
+        old
+    }
+
+    /// Lower the priority of a key which is already in a queue.
+    ///
+    /// Returns `true` if the priority was lowered, and `false` if the
+    /// key isn't in the queue or its priority is already no higher than
+    /// `priority`.
+    ///
+    /// Time: O(log n)
+    pub fn decrease_priority<BK>(&mut self, key: &BK, priority: P) -> bool
+    where
+        BK: Ord + ?Sized,
+        K: Borrow<BK>,
+    {
+        match self.priorities.get_key_value(key) {
+            Some((key, old_priority)) if priority < *old_priority => {
+                let key = key.clone();
+                self.insert(key, priority);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Remove a key from a queue, and return its priority.
+    ///
+    /// Time: O(log n)
+    pub fn remove<BK>(&mut self, key: &BK) -> Option<P>
+    where
+        BK: Ord + ?Sized,
+        K: Borrow<BK>,
+    {
+        let (key, priority) = self.priorities.remove_with_key(key)?;
+        self.queue.remove(&(priority.clone(), key));
+        Some(priority)
+    }
+
+    /// Construct a new queue by inserting a key with a given priority
+    /// into a queue.
+    ///
+    /// Time: O(log n)
+    #[must_use]
+    pub fn update(&self, key: K, priority: P) -> Self {
+        let mut out = self.clone();
+        out.insert(key, priority);
+        out
+    }
+
+    /// Construct a new queue without the key with the lowest priority,
+    /// and return that key and its priority along with the new queue.
+    ///
+    /// Time: O(log n)
+    #[must_use]
+    pub fn without_min(&self) -> (Option<(K, P)>, Self) {
+        let mut out = self.clone();
+        let min = out.pop_min();
+        (min, out)
+    }
+
+    /// Get an iterator over the keys and priorities in a queue, from
+    /// the lowest priority to the highest.
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_, K, P> {
+        Iter {
+            it: self.queue.iter(),
+        }
+    }
+}
+
+// Core traits
+
+impl<K, P> Clone for PSQueue<K, P> {
+    /// Clone a queue.
+    ///
+    /// Time: O(1)
+    #[inline]
+    fn clone(&self) -> Self {
+        PSQueue {
+            priorities: self.priorities.clone(),
+            queue: self.queue.clone(),
+        }
+    }
+}
+
+impl<K, P> Default for PSQueue<K, P>
+where
+    K: Ord + Clone,
+    P: Ord + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, P> PartialEq for PSQueue<K, P>
+where
+    K: Ord + Clone,
+    P: Ord + Clone,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.priorities == other.priorities
+    }
+}
+
+impl<K, P> Eq for PSQueue<K, P>
+where
+    K: Ord + Clone,
+    P: Ord + Clone,
+{
+}
+
+impl<K, P> Debug for PSQueue<K, P>
+where
+    K: Ord + Clone + Debug,
+    P: Ord + Clone + Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, P> Extend<(K, P)> for PSQueue<K, P>
+where
+    K: Ord + Clone,
+    P: Ord + Clone,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (K, P)>,
+    {
+        for (key, priority) in iter {
+            self.insert(key, priority);
+        }
+    }
+}
+
+impl<K, P> FromIterator<(K, P)> for PSQueue<K, P>
+where
+    K: Ord + Clone,
+    P: Ord + Clone,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K, P)>,
+    {
+        let mut queue = Self::new();
+        queue.extend(iter);
+        queue
+    }
+}
+
+impl<'a, K, P> IntoIterator for &'a PSQueue<K, P>
+where
+    K: Ord + Clone,
+    P: Ord + Clone,
+{
+    type Item = (&'a K, &'a P);
+    type IntoIter = Iter<'a, K, P>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// Iterators
+
+/// An iterator over the keys and priorities in a queue, from the lowest
+/// priority to the highest.
+pub struct Iter<'a, K, P> {
+    it: SetIter<'a, (P, K)>,
+}
+
+impl<'a, K, P> Iterator for Iter<'a, K, P>
+where
+    K: Ord,
+    P: Ord,
+{
+    type Item = (&'a K, &'a P);
+
+    /// Advance the iterator and return the next value.
+    ///
+    /// Time: O(1)*
+    fn next(&mut self) -> Option<Self::Item> {
+        self.it.next().map(|(priority, key)| (key, priority))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+impl<'a, K, P> DoubleEndedIterator for Iter<'a, K, P>
+where
+    K: Ord,
+    P: Ord,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.it.next_back().map(|(priority, key)| (key, priority))
+    }
+}
+
+impl<'a, K, P> ExactSizeIterator for Iter<'a, K, P>
+where
+    K: Ord,
+    P: Ord,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::proptest::collection;
+    use ::proptest::num::{i16, u8};
+    use ::proptest::proptest;
+    use std::collections::BTreeMap;
+
+    proptest! {
+        #[test]
+        fn matches_map(ref actions in collection::vec((u8::ANY, 0..20u8, i16::ANY), 0..500)) {
+            let mut queue = PSQueue::new();
+            let mut expected: BTreeMap<u8, i16> = BTreeMap::new();
+            for (action, key, priority) in actions {
+                match action % 4 {
+                    0 | 1 => assert_eq!(expected.insert(*key, *priority), queue.insert(*key, *priority)),
+                    2 => {
+                        let lowered = match expected.get_mut(key) {
+                            Some(old) if *priority < *old => {
+                                *old = *priority;
+                                true
+                            }
+                            _ => false,
+                        };
+                        assert_eq!(lowered, queue.decrease_priority(key, *priority));
+                    }
+                    _ => {
+                        let min = expected
+                            .iter()
+                            .map(|(key, priority)| (*priority, *key))
+                            .min()
+                            .map(|(priority, key)| (key, priority));
+                        if let Some((key, _)) = min {
+                            expected.remove(&key);
+                        }
+                        assert_eq!(min, queue.pop_min());
+                    }
+                }
+                assert_eq!(expected.len(), queue.len());
+                for (key, priority) in &expected {
+                    assert_eq!(Some(priority), queue.get(key));
+                }
+            }
+            let mut ordered: Vec<(i16, u8)> = expected.iter().map(|(key, priority)| (*priority, *key)).collect();
+            ordered.sort();
+            let found: Vec<(i16, u8)> = queue.iter().map(|(key, priority)| (*priority, *key)).collect();
+            assert_eq!(ordered, found);
+        }
+    }
+}
@@ -6,6 +6,7 @@ use serde::de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
 use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
 use std::fmt;
 use std::hash::{BuildHasher, Hash};
+use std::iter::once;
 use std::marker::PhantomData;
 use std::ops::Deref;
 
@@ -17,7 +18,7 @@ use crate::vector::Vector;
 
 struct SeqVisitor<'de, S, A>
 where
-    S: From<Vec<A>>,
+    S: Default + Extend<A>,
     A: Deserialize<'de>,
 {
     phantom_s: PhantomData<S>,
@@ -27,7 +28,7 @@ where
 
 impl<'de, S, A> SeqVisitor<'de, S, A>
 where
-    S: From<Vec<A>>,
+    S: Default + Extend<A>,
     A: Deserialize<'de>,
 {
     pub(crate) fn new() -> SeqVisitor<'de, S, A> {
@@ -41,7 +42,7 @@ where
 
 impl<'de, S, A> Visitor<'de> for SeqVisitor<'de, S, A>
 where
-    S: From<Vec<A>>,
+    S: Default + Extend<A>,
     A: Deserialize<'de>,
 {
     type Value = S;
@@ -54,20 +55,17 @@ where
     where
         Access: SeqAccess<'de>,
     {
-        let mut v: Vec<A> = match access.size_hint() {
-            None => Vec::new(),
-            Some(l) => Vec::with_capacity(l),
-        };
+        let mut out = S::default();
         while let Some(i) = access.next_element()? {
-            v.push(i)
+            out.extend(once(i))
         }
-        Ok(From::from(v))
+        Ok(out)
     }
 }
 
 struct MapVisitor<'de, S, K, V>
 where
-    S: From<Vec<(K, V)>>,
+    S: Default + Extend<(K, V)>,
     K: Deserialize<'de>,
     V: Deserialize<'de>,
 {
@@ -79,7 +77,7 @@ where
 
 impl<'de, S, K, V> MapVisitor<'de, S, K, V>
 where
-    S: From<Vec<(K, V)>>,
+    S: Default + Extend<(K, V)>,
     K: Deserialize<'de>,
     V: Deserialize<'de>,
 {
@@ -95,7 +93,7 @@ where
 
 impl<'de, S, K, V> Visitor<'de> for MapVisitor<'de, S, K, V>
 where
-    S: From<Vec<(K, V)>>,
+    S: Default + Extend<(K, V)>,
     K: Deserialize<'de>,
     V: Deserialize<'de>,
 {
@@ -109,17 +107,18 @@ where
     where
         Access: MapAccess<'de>,
     {
-        let mut v: Vec<(K, V)> = match access.size_hint() {
-            None => Vec::new(),
-            Some(l) => Vec::with_capacity(l),
-        };
+        let mut out = S::default();
         while let Some(i) = access.next_entry()? {
-            v.push(i)
+            out.extend(once(i))
         }
-        Ok(From::from(v))
+        Ok(out)
     }
 }
 
+// Elements are inserted into the collection as they're deserialised,
+// rather than buffered in a `Vec` first, so that deserialising a large
+// collection doesn't need memory for two copies of it.
+
 // Set
 
 impl<'de, A: Deserialize<'de> + Ord + Clone> Deserialize<'de> for OrdSet<A> {
@@ -264,6 +263,15 @@ mod test {
     use ::proptest::proptest;
     use serde_json::{from_str, to_string};
 
+    #[test]
+    fn de_large_vector() {
+        let v: Vector<usize> = (0..10_000).collect();
+        assert_eq!(
+            v,
+            from_str::<Vector<usize>>(&to_string(&v).unwrap()).unwrap()
+        );
+    }
+
     proptest! {
         #[test]
         fn ser_ordset(ref v in ord_set(i32::ANY, 0..100)) {
@@ -0,0 +1,331 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! A persistent slot map with stable keys.
+//!
+//! A [`SlotMap`][SlotMap] stores values in slots and hands out a
+//! [`Key`][Key] for each value inserted into it. A key stays valid
+//! until its value is removed, and a key to a removed value never
+//! refers to another value, even if the slot is later reused, because
+//! each slot keeps a generation counter which is bumped every time its
+//! value is removed.
+//!
+//! The slots are stored in a [`Vector`][Vector], so lookups and updates
+//! are O(log n), and cloning a slot map is O(1), which makes it cheap
+//! to keep snapshots around for things like undo and redo.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[macro_use] extern crate im;
+//! # use im::slotmap::SlotMap;
+//! let mut entities = SlotMap::new();
+//! let player = entities.insert("player");
+//! let monster = entities.insert("monster");
+//! let snapshot = entities.clone();
+//! assert_eq!(Some("monster"), entities.remove(monster));
+//! let ghost = entities.insert("ghost");
+//! assert_eq!(None, entities.get(monster));
+//! assert_eq!(Some(&"ghost"), entities.get(ghost));
+//! assert_eq!(Some(&"monster"), snapshot.get(monster));
+//! assert_eq!(Some(&"player"), snapshot.get(player));
+//! ```
+//!
+//! [SlotMap]: struct.SlotMap.html
+//! [Key]: struct.Key.html
+//! [Vector]: ../vector/struct.Vector.html
+
+use std::fmt::{Debug, Error, Formatter};
+use std::iter::{Enumerate, FusedIterator};
+use std::ops::{Index, IndexMut};
+
+use crate::vector::{Iter as VectorIter, Vector};
+
+/// A key to a value in a [`SlotMap`][SlotMap].
+///
+/// [SlotMap]: struct.SlotMap.html
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Key {
+    index: usize,
+    generation: u64,
+}
+
+#[derive(Clone)]
+struct Slot<V> {
+    generation: u64,
+    value: Option<V>,
+}
+
+/// A persistent slot map with stable keys.
+///
+/// Please see the [module documentation][module] for more details.
+///
+/// [module]: index.html
+pub struct SlotMap<V> {
+    slots: Vector<Slot<V>>,
+    free: Vector<usize>,
+    size: usize,
+}
+
+impl<V: Clone> SlotMap<V> {
+    /// Construct an empty slot map.
+    #[must_use]
+    pub fn new() -> Self {
+        SlotMap {
+            slots: Vector::new(),
+            free: Vector::new(),
+            size: 0,
+        }
+    }
+
+    /// Test whether a slot map is empty.
+    ///
+    /// Time: O(1)
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the number of values in a slot map.
+    ///
+    /// Time: O(1)
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    /// Test whether a key refers to a value in a slot map.
+    ///
+    /// Time: O(log n)
+    #[must_use]
+    pub fn contains_key(&self, key: Key) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Get the value a key refers to, or `None` if it has been
+    /// removed.
+    ///
+    /// Time: O(log n)
+    #[must_use]
+    pub fn get(&self, key: Key) -> Option<&V> {
+        match self.slots.get(key.index) {
+            Some(slot) if slot.generation == key.generation => slot.value.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Get a mutable reference to the value a key refers to, or `None`
+    /// if it has been removed.
+    ///
+    /// Time: O(log n)
+    #[must_use]
+    pub fn get_mut(&mut self, key: Key) -> Option<&mut V> {
+        match self.slots.get_mut(key.index) {
+            Some(slot) if slot.generation == key.generation => slot.value.as_mut(),
+            _ => None,
+        }
+    }
+
+    /// Insert a value into a slot map, and return its key.
+    ///
+    /// Slots left empty by removed values are reused before the map
+    /// grows.
+    ///
+    /// Time: O(log n)
+    pub fn insert(&mut self, value: V) -> Key {
+        self.size += 1;
+        match self.free.pop_back() {
+            Some(index) => {
+                let slot = &mut self.slots[index];
+                slot.value = Some(value);
+                Key {
+                    index,
+                    generation: slot.generation,
+                }
+            }
+            None => {
+                self.slots.push_back(Slot {
+                    generation: 0,
+                    value: Some(value),
+                });
+                Key {
+                    index: self.slots.len() - 1,
+                    generation: 0,
+                }
+            }
+        }
+    }
+
+    /// Remove the value a key refers to from a slot map, and return
+    /// it.
+    ///
+    /// The key, and any copies of it, will no longer refer to anything
+    /// in this map.
+    ///
+    /// Time: O(log n)
+    pub fn remove(&mut self, key: Key) -> Option<V> {
+        if !self.contains_key(key) {
+            return None;
+        }
+        let slot = &mut self.slots[key.index];
+        slot.generation += 1;
+        self.free.push_back(key.index);
+        self.size -= 1;
+        slot.value.take()
+    }
+
+    /// Construct a new slot map without the value a key refers to.
+    ///
+    /// Time: O(log n)
+    #[must_use]
+    pub fn without(&self, key: Key) -> Self {
+        let mut out = self.clone();
+        out.remove(key);
+        out
+    }
+
+    /// Get an iterator over the keys and values in a slot map, in
+    /// order of their slots.
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_, V> {
+        Iter {
+            it: self.slots.iter().enumerate(),
+            remaining: self.size,
+        }
+    }
+}
+
+// Core traits
+
+impl<V: Clone> Clone for SlotMap<V> {
+    /// Clone a slot map.
+    ///
+    /// Time: O(1)
+    #[inline]
+    fn clone(&self) -> Self {
+        SlotMap {
+            slots: self.slots.clone(),
+            free: self.free.clone(),
+            size: self.size,
+        }
+    }
+}
+
+impl<V: Clone> Default for SlotMap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: Clone + Debug> Debug for SlotMap<V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<V: Clone> Index<Key> for SlotMap<V> {
+    type Output = V;
+
+    fn index(&self, key: Key) -> &Self::Output {
+        match self.get(key) {
+            None => panic!("SlotMap::index: invalid key"),
+            Some(value) => value,
+        }
+    }
+}
+
+impl<V: Clone> IndexMut<Key> for SlotMap<V> {
+    fn index_mut(&mut self, key: Key) -> &mut Self::Output {
+        match self.get_mut(key) {
+            None => panic!("SlotMap::index_mut: invalid key"),
+            Some(value) => value,
+        }
+    }
+}
+
+impl<'a, V: Clone> IntoIterator for &'a SlotMap<V> {
+    type Item = (Key, &'a V);
+    type IntoIter = Iter<'a, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// Iterators
+
+/// An iterator over the keys and values in a slot map.
+pub struct Iter<'a, V> {
+    it: Enumerate<VectorIter<'a, Slot<V>>>,
+    remaining: usize,
+}
+
+impl<'a, V: Clone> Iterator for Iter<'a, V> {
+    type Item = (Key, &'a V);
+
+    /// Advance the iterator and return the next value.
+    ///
+    /// Time: O(1)*
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, slot) in &mut self.it {
+            if let Some(ref value) = slot.value {
+                self.remaining -= 1;
+                let key = Key {
+                    index,
+                    generation: slot.generation,
+                };
+                return Some((key, value));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, V: Clone> ExactSizeIterator for Iter<'a, V> {}
+
+impl<'a, V: Clone> FusedIterator for Iter<'a, V> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::proptest::collection;
+    use ::proptest::num::{i16, u8};
+    use ::proptest::proptest;
+
+    proptest! {
+        #[test]
+        fn keys_stay_stable(ref actions in collection::vec((u8::ANY, i16::ANY), 0..500)) {
+            let mut map = SlotMap::new();
+            let mut live: Vec<(Key, i16)> = Vec::new();
+            let mut dead: Vec<Key> = Vec::new();
+            for (action, value) in actions {
+                if action % 3 == 2 && !live.is_empty() {
+                    let (key, value) = live.remove(*action as usize % live.len());
+                    assert_eq!(Some(value), map.remove(key));
+                    assert_eq!(None, map.remove(key));
+                    dead.push(key);
+                } else {
+                    live.push((map.insert(*value), *value));
+                }
+                assert_eq!(live.len(), map.len());
+                for (key, value) in &live {
+                    assert_eq!(Some(value), map.get(*key));
+                }
+                for key in &dead {
+                    assert!(!map.contains_key(*key));
+                }
+            }
+            let mut found: Vec<(Key, i16)> = map.iter().map(|(key, value)| (key, *value)).collect();
+            found.sort();
+            live.sort();
+            assert_eq!(live, found);
+        }
+    }
+}
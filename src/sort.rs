@@ -2,16 +2,12 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+use crate::util::gen_range;
 use crate::vector::FocusMut;
 use rand_core::{RngCore, SeedableRng};
 use std::cmp::Ordering;
 use std::mem;
 
-fn gen_range<R: RngCore>(rng: &mut R, min: usize, max: usize) -> usize {
-    let range = max - min;
-    min + (rng.next_u64() as usize % range)
-}
-
 // Ported from the Java version at:
 //    http://www.cs.princeton.edu/~rs/talks/QuicksortIsOptimal.pdf
 // There are a couple of modifications made here to make it more performant on the tree structure of
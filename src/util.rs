@@ -4,7 +4,10 @@
 
 // Every codebase needs a `util` module.
 
+use rand_core::RngCore;
 use std::cmp::Ordering;
+use std::iter::FusedIterator;
+use std::mem;
 use std::ops::{Bound, IndexMut, Range, RangeBounds};
 use std::ptr;
 
@@ -105,6 +108,111 @@ where
     start_index..end_index
 }
 
+pub(crate) fn gen_range<R: RngCore>(rng: &mut R, min: usize, max: usize) -> usize {
+    let range = max - min;
+    min + (rng.next_u64() as usize % range)
+}
+
+// Decide whether to combine two ordered trees by inserting or removing the
+// values of the smaller one in the larger one, at O(m log n), rather than
+// merging both in order into a new tree, at O(n + m). Point updates also
+// leave the parts of the larger tree they don't touch shared with it.
+pub(crate) fn prefer_point_updates(smaller: usize, larger: usize) -> bool {
+    let depth = (mem::size_of::<usize>() * 8) as u32 - larger.leading_zeros();
+    smaller.saturating_mul(depth as usize) <= larger + smaller
+}
+
+// An item from a walk over two collections side by side: found only on
+// the left, only on the right, or on both sides.
+pub(crate) enum Merged<L, R> {
+    Left(L),
+    Right(R),
+    Both(L, R),
+}
+
+// Walks two sorted iterators side by side in order, pairing up the items
+// which `cmp` says are equal. Each side's next item is kept around to
+// compare it with the other side's, which is why the item types are
+// parameters of their own.
+pub(crate) struct MergeIter<L, R, A, B, F> {
+    left: L,
+    right: R,
+    next_left: Option<A>,
+    next_right: Option<B>,
+    cmp: F,
+}
+
+impl<L, R, F> MergeIter<L, R, L::Item, R::Item, F>
+where
+    L: Iterator,
+    R: Iterator,
+    F: FnMut(&L::Item, &R::Item) -> Ordering,
+{
+    pub(crate) fn new(mut left: L, mut right: R, cmp: F) -> Self {
+        MergeIter {
+            next_left: left.next(),
+            next_right: right.next(),
+            left,
+            right,
+            cmp,
+        }
+    }
+
+    // The number of items left on each side.
+    pub(crate) fn lens(&self) -> (usize, usize)
+    where
+        L: ExactSizeIterator,
+        R: ExactSizeIterator,
+    {
+        (
+            self.left.len() + self.next_left.iter().count(),
+            self.right.len() + self.next_right.iter().count(),
+        )
+    }
+}
+
+impl<L, R, F> Iterator for MergeIter<L, R, L::Item, R::Item, F>
+where
+    L: Iterator,
+    R: Iterator,
+    F: FnMut(&L::Item, &R::Item) -> Ordering,
+{
+    type Item = Merged<L::Item, R::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let order = match (&self.next_left, &self.next_right) {
+            (None, None) => return None,
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(left), Some(right)) => (self.cmp)(left, right),
+        };
+        match order {
+            Ordering::Less => {
+                let value = mem::replace(&mut self.next_left, self.left.next());
+                value.map(Merged::Left)
+            }
+            Ordering::Greater => {
+                let value = mem::replace(&mut self.next_right, self.right.next());
+                value.map(Merged::Right)
+            }
+            Ordering::Equal => {
+                let right = mem::replace(&mut self.next_right, self.right.next())?;
+                let left = mem::replace(&mut self.next_left, self.left.next())?;
+                Some(Merged::Both(left, right))
+            }
+        }
+    }
+}
+
+// Once a side runs out, its iterator is never called again.
+impl<L, R, F> FusedIterator for MergeIter<L, R, L::Item, R::Item, F>
+where
+    L: Iterator,
+    R: Iterator,
+    F: FnMut(&L::Item, &R::Item) -> Ordering,
+{
+}
+
 macro_rules! def_pool {
     ($name:ident<$($arg:tt),*>, $pooltype:ty) => {
         /// A memory pool for the appropriate node type.
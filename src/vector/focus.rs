@@ -79,7 +79,7 @@ use crate::vector::{
 /// assert_eq!(499500, sum);
 /// ```
 ///
-/// [Vector]: enum.Vector.html
+/// [Vector]: struct.Vector.html
 /// [Iter]: struct.Iter.html
 /// [narrow]: #method.narrow
 /// [split_at]: #method.split_at
@@ -96,7 +96,7 @@ where
 {
     /// Construct a `Focus` for a [`Vector`][Vector].
     ///
-    /// [Vector]: enum.Vector.html
+    /// [Vector]: struct.Vector.html
     pub fn new(vector: &'a Vector<A>) -> Self {
         match &vector.vector {
             Inline(_, chunk) => Focus::Single(chunk),
@@ -107,7 +107,7 @@ where
 
     /// Get the length of the focused [`Vector`][Vector].
     ///
-    /// [Vector]: enum.Vector.html
+    /// [Vector]: struct.Vector.html
     pub fn len(&self) -> usize {
         match self {
             Focus::Single(chunk) => chunk.len(),
@@ -117,7 +117,7 @@ where
 
     /// Test if the focused [`Vector`][Vector] is empty.
     ///
-    /// [Vector]: enum.Vector.html
+    /// [Vector]: struct.Vector.html
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
@@ -172,7 +172,7 @@ where
     /// ```
     ///
     /// [slice::split_at]: https://doc.rust-lang.org/std/primitive.slice.html#method.split_at
-    /// [Vector::split_at]: enum.Vector.html#method.split_at
+    /// [Vector::split_at]: struct.Vector.html#method.split_at
     pub fn narrow<R>(self, range: R) -> Self
     where
         R: RangeBounds<usize>,
@@ -214,7 +214,7 @@ where
     /// ```
     ///
     /// [slice::split_at]: https://doc.rust-lang.org/std/primitive.slice.html#method.split_at
-    /// [Vector::split_at]: enum.Vector.html#method.split_at
+    /// [Vector::split_at]: struct.Vector.html#method.split_at
     pub fn split_at(self, index: usize) -> (Self, Self) {
         if index >= self.len() {
             panic!("vector::Focus::split_at: index out of bounds");
@@ -646,7 +646,7 @@ where
     /// ```
     ///
     /// [slice::split_at]: https://doc.rust-lang.org/std/primitive.slice.html#method.split_at
-    /// [Vector::split_at]: enum.Vector.html#method.split_at
+    /// [Vector::split_at]: struct.Vector.html#method.split_at
     pub fn narrow<R>(self, range: R) -> Self
     where
         R: RangeBounds<usize>,
@@ -695,7 +695,7 @@ where
     /// ```
     ///
     /// [slice::split_at]: https://doc.rust-lang.org/std/primitive.slice.html#method.split_at
-    /// [Vector::split_at]: enum.Vector.html#method.split_at
+    /// [Vector::split_at]: struct.Vector.html#method.split_at
     #[allow(clippy::redundant_clone)]
     pub fn split_at(self, index: usize) -> (Self, Self) {
         if index > self.len() {
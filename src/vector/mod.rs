@@ -38,17 +38,32 @@
 //! full data structure once you go past the chunk size. This makes them
 //! perform identically to [`Vec`][Vec] at small sizes.
 //!
+//! ## Byte Buffers
+//!
+//! A `Vector<u8>` makes a good persistent byte buffer: cutting it up
+//! with [`slice`][slice] or [`split_at`][split_at] and joining buffers
+//! with [`append`][append] are O(log n), and share the underlying
+//! chunks rather than copying them. It implements
+//! [`io::Write`][io::Write], so you can write into it directly, and it
+//! converts to and from [`Vec<u8>`][Vec] and `&[u8]` using `From`.
+//!
 //! [rrbpaper]: https://infoscience.epfl.ch/record/213452/files/rrbvector.pdf
 //! [chunkedseq]: http://deepsea.inria.fr/pasl/chunkedseq.pdf
 //! [Vec]: https://doc.rust-lang.org/std/vec/struct.Vec.html
 //! [VecDeque]: https://doc.rust-lang.org/std/collections/struct.VecDeque.html
+//! [io::Write]: https://doc.rust-lang.org/std/io/trait.Write.html
+//! [slice]: struct.Vector.html#method.slice
+//! [split_at]: struct.Vector.html#method.split_at
+//! [append]: struct.Vector.html#method.append
 
 use std::borrow::Borrow;
 use std::cmp::Ordering;
+use std::collections::VecDeque;
 use std::fmt::{Debug, Error, Formatter};
 use std::hash::{Hash, Hasher};
+use std::io;
 use std::iter::Sum;
-use std::iter::{FromIterator, FusedIterator};
+use std::iter::{Cloned, FromIterator, FusedIterator, Rev};
 use std::mem::{replace, swap};
 use std::ops::{Add, Index, IndexMut, RangeBounds};
 
@@ -341,6 +356,77 @@ impl<A: Clone> Vector<A> {
         }
     }
 
+    /// Get a slice of the contents of a vector, if they're stored
+    /// contiguously in memory.
+    ///
+    /// This is always the case for vectors which are inlined or fit in a
+    /// single chunk, but vectors which have grown larger than that and
+    /// then shrunk again may still be spread over several chunks. Use
+    /// [`make_contiguous()`][make_contiguous] to rearrange those.
+    ///
+    /// Time: O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::Vector;
+    /// let vec = vector![1, 2, 3];
+    /// assert_eq!(Some(&[1, 2, 3][..]), vec.as_slice());
+    /// let vec: Vector<i32> = (0..10000).collect();
+    /// assert_eq!(None, vec.as_slice());
+    /// ```
+    ///
+    /// [make_contiguous]: #method.make_contiguous
+    #[must_use]
+    pub fn as_slice(&self) -> Option<&[A]> {
+        match &self.vector {
+            Inline(_, chunk) => Some(chunk),
+            Single(_, chunk) => Some(chunk.as_slice()),
+            Full(_, tree) => [&tree.outer_f, &tree.inner_f, &tree.inner_b, &tree.outer_b]
+                .iter()
+                .find(|chunk| chunk.len() == tree.length)
+                .map(|chunk| chunk.as_slice()),
+        }
+    }
+
+    /// Rearrange the contents of a vector so that they're stored
+    /// contiguously in memory, and get a mutable slice of them.
+    ///
+    /// Returns `None`, leaving the vector unchanged, if it's too large to
+    /// fit in a single chunk. Otherwise, if the contents are spread over
+    /// several chunks, they're copied into a single chunk, and if that
+    /// chunk is shared with another vector, it's copied so that changes
+    /// to the slice don't affect the other vector.
+    ///
+    /// Time: O(n) if the contents need to be moved, otherwise O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::Vector;
+    /// let mut vec: Vector<i32> = (0..10000).collect();
+    /// vec.truncate(5);
+    /// vec.make_contiguous().unwrap().reverse();
+    /// assert_eq!(vector![4, 3, 2, 1, 0], vec);
+    /// ```
+    pub fn make_contiguous(&mut self) -> Option<&mut [A]> {
+        if self.len() > CHUNK_SIZE {
+            return None;
+        }
+        if let Full(pool, _) = &self.vector {
+            let pool = pool.clone();
+            let chunk: Chunk<A> = self.iter().cloned().collect();
+            self.vector = Single(pool.clone(), PoolRef::new(&pool.value_pool, chunk));
+        }
+        match &mut self.vector {
+            Inline(_, chunk) => Some(chunk),
+            Single(pool, chunk) => Some(PoolRef::make_mut(&pool.value_pool, chunk).as_mut_slice()),
+            Full(_, _) => unreachable!(),
+        }
+    }
+
     /// Test whether two vectors refer to the same content in memory.
     ///
     /// This uses the following rules to determine equality:
@@ -388,6 +474,38 @@ impl<A: Clone> Vector<A> {
         Iter::new(self)
     }
 
+    /// Get an iterator over clones of the values in a vector.
+    ///
+    /// Time: O(1)
+    #[inline]
+    pub fn cloned_iter(&self) -> Cloned<Iter<'_, A>> {
+        self.iter().cloned()
+    }
+
+    /// Get a view of a vector in reverse order.
+    ///
+    /// The view borrows the vector rather than copying it, and indexes
+    /// and iterates over it from back to front.
+    ///
+    /// Time: O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::vector::Vector;
+    /// let vec = vector![1, 2, 3];
+    /// let reversed = vec.reversed();
+    /// assert_eq!(Some(&3), reversed.front());
+    /// assert_eq!(1, reversed[2]);
+    /// assert_eq!(vec![3, 2, 1], reversed.iter().cloned().collect::<Vec<_>>());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn reversed(&self) -> Reversed<'_, A> {
+        Reversed { vector: self }
+    }
+
     /// Get a mutable iterator over a vector.
     ///
     /// Time: O(1)
@@ -427,10 +545,117 @@ impl<A: Clone> Vector<A> {
         ChunksMut::new(self)
     }
 
+    /// Fold the values of a vector into an accumulator, from front to back.
+    ///
+    /// This walks the leaf nodes directly rather than going through an
+    /// iterator, which makes it faster than `iter().fold()`.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::vector::Vector;
+    /// let vec = vector![1, 2, 3];
+    /// assert_eq!(6, vec.fold(0, |sum, value| sum + value));
+    /// ```
+    pub fn fold<'a, B, F>(&'a self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, &'a A) -> B,
+    {
+        match self.fold_while(init, &mut |acc, value| Ok(f(acc, value))) {
+            Ok(acc) | Err(acc) => acc,
+        }
+    }
+
+    /// Combine the values of a vector from front to back using `f`, or
+    /// return `None` if the vector is empty.
+    ///
+    /// Time: O(n)
+    pub fn reduce<F>(&self, mut f: F) -> Option<A>
+    where
+        A: Clone,
+        F: FnMut(A, &A) -> A,
+    {
+        self.fold(None, |acc, value| match acc {
+            None => Some(value.clone()),
+            Some(acc) => Some(f(acc, value)),
+        })
+    }
+
+    /// Test whether any value in a vector satisfies a predicate.
+    ///
+    /// Time: O(n)
+    pub fn any<F>(&self, mut f: F) -> bool
+    where
+        F: FnMut(&A) -> bool,
+    {
+        self.find(|value| f(value)).is_some()
+    }
+
+    /// Test whether every value in a vector satisfies a predicate.
+    ///
+    /// Time: O(n)
+    pub fn all<F>(&self, mut f: F) -> bool
+    where
+        F: FnMut(&A) -> bool,
+    {
+        !self.any(|value| !f(value))
+    }
+
+    /// Find the first value in a vector which satisfies a predicate.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::vector::Vector;
+    /// let vec = vector![1, 2, 3];
+    /// assert_eq!(Some(&2), vec.find(|value| value % 2 == 0));
+    /// assert_eq!(None, vec.find(|value| *value > 3));
+    /// ```
+    #[must_use]
+    pub fn find<F>(&self, mut f: F) -> Option<&A>
+    where
+        F: FnMut(&A) -> bool,
+    {
+        match self.fold_while(None, &mut |_, value| {
+            if f(value) {
+                Err(Some(value))
+            } else {
+                Ok(None)
+            }
+        }) {
+            Ok(_) => None,
+            Err(found) => found,
+        }
+    }
+
+    fn fold_while<'a, B, F>(&'a self, init: B, f: &mut F) -> Result<B, B>
+    where
+        F: FnMut(B, &'a A) -> Result<B, B>,
+    {
+        let mut acc = init;
+        for chunk in self.leaves() {
+            for value in chunk {
+                acc = f(acc, value)?;
+            }
+        }
+        Ok(acc)
+    }
+
     /// Construct a [`Focus`][Focus] for a vector.
     ///
+    /// A focus remembers the path to the leaf it last looked at, which
+    /// makes it much faster than [`get`][get] for a large number of
+    /// index lookups which are close to each other.
+    ///
     /// Time: O(1)
     ///
+    /// [get]: #method.get
     /// [Focus]: enum.Focus.html
     #[inline]
     #[must_use]
@@ -644,6 +869,72 @@ impl<A: Clone> Vector<A> {
         self.back()
     }
 
+    /// Get a vector of all but the first element of a vector.
+    ///
+    /// If the vector is empty, `None` is returned.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::Vector;
+    /// assert_eq!(Some(vector![2, 3]), vector![1, 2, 3].tail());
+    /// assert_eq!(None, Vector::<i32>::new().tail());
+    /// ```
+    #[must_use]
+    pub fn tail(&self) -> Option<Self> {
+        self.uncons().map(|(_, tail)| tail)
+    }
+
+    /// Split a vector into its first element and the rest of the
+    /// vector.
+    ///
+    /// If the vector is empty, `None` is returned.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::Vector;
+    /// fn sum(vec: &Vector<i32>) -> i32 {
+    ///     match vec.uncons() {
+    ///         None => 0,
+    ///         Some((head, tail)) => head + sum(&tail),
+    ///     }
+    /// }
+    /// assert_eq!(6, sum(&vector![1, 2, 3]));
+    /// ```
+    #[must_use]
+    pub fn uncons(&self) -> Option<(A, Self)> {
+        let mut tail = self.clone();
+        tail.pop_front().map(|head| (head, tail))
+    }
+
+    /// Split a vector into the vector of all but its last element and
+    /// its last element.
+    ///
+    /// If the vector is empty, `None` is returned.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::Vector;
+    /// assert_eq!(Some((vector![1, 2], 3)), vector![1, 2, 3].unsnoc());
+    /// assert_eq!(None, Vector::<i32>::new().unsnoc());
+    /// ```
+    #[must_use]
+    pub fn unsnoc(&self) -> Option<(Self, A)> {
+        let mut init = self.clone();
+        init.pop_back().map(|last| (init, last))
+    }
+
     /// Get the index of a given element in the vector.
     ///
     /// Searches the vector for the first occurrence of a given value,
@@ -822,6 +1113,68 @@ impl<A: Clone> Vector<A> {
         }
     }
 
+    /// Construct a vector from an iterator of fallible values.
+    ///
+    /// The values are pushed onto the end of the vector in the order
+    /// the iterator produces them. If the iterator produces an error,
+    /// iteration stops and the error is returned.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::vector::Vector;
+    /// let parsed = Vector::try_from_iter(vec!["1", "2", "3"].into_iter().map(str::parse::<i32>));
+    /// assert_eq!(Ok(vector![1, 2, 3]), parsed);
+    ///
+    /// let failed = Vector::try_from_iter(vec!["1", "x"].into_iter().map(str::parse::<i32>));
+    /// assert!(failed.is_err());
+    /// ```
+    pub fn try_from_iter<I, E>(iter: I) -> Result<Self, E>
+    where
+        I: IntoIterator<Item = Result<A, E>>,
+    {
+        let mut seq = Self::new();
+        for item in iter {
+            seq.push_back(item?);
+        }
+        Ok(seq)
+    }
+
+    /// Construct a vector from an iterator, in the reverse of the order
+    /// the iterator produces its values.
+    ///
+    /// Collecting an iterator into a vector with
+    /// [`FromIterator`][FromIterator] keeps the order of its values.
+    /// This pushes each value onto the front of the vector instead, for
+    /// when you want the reverse order and the iterator can't be
+    /// reversed.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::vector::Vector;
+    /// let words = "one two three".split_whitespace();
+    /// assert_eq!(vector!["three", "two", "one"], Vector::from_iter_rev(words));
+    /// ```
+    ///
+    /// [FromIterator]: https://doc.rust-lang.org/std/iter/trait.FromIterator.html
+    pub fn from_iter_rev<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = A>,
+    {
+        let mut seq = Self::new();
+        for item in iter {
+            seq.push_front(item);
+        }
+        seq
+    }
+
     /// Create a new vector with the value at index `index` updated.
     ///
     /// Panics if the index is out of bounds.
@@ -1087,6 +1440,43 @@ impl<A: Clone> Vector<A> {
         self.append(other)
     }
 
+    /// Concatenate a sequence of vectors into a single vector.
+    ///
+    /// Rather than appending each vector to the result in turn, this
+    /// appends neighbouring vectors pairwise, then neighbouring pairs,
+    /// and so on, which keeps the resulting tree shallow and balanced
+    /// when joining a large number of vectors.
+    ///
+    /// Time: O(m log n) where m is the number of vectors
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::vector::Vector;
+    /// let vecs = vec![vector![1, 2], vector![3], Vector::new(), vector![4, 5]];
+    /// assert_eq!(vector![1, 2, 3, 4, 5], Vector::concat_all(vecs));
+    /// ```
+    #[must_use]
+    pub fn concat_all<I>(vectors: I) -> Self
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        let mut level: Vec<Self> = vectors.into_iter().filter(|v| !v.is_empty()).collect();
+        while level.len() > 1 {
+            let mut next = Vec::with_capacity(level.len() / 2 + 1);
+            let mut it = level.into_iter();
+            while let Some(mut left) = it.next() {
+                if let Some(right) = it.next() {
+                    left.append(right);
+                }
+                next.push(left);
+            }
+            level = next;
+        }
+        level.pop().unwrap_or_default()
+    }
+
     /// Retain only the elements specified by the predicate.
     ///
     /// Remove all elements for which the provided function `f`
@@ -1114,6 +1504,37 @@ impl<A: Clone> Vector<A> {
         }
     }
 
+    /// Remove the elements which satisfy a predicate from a vector, and
+    /// return them in a new vector, in their original order.
+    ///
+    /// Time: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::vector::Vector;
+    /// let mut vec = vector![1, 2, 3, 4, 5];
+    /// let evens = vec.drain_filter(|v| v % 2 == 0);
+    /// assert_eq!(vector![1, 3, 5], vec);
+    /// assert_eq!(vector![2, 4], evens);
+    /// ```
+    pub fn drain_filter<F>(&mut self, mut f: F) -> Self
+    where
+        F: FnMut(&A) -> bool,
+    {
+        let mut removed = Vector::new();
+        self.retain(|value| {
+            if f(value) {
+                removed.push_back(value.clone());
+                false
+            } else {
+                true
+            }
+        });
+        removed
+    }
+
     /// Split a vector at a given index.
     ///
     /// Split a vector at a given index, consuming the vector and
@@ -1350,6 +1771,190 @@ impl<A: Clone> Vector<A> {
         middle
     }
 
+    /// Construct a new vector with a range of elements removed.
+    ///
+    /// Panics if the range is out of bounds.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::vector::Vector;
+    /// let vec = vector![1, 2, 3, 4, 5];
+    /// assert_eq!(vector![1, 4, 5], vec.remove_range(1..3));
+    /// assert_eq!(vector![1, 2], vec.remove_range(2..));
+    /// ```
+    #[must_use]
+    pub fn remove_range<R>(&self, range: R) -> Self
+    where
+        R: RangeBounds<usize>,
+    {
+        self.extract_range(range).0
+    }
+
+    /// Construct a new vector with a range of elements removed, and
+    /// return it along with a vector of the removed elements.
+    ///
+    /// Panics if the range is out of bounds.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::vector::Vector;
+    /// let vec = vector![1, 2, 3, 4, 5];
+    /// let (remaining, removed) = vec.extract_range(1..3);
+    /// assert_eq!(vector![1, 4, 5], remaining);
+    /// assert_eq!(vector![2, 3], removed);
+    /// ```
+    #[must_use]
+    pub fn extract_range<R>(&self, range: R) -> (Self, Self)
+    where
+        R: RangeBounds<usize>,
+    {
+        let r = to_range(&range, self.len());
+        if r.start > r.end || r.end > self.len() {
+            panic!(
+                "Vector::extract_range: range {}..{} out of bounds for length {}",
+                r.start,
+                r.end,
+                self.len()
+            );
+        }
+        let mut remaining = self.clone();
+        let mut removed = remaining.split_off(r.start);
+        let right = removed.split_off(r.end - r.start);
+        remaining.append(right);
+        (remaining, removed)
+    }
+
+    /// Replace a range of a vector with the contents of an iterator.
+    ///
+    /// The elements in `range` are removed from the current vector
+    /// and returned as a new vector, and the values produced by
+    /// `replace_with` are inserted in their place. The replacement
+    /// doesn't need to be the same length as the range.
+    ///
+    /// Panics if the range is out of bounds.
+    ///
+    /// Time: O(log n + m) where m is the number of inserted values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::vector::Vector;
+    /// let mut vec = vector![1, 2, 3, 4, 5];
+    /// let removed = vec.splice(1..3, vec![7, 8, 9]);
+    /// assert_eq!(vector![1, 7, 8, 9, 4, 5], vec);
+    /// assert_eq!(vector![2, 3], removed);
+    /// ```
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Self
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator<Item = A>,
+    {
+        let r = to_range(&range, self.len());
+        if r.start > r.end || r.end > self.len() {
+            panic!(
+                "Vector::splice: range {}..{} out of bounds for length {}",
+                r.start,
+                r.end,
+                self.len()
+            );
+        }
+        let mut removed = self.split_off(r.start);
+        let right = removed.split_off(r.end - r.start);
+        self.extend(replace_with);
+        self.append(right);
+        removed
+    }
+
+    /// Construct a vector consisting of `count` copies of the current
+    /// vector, concatenated.
+    ///
+    /// The result is built by repeatedly appending the vector to
+    /// itself, so the copies share their tree nodes rather than
+    /// duplicating the elements.
+    ///
+    /// Time: O(log n * log count)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::vector::Vector;
+    /// let vec = vector![1, 2, 3];
+    /// assert_eq!(vector![1, 2, 3, 1, 2, 3, 1, 2, 3], vec.repeat_self(3));
+    /// assert_eq!(Vector::<i32>::new(), vec.repeat_self(0));
+    /// ```
+    #[must_use]
+    pub fn repeat_self(&self, count: usize) -> Self {
+        let mut out = Self {
+            vector: Inline(self.pool().clone(), InlineArray::new()),
+        };
+        if self.is_empty() {
+            return out;
+        }
+        let mut block = self.clone();
+        let mut remaining = count;
+        while remaining > 0 {
+            if remaining & 1 == 1 {
+                out.append(block.clone());
+            }
+            remaining >>= 1;
+            if remaining > 0 {
+                let copy = block.clone();
+                block.append(copy);
+            }
+        }
+        out
+    }
+
+    /// Append a copy of a range of the current vector to its end.
+    ///
+    /// The copied range shares its tree nodes with the original
+    /// rather than cloning each element.
+    ///
+    /// Panics if the range is out of bounds.
+    ///
+    /// Time: O(log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::vector::Vector;
+    /// let mut vec = vector![1, 2, 3, 4];
+    /// vec.extend_from_within(1..3);
+    /// assert_eq!(vector![1, 2, 3, 4, 2, 3], vec);
+    /// ```
+    pub fn extend_from_within<R>(&mut self, range: R)
+    where
+        R: RangeBounds<usize>,
+    {
+        let r = to_range(&range, self.len());
+        if r.start > r.end || r.end > self.len() {
+            panic!(
+                "Vector::extend_from_within: range {}..{} out of bounds for length {}",
+                r.start,
+                r.end,
+                self.len()
+            );
+        }
+        if r.start == r.end {
+            return;
+        }
+        let mut copy = self.clone();
+        copy.truncate(r.end);
+        let copy = copy.split_off(r.start);
+        self.append(copy);
+    }
+
     /// Insert an element into a vector.
     ///
     /// Insert an element at position `index`, shifting all elements
@@ -1796,7 +2401,7 @@ impl<A: Clone> Sum for Vector<A> {
     where
         I: Iterator<Item = Self>,
     {
-        it.fold(Self::new(), |a, b| a + b)
+        Self::concat_all(it)
     }
 }
 
@@ -1941,13 +2546,191 @@ impl<'a, A: Clone> From<&'a Vec<A>> for Vector<A> {
     }
 }
 
+impl<A: Clone> From<VecDeque<A>> for Vector<A> {
+    /// Create a vector from a [`std::collections::VecDeque`][vecdeque],
+    /// keeping the queue's front-to-back order.
+    ///
+    /// Time: O(n)
+    ///
+    /// [vecdeque]: https://doc.rust-lang.org/std/collections/struct.VecDeque.html
+    fn from(deque: VecDeque<A>) -> Self {
+        deque.into_iter().collect()
+    }
+}
+
+impl<A: Clone> From<&VecDeque<A>> for Vector<A> {
+    /// Create a vector from a [`std::collections::VecDeque`][vecdeque],
+    /// keeping the queue's front-to-back order.
+    ///
+    /// Time: O(n)
+    ///
+    /// [vecdeque]: https://doc.rust-lang.org/std/collections/struct.VecDeque.html
+    fn from(deque: &VecDeque<A>) -> Self {
+        deque.iter().cloned().collect()
+    }
+}
+
+impl<A: Clone> From<Vector<A>> for VecDeque<A> {
+    /// Create a [`std::collections::VecDeque`][vecdeque] from a vector,
+    /// with the vector's first element at the front of the queue.
+    ///
+    /// Time: O(n)
+    ///
+    /// [vecdeque]: https://doc.rust-lang.org/std/collections/struct.VecDeque.html
+    fn from(vector: Vector<A>) -> Self {
+        vector.into_iter().collect()
+    }
+}
+
+impl<A: Clone> From<Vector<A>> for Vec<A> {
+    /// Create a [`std::vec::Vec`][vec] from a vector.
+    ///
+    /// Time: O(n)
+    ///
+    /// [vec]: https://doc.rust-lang.org/std/vec/struct.Vec.html
+    fn from(vector: Vector<A>) -> Self {
+        vector.into_iter().collect()
+    }
+}
+
+impl io::Write for Vector<u8> {
+    /// Append a buffer of bytes to the back of the vector.
+    ///
+    /// This never fails, and always writes the whole buffer.
+    ///
+    /// Time: O(m) where m is the length of the buffer
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.extend(buf.iter().cloned());
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// Reversed view
+
+/// A view of a [`Vector`][Vector] in reverse order.
+///
+/// This is created by [`Vector::reversed()`][reversed].
+///
+/// [Vector]: ./struct.Vector.html
+/// [reversed]: ./struct.Vector.html#method.reversed
+pub struct Reversed<'a, A> {
+    vector: &'a Vector<A>,
+}
+
+impl<'a, A: Clone> Reversed<'a, A> {
+    /// Get the vector this is a view of.
+    #[must_use]
+    pub fn as_vector(&self) -> &'a Vector<A> {
+        self.vector
+    }
+
+    /// Test whether the vector is empty.
+    ///
+    /// Time: O(1)
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.vector.is_empty()
+    }
+
+    /// Get the length of the vector.
+    ///
+    /// Time: O(1)
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.vector.len()
+    }
+
+    /// Get a reference to the value at index `index` counting from the
+    /// back of the vector.
+    ///
+    /// Returns `None` if the index is out of bounds.
+    ///
+    /// Time: O(log n)
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&'a A> {
+        if index >= self.len() {
+            return None;
+        }
+        self.vector.get(self.len() - 1 - index)
+    }
+
+    /// Get the first element in reverse order, which is the last
+    /// element of the vector.
+    ///
+    /// Time: O(log n)
+    #[must_use]
+    pub fn front(&self) -> Option<&'a A> {
+        self.vector.back()
+    }
+
+    /// Get the last element in reverse order, which is the first
+    /// element of the vector.
+    ///
+    /// Time: O(log n)
+    #[must_use]
+    pub fn back(&self) -> Option<&'a A> {
+        self.vector.front()
+    }
+
+    /// Get an iterator over the vector from back to front.
+    pub fn iter(&self) -> Rev<Iter<'a, A>> {
+        self.vector.iter().rev()
+    }
+}
+
+impl<'a, A> Clone for Reversed<'a, A> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, A> Copy for Reversed<'a, A> {}
+
+impl<'a, A: Clone + Debug> Debug for Reversed<'a, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, A: Clone> Index<usize> for Reversed<'a, A> {
+    type Output = A;
+
+    /// Get a reference to the value at index `index` counting from the
+    /// back of the vector.
+    ///
+    /// Time: O(log n)
+    fn index(&self, index: usize) -> &Self::Output {
+        match self.get(index) {
+            Some(value) => value,
+            None => panic!(
+                "Reversed::index: index out of bounds: {} < {}",
+                index,
+                self.len()
+            ),
+        }
+    }
+}
+
+impl<'a, A: Clone> IntoIterator for Reversed<'a, A> {
+    type Item = &'a A;
+    type IntoIter = Rev<Iter<'a, A>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 // Iterators
 
 /// An iterator over vectors with values of type `A`.
 ///
 /// To obtain one, use [`Vector::iter()`][iter].
 ///
-/// [iter]: enum.Vector.html#method.iter
+/// [iter]: struct.Vector.html#method.iter
 pub struct Iter<'a, A> {
     focus: Focus<'a, A>,
     front_index: usize,
@@ -2014,11 +2797,21 @@ impl<'a, A: Clone> ExactSizeIterator for Iter<'a, A> {}
 
 impl<'a, A: Clone> FusedIterator for Iter<'a, A> {}
 
+impl<'a, A: Clone> Clone for Iter<'a, A> {
+    fn clone(&self) -> Self {
+        Iter {
+            focus: self.focus.clone(),
+            front_index: self.front_index,
+            back_index: self.back_index,
+        }
+    }
+}
+
 /// A mutable iterator over vectors with values of type `A`.
 ///
 /// To obtain one, use [`Vector::iter_mut()`][iter_mut].
 ///
-/// [iter_mut]: enum.Vector.html#method.iter_mut
+/// [iter_mut]: struct.Vector.html#method.iter_mut
 pub struct IterMut<'a, A> {
     focus: FocusMut<'a, A>,
     front_index: usize,
@@ -2140,7 +2933,7 @@ impl<A: Clone> FusedIterator for ConsumingIter<A> {}
 ///
 /// To obtain one, use [`Vector::chunks()`][chunks].
 ///
-/// [chunks]: enum.Vector.html#method.chunks
+/// [chunks]: struct.Vector.html#method.chunks
 pub struct Chunks<'a, A> {
     focus: Focus<'a, A>,
     front_index: usize,
@@ -2198,7 +2991,7 @@ impl<'a, A: Clone> FusedIterator for Chunks<'a, A> {}
 ///
 /// To obtain one, use [`Vector::chunks_mut()`][chunks_mut].
 ///
-/// [chunks_mut]: enum.Vector.html#method.chunks_mut
+/// [chunks_mut]: struct.Vector.html#method.chunks_mut
 pub struct ChunksMut<'a, A> {
     focus: FocusMut<'a, A>,
     front_index: usize,
@@ -2521,6 +3314,165 @@ mod test {
         assert!(!huge.ptr_eq(&huge2));
     }
 
+    #[test]
+    fn splice() {
+        let mut vec = Vector::from_iter(0..10_000);
+        let removed = vec.splice(100..120, vec![-1; 5]);
+        vec.assert_invariants();
+        assert_eq!(Vector::from_iter(100..120), removed);
+        assert_eq!(9985, vec.len());
+        assert_eq!(Some(&-1), vec.get(104));
+        assert_eq!(Some(&120), vec.get(105));
+        let removed = vec.splice(.., Vec::new());
+        assert!(vec.is_empty());
+        assert_eq!(9985, removed.len());
+    }
+
+    #[test]
+    #[should_panic]
+    fn splice_out_of_bounds() {
+        let mut vec = vector![1, 2, 3];
+        vec.splice(2..4, vec![0]);
+    }
+
+    #[test]
+    fn from_iter_order() {
+        let forward: Vector<usize> = (0..10_000).collect();
+        let backward = Vector::from_iter_rev(0..10_000);
+        forward.assert_invariants();
+        backward.assert_invariants();
+        assert!(forward.iter().cloned().eq(0..10_000));
+        assert!(backward.iter().cloned().eq((0..10_000).rev()));
+    }
+
+    #[test]
+    fn extract_range() {
+        let vec = Vector::from_iter(0..10_000);
+        let (remaining, removed) = vec.extract_range(100..5000);
+        remaining.assert_invariants();
+        removed.assert_invariants();
+        assert_eq!(Vector::from_iter((0..100).chain(5000..10_000)), remaining);
+        assert_eq!(Vector::from_iter(100..5000), removed);
+        assert_eq!(remaining, vec.remove_range(100..5000));
+        assert_eq!(Vector::from_iter(0..10_000), vec);
+        assert!(vec.remove_range(..).is_empty());
+        assert_eq!(vec, vec.remove_range(10_000..));
+    }
+
+    #[test]
+    fn clone_iter() {
+        let vec = Vector::from_iter(0..1000);
+        let mut iter = vec.iter();
+        iter.next();
+        iter.next_back();
+        let copy = iter.clone();
+        assert_eq!(iter.count(), 998);
+        assert_eq!(copy.clone().last(), Some(&998));
+        assert_eq!(vec![&998, &997], copy.rev().take(2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn write_bytes() {
+        use std::io::Write;
+        let mut buffer: Vector<u8> = Vector::from(&b"GET "[..]);
+        let request = buffer.clone();
+        write!(buffer, "/index.html HTTP/1.1\r\n").unwrap();
+        buffer.write_all(&[b'x'; 1000]).unwrap();
+        assert_eq!(b"GET ".to_vec(), Vec::from(request));
+        let (line, body) = buffer.split_at(26);
+        assert_eq!(b"GET /index.html HTTP/1.1\r\n".to_vec(), Vec::from(line));
+        assert_eq!(vec![b'x'; 1000], Vec::from(body));
+    }
+
+    #[test]
+    fn contiguous_slices() {
+        let small: Vector<usize> = (0..10).collect();
+        assert_eq!(Some(&(0..10).collect::<Vec<_>>()[..]), small.as_slice());
+        let mut vec: Vector<usize> = (0..10_000).collect();
+        assert_eq!(None, vec.as_slice());
+        assert_eq!(None, vec.make_contiguous());
+        vec.truncate(CHUNK_SIZE);
+        let snapshot = vec.clone();
+        for value in vec.make_contiguous().unwrap() {
+            *value += 1;
+        }
+        assert_eq!(
+            Some(&(1..=CHUNK_SIZE).collect::<Vec<_>>()[..]),
+            vec.as_slice()
+        );
+        assert_eq!(Vector::from_iter(0..CHUNK_SIZE), snapshot);
+    }
+
+    #[test]
+    fn internal_iteration() {
+        let vec = Vector::from_iter(0..10_000);
+        assert_eq!(
+            vec.iter().sum::<usize>(),
+            vec.fold(0, |sum, value| sum + value)
+        );
+        assert_eq!(Some(vec.iter().sum()), vec.reduce(|sum, value| sum + value));
+        assert_eq!(Some(&5000), vec.find(|value| *value >= 5000));
+        assert!(vec.all(|value| *value < 10_000));
+        assert!(!vec.any(|value| *value >= 10_000));
+        assert_eq!(
+            None,
+            Vector::<usize>::new().reduce(|sum, value| sum + value)
+        );
+    }
+
+    #[test]
+    fn concat_all() {
+        let parts: Vec<Vector<usize>> = (0..5000)
+            .map(|i| Vector::from_iter(i * 7..(i + 1) * 7))
+            .collect();
+        let vec = Vector::concat_all(parts.clone());
+        vec.assert_invariants();
+        assert_eq!(Vector::from_iter(0..35_000), vec);
+        assert_eq!(vec, parts.into_iter().sum());
+        assert_eq!(Vector::<usize>::new(), Vector::concat_all(vec![]));
+    }
+
+    #[test]
+    fn vecdeque_conversions() {
+        let mut deque: VecDeque<i32> = (0..1000).collect();
+        deque.push_front(-1);
+        let vec = Vector::from(&deque);
+        assert_eq!(Some(&-1), vec.front());
+        assert_eq!(vec, Vector::from(deque.clone()));
+        assert_eq!(deque, VecDeque::from(vec));
+    }
+
+    #[test]
+    fn repeat_self() {
+        let vec = Vector::from_iter(0..100);
+        for count in 0..20 {
+            let repeated = vec.repeat_self(count);
+            repeated.assert_invariants();
+            let expected: Vec<i32> = (0..count).flat_map(|_| 0..100).collect();
+            assert_eq!(Vector::from(expected), repeated);
+        }
+    }
+
+    #[test]
+    fn extend_from_within() {
+        let mut vec = Vector::from_iter(0..1000);
+        vec.extend_from_within(100..300);
+        vec.assert_invariants();
+        assert_eq!(1200, vec.len());
+        assert_eq!(Some(&100), vec.get(1000));
+        assert_eq!(Some(&299), vec.get(1199));
+        vec.extend_from_within(..);
+        assert_eq!(2400, vec.len());
+        assert_eq!(vec.take(1200), vec.skip(1200));
+    }
+
+    #[test]
+    #[should_panic]
+    fn extend_from_within_out_of_bounds() {
+        let mut vec = vector![1, 2, 3];
+        vec.extend_from_within(2..5);
+    }
+
     #[test]
     fn ptr_eq() {
         for len in 32..256 {
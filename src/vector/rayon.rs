@@ -1,4 +1,4 @@
-//! Parallel iterators.
+//! Parallel iterators and sorting.
 //!
 //! These are only available when using the `rayon` feature flag.
 
@@ -184,11 +184,110 @@ where
     }
 }
 
+// Vectors shorter than this are sorted on a single thread.
+const PAR_SORT_THRESHOLD: usize = 64 * CHUNK_SIZE;
+
+impl<A> Vector<A>
+where
+    A: Clone + Send + Sync,
+{
+    /// Sort a vector in parallel.
+    ///
+    /// Time: O(n log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::vector::Vector;
+    /// # use std::iter::FromIterator;
+    /// let mut vec = Vector::from_iter((0..100_000).rev());
+    /// vec.par_sort();
+    /// assert_eq!(Vector::from_iter(0..100_000), vec);
+    /// ```
+    pub fn par_sort(&mut self)
+    where
+        A: Ord,
+    {
+        self.par_sort_by(Ord::cmp)
+    }
+
+    /// Sort a vector in parallel using a comparator function.
+    ///
+    /// The vector is split in half until the halves are small enough to
+    /// sort on a single thread, and the sorted halves are merged back
+    /// together, with halves which are already in order joined without
+    /// looking at their elements.
+    ///
+    /// Time: O(n log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::vector::Vector;
+    /// # use std::iter::FromIterator;
+    /// let mut vec = Vector::from_iter(0..100_000);
+    /// vec.par_sort_by(|left, right| right.cmp(left));
+    /// assert_eq!(Vector::from_iter((0..100_000).rev()), vec);
+    /// ```
+    pub fn par_sort_by<F>(&mut self, cmp: F)
+    where
+        F: Fn(&A, &A) -> Ordering + Sync,
+    {
+        let vec = std::mem::take(self);
+        *self = par_merge_sort(vec, &cmp);
+    }
+}
+
+fn par_merge_sort<A, F>(mut vec: Vector<A>, cmp: &F) -> Vector<A>
+where
+    A: Clone + Send + Sync,
+    F: Fn(&A, &A) -> Ordering + Sync,
+{
+    if vec.len() <= PAR_SORT_THRESHOLD {
+        vec.sort_by(cmp);
+        return vec;
+    }
+    let index = vec.len() / 2;
+    let (left, right) = vec.split_at(index);
+    let (left, right) = ::rayon::join(|| par_merge_sort(left, cmp), || par_merge_sort(right, cmp));
+    merge_sorted(left, right, cmp)
+}
+
+fn merge_sorted<A, F>(mut left: Vector<A>, right: Vector<A>, cmp: &F) -> Vector<A>
+where
+    A: Clone,
+    F: Fn(&A, &A) -> Ordering,
+{
+    match (left.back(), right.front()) {
+        (Some(last), Some(first)) if cmp(last, first) != Ordering::Greater => {
+            left.append(right);
+            return left;
+        }
+        _ => {}
+    }
+    let mut out = Vector::new();
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+    while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+        if cmp(r, l) == Ordering::Less {
+            out.push_back(right.next().unwrap());
+        } else {
+            out.push_back(left.next().unwrap());
+        }
+    }
+    out.extend(left);
+    out.extend(right);
+    out
+}
+
 #[cfg(test)]
 mod test {
     use super::super::*;
     use super::proptest::vector;
-    use ::proptest::num::i32;
+    use ::proptest::collection;
+    use ::proptest::num::{i16, i32};
     use ::proptest::proptest;
     use ::rayon::iter::{IntoParallelRefIterator, IntoParallelRefMutIterator, ParallelIterator};
 
@@ -198,6 +297,16 @@ mod test {
             assert_eq!(input.iter().max(), input.par_iter().max())
         }
 
+        #[test]
+        fn par_sort(ref input in collection::vec(i16::ANY, 0..20_000)) {
+            let mut vec: Vector<i16> = input.iter().cloned().collect();
+            vec.par_sort();
+            vec.assert_invariants();
+            let mut expected = input.clone();
+            expected.sort();
+            assert_eq!(Vector::from(expected), vec);
+        }
+
         #[test]
         fn par_mut_iter(ref mut input in vector(i32::ANY, 0..10000)) {
             let mut vec = input.clone();
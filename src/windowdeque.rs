@@ -0,0 +1,493 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! A persistent deque which keeps track of an aggregate of its
+//! contents.
+//!
+//! A [`WindowDeque`][WindowDeque] is a double ended queue which
+//! maintains a running aggregate, such as a sum, a minimum or a
+//! maximum, over the values in it, described by a [`Monoid`][Monoid].
+//! The aggregate can be queried in O(1) time, and is kept up to date as
+//! values are pushed and popped, which makes it a good fit for
+//! computing sliding window aggregates over a stream.
+//!
+//! It's implemented as a pair of stacks, each of which stores the
+//! aggregate of every value beneath each value, so that pushing and
+//! popping are O(1) amortised.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[macro_use] extern crate im;
+//! # use im::windowdeque::{Max, WindowDeque};
+//! let mut window: WindowDeque<i32, Max> = WindowDeque::new();
+//! let mut maxima = Vec::new();
+//! for value in vec![3, 1, 4, 1, 5, 9, 2, 6] {
+//!     window.push_back(value);
+//!     if window.len() > 3 {
+//!         window.pop_front();
+//!     }
+//!     maxima.push(window.aggregate());
+//! }
+//! assert_eq!(vec![3, 3, 4, 4, 5, 9, 9, 9], maxima);
+//! ```
+//!
+//! [WindowDeque]: struct.WindowDeque.html
+//! [Monoid]: trait.Monoid.html
+
+use std::fmt::{Debug, Error, Formatter};
+use std::iter::{Chain, FromIterator, FusedIterator, Rev};
+use std::marker::PhantomData;
+use std::ops::Add;
+
+use crate::vector::{Iter as VectorIter, Vector};
+
+/// An associative operation with an identity element, used to
+/// aggregate the values in a [`WindowDeque`][WindowDeque].
+///
+/// `combine` must be associative, but it doesn't need to be
+/// commutative: the `left` argument always comes before the `right`
+/// argument in the deque.
+///
+/// [WindowDeque]: struct.WindowDeque.html
+pub trait Monoid<A> {
+    /// The identity element, which leaves any value unchanged when
+    /// combined with it.
+    fn empty() -> A;
+
+    /// Combine two values.
+    fn combine(left: &A, right: &A) -> A;
+}
+
+/// The monoid of values under addition, with `Default::default()` as
+/// the identity element.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Sum;
+
+impl<A> Monoid<A> for Sum
+where
+    A: Clone + Default + Add<Output = A>,
+{
+    fn empty() -> A {
+        A::default()
+    }
+
+    fn combine(left: &A, right: &A) -> A {
+        left.clone() + right.clone()
+    }
+}
+
+/// The monoid of values under `min`, with the largest value of the
+/// type as the identity element.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Min;
+
+/// The monoid of values under `max`, with the smallest value of the
+/// type as the identity element.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Max;
+
+macro_rules! impl_bounded_monoids {
+    ($($t:ty),*) => {
+        $(
+            impl Monoid<$t> for Min {
+                #[inline]
+                fn empty() -> $t {
+                    <$t>::MAX
+                }
+
+                #[inline]
+                fn combine(left: &$t, right: &$t) -> $t {
+                    std::cmp::min(*left, *right)
+                }
+            }
+
+            impl Monoid<$t> for Max {
+                #[inline]
+                fn empty() -> $t {
+                    <$t>::MIN
+                }
+
+                #[inline]
+                fn combine(left: &$t, right: &$t) -> $t {
+                    std::cmp::max(*left, *right)
+                }
+            }
+        )*
+    };
+}
+
+impl_bounded_monoids!(u8, u16, u32, u64, u128, usize);
+impl_bounded_monoids!(i8, i16, i32, i64, i128, isize);
+
+/// A persistent double ended queue which keeps track of an aggregate
+/// of its contents.
+///
+/// The aggregate is computed using the monoid `M`, and can be queried
+/// in O(1) time using [`aggregate()`][aggregate].
+///
+/// Pushing and popping are O(1) amortised. Popping from a side of the
+/// deque which has run out of values redistributes the values from the
+/// other side, which takes O(n) time, so if you keep popping from the
+/// same copy of a deque shared between several owners, the amortised
+/// bounds don't hold.
+///
+/// [aggregate]: #method.aggregate
+pub struct WindowDeque<A, M> {
+    // Both sides are stacks with their tops at the back of the
+    // vector, so the top of `front` is the first value in the deque
+    // and the top of `back` is the last. Each value is stored along
+    // with the aggregate of itself and every value beneath it.
+    front: Vector<(A, A)>,
+    back: Vector<(A, A)>,
+    monoid: PhantomData<M>,
+}
+
+impl<A, M> WindowDeque<A, M>
+where
+    A: Clone,
+    M: Monoid<A>,
+{
+    /// Construct an empty deque.
+    #[must_use]
+    pub fn new() -> Self {
+        WindowDeque {
+            front: Vector::new(),
+            back: Vector::new(),
+            monoid: PhantomData,
+        }
+    }
+
+    /// Test whether a deque is empty.
+    ///
+    /// Time: O(1)
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the length of a deque.
+    ///
+    /// Time: O(1)
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    /// Get the aggregate of all the values in a deque, combined from
+    /// front to back, or the monoid's identity element if the deque is
+    /// empty.
+    ///
+    /// Time: O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate im;
+    /// # use im::windowdeque::{Sum, WindowDeque};
+    /// let mut window: WindowDeque<i32, Sum> = WindowDeque::new();
+    /// assert_eq!(0, window.aggregate());
+    /// window.push_back(2);
+    /// window.push_front(1);
+    /// window.push_back(3);
+    /// assert_eq!(6, window.aggregate());
+    /// ```
+    #[must_use]
+    pub fn aggregate(&self) -> A {
+        match (self.front.last(), self.back.last()) {
+            (None, None) => M::empty(),
+            (Some((_, front)), None) => front.clone(),
+            (None, Some((_, back))) => back.clone(),
+            (Some((_, front)), Some((_, back))) => M::combine(front, back),
+        }
+    }
+
+    /// Get the first value in a deque.
+    ///
+    /// Time: O(1)
+    #[must_use]
+    pub fn front(&self) -> Option<&A> {
+        match self.front.last() {
+            Some((value, _)) => Some(value),
+            None => self.back.front().map(|(value, _)| value),
+        }
+    }
+
+    /// Get the last value in a deque.
+    ///
+    /// Time: O(1)
+    #[must_use]
+    pub fn back(&self) -> Option<&A> {
+        match self.back.last() {
+            Some((value, _)) => Some(value),
+            None => self.front.front().map(|(value, _)| value),
+        }
+    }
+
+    /// Push a value to the front of a deque.
+    ///
+    /// Time: O(1)*
+    pub fn push_front(&mut self, value: A) {
+        let aggregate = match self.front.last() {
+            None => value.clone(),
+            Some((_, below)) => M::combine(&value, below),
+        };
+        self.front.push_back((value, aggregate));
+    }
+
+    /// Push a value to the back of a deque.
+    ///
+    /// Time: O(1)*
+    pub fn push_back(&mut self, value: A) {
+        let aggregate = match self.back.last() {
+            None => value.clone(),
+            Some((_, below)) => M::combine(below, &value),
+        };
+        self.back.push_back((value, aggregate));
+    }
+
+    /// Remove the first value from a deque and return it.
+    ///
+    /// Time: O(1)*
+    pub fn pop_front(&mut self) -> Option<A> {
+        if self.front.is_empty() {
+            let len = self.back.len();
+            self.rebalance(len - len / 2);
+        }
+        self.front.pop_back().map(|(value, _)| value)
+    }
+
+    /// Remove the last value from a deque and return it.
+    ///
+    /// Time: O(1)*
+    pub fn pop_back(&mut self) -> Option<A> {
+        if self.back.is_empty() {
+            let len = self.front.len();
+            self.rebalance(len / 2);
+        }
+        self.back.pop_back().map(|(value, _)| value)
+    }
+
+    /// Get an iterator over the values in a deque, from front to back.
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_, A> {
+        Iter {
+            it: self.front.iter().rev().chain(self.back.iter()),
+        }
+    }
+
+    /// Redistribute the values in the deque so that the first
+    /// `front_len` of them are on the front stack.
+    fn rebalance(&mut self, front_len: usize) {
+        let values: Vec<A> = self.iter().cloned().collect();
+        self.front = Vector::new();
+        self.back = Vector::new();
+        for value in values[..front_len].iter().rev() {
+            self.push_front(value.clone());
+        }
+        for value in &values[front_len..] {
+            self.push_back(value.clone());
+        }
+    }
+}
+
+// Core traits
+
+impl<A: Clone, M> Clone for WindowDeque<A, M> {
+    /// Clone a deque.
+    ///
+    /// Time: O(1)
+    fn clone(&self) -> Self {
+        WindowDeque {
+            front: self.front.clone(),
+            back: self.back.clone(),
+            monoid: PhantomData,
+        }
+    }
+}
+
+impl<A, M> Default for WindowDeque<A, M>
+where
+    A: Clone,
+    M: Monoid<A>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A, M> Debug for WindowDeque<A, M>
+where
+    A: Clone + Debug,
+    M: Monoid<A>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<A, M> PartialEq for WindowDeque<A, M>
+where
+    A: Clone + PartialEq,
+    M: Monoid<A>,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<A, M> Eq for WindowDeque<A, M>
+where
+    A: Clone + Eq,
+    M: Monoid<A>,
+{
+}
+
+impl<A, M> Extend<A> for WindowDeque<A, M>
+where
+    A: Clone,
+    M: Monoid<A>,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = A>,
+    {
+        for value in iter {
+            self.push_back(value);
+        }
+    }
+}
+
+impl<A, M> FromIterator<A> for WindowDeque<A, M>
+where
+    A: Clone,
+    M: Monoid<A>,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = A>,
+    {
+        let mut deque = Self::new();
+        deque.extend(iter);
+        deque
+    }
+}
+
+impl<'a, A, M> IntoIterator for &'a WindowDeque<A, M>
+where
+    A: Clone,
+    M: Monoid<A>,
+{
+    type Item = &'a A;
+    type IntoIter = Iter<'a, A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// Iterators
+
+/// An iterator over the values in a deque, from front to back.
+pub struct Iter<'a, A> {
+    it: Chain<Rev<VectorIter<'a, (A, A)>>, VectorIter<'a, (A, A)>>,
+}
+
+impl<'a, A: Clone> Iterator for Iter<'a, A> {
+    type Item = &'a A;
+
+    /// Advance the iterator and return the next value.
+    ///
+    /// Time: O(1)*
+    fn next(&mut self) -> Option<Self::Item> {
+        self.it.next().map(|(value, _)| value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+impl<'a, A: Clone> DoubleEndedIterator for Iter<'a, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.it.next_back().map(|(value, _)| value)
+    }
+}
+
+impl<'a, A: Clone> ExactSizeIterator for Iter<'a, A> {}
+
+impl<'a, A: Clone> FusedIterator for Iter<'a, A> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::proptest::collection;
+    use ::proptest::num::{i16, u8};
+    use ::proptest::proptest;
+    use std::collections::VecDeque;
+
+    /// A monoid which isn't commutative, to check that values are
+    /// combined in order.
+    struct Concat;
+
+    impl Monoid<Vec<i16>> for Concat {
+        fn empty() -> Vec<i16> {
+            Vec::new()
+        }
+
+        fn combine(left: &Vec<i16>, right: &Vec<i16>) -> Vec<i16> {
+            left.iter().chain(right.iter()).cloned().collect()
+        }
+    }
+
+    #[test]
+    fn aggregates_of_empty_deques() {
+        assert_eq!(0, WindowDeque::<i32, Sum>::new().aggregate());
+        assert_eq!(i32::MAX, WindowDeque::<i32, Min>::new().aggregate());
+        assert_eq!(i32::MIN, WindowDeque::<i32, Max>::new().aggregate());
+    }
+
+    proptest! {
+        #[test]
+        fn matches_vecdeque(ref actions in collection::vec((u8::ANY, i16::ANY), 0..1000)) {
+            let mut deque: WindowDeque<Vec<i16>, Concat> = WindowDeque::new();
+            let mut expected = VecDeque::new();
+            for (action, value) in actions {
+                match action % 4 {
+                    0 => {
+                        deque.push_front(vec![*value]);
+                        expected.push_front(vec![*value]);
+                    }
+                    1 => {
+                        deque.push_back(vec![*value]);
+                        expected.push_back(vec![*value]);
+                    }
+                    2 => assert_eq!(expected.pop_front(), deque.pop_front()),
+                    _ => assert_eq!(expected.pop_back(), deque.pop_back()),
+                }
+                assert_eq!(expected.len(), deque.len());
+                assert_eq!(expected.front(), deque.front());
+                assert_eq!(expected.back(), deque.back());
+                let aggregate: Vec<i16> = expected.iter().flatten().cloned().collect();
+                assert_eq!(aggregate, deque.aggregate());
+            }
+            assert!(expected.iter().eq(deque.iter()));
+        }
+
+        #[test]
+        fn sliding_window_min(ref values in collection::vec(i16::ANY, 0..1000), size in 1..50usize) {
+            let mut window: WindowDeque<i16, Min> = WindowDeque::new();
+            for (index, value) in values.iter().enumerate() {
+                window.push_back(*value);
+                if window.len() > size {
+                    window.pop_front();
+                }
+                let start = (index + 1).saturating_sub(size);
+                assert_eq!(values[start..=index].iter().min(), Some(&window.aggregate()));
+            }
+        }
+    }
+}